@@ -5,6 +5,14 @@ import { ApiPromise, WsProvider } from '@polkadot/api';
 import { EventRecord } from '@polkadot/types/interfaces';
 import { ConfigService } from '@nestjs/config';
 
+// Runtime transaction extensions unknown to @polkadot/api, needed to decode block extrinsics
+const signedExtensions = {
+    SponsoredBy: {
+        extrinsic: { sponsoredBy: 'Option<AccountId>' },
+        payload: {},
+    },
+};
+
 @Injectable()
 export class BlockchainService implements OnModuleInit, OnModuleDestroy {
     private readonly logger = new Logger(BlockchainService.name);
@@ -26,7 +34,7 @@ export class BlockchainService implements OnModuleInit, OnModuleDestroy {
 
         try {
             const provider = new WsProvider(wsUrl);
-            this.api = await ApiPromise.create({ provider });
+            this.api = await ApiPromise.create({ provider, signedExtensions });
 
             this.logger.log('Connected to Substrate node');
 
@@ -16,6 +16,15 @@ if (typeof window !== 'undefined') {
     });
 }
 
+// Runtime transaction extensions unknown to @polkadot/api. SponsoredBy names the account
+// paying the fee; it is left empty unless a sponsor is passed to signAndSend.
+const signedExtensions = {
+    SponsoredBy: {
+        extrinsic: { sponsoredBy: 'Option<AccountId>' },
+        payload: {},
+    },
+};
+
 interface Account {
     address: string;
     meta: {
@@ -38,7 +47,7 @@ export function usePolkadot() {
         const initApi = async () => {
             try {
                 const provider = new WsProvider(wsUrl);
-                const apiInstance = await ApiPromise.create({ provider });
+                const apiInstance = await ApiPromise.create({ provider, signedExtensions });
                 setApi(apiInstance);
             } catch (err: any) {
                 setError(`Failed to connect to Substrate node: ${err.message}`);
@@ -96,6 +105,7 @@ export function usePolkadot() {
     const signAndSend = async (
         extrinsic: any,
         callback?: (status: any) => void,
+        sponsoredBy?: string,
     ) => {
         if (typeof window === 'undefined') {
             throw new Error('Transaction signing is only available in browser');
@@ -118,7 +128,7 @@ export function usePolkadot() {
             // Sign and send
             await extrinsic.signAndSend(
                 selectedAccount.address,
-                { signer: injector.signer },
+                { signer: injector.signer, sponsoredBy },
                 callback,
             );
         } catch (err: any) {
@@ -7,12 +7,63 @@
 
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObject, RpcModule};
 use sc_transaction_pool_api::TransactionPool;
-use solochain_template_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use serde::{Deserialize, Serialize};
+use solochain_template_runtime::{apis::TokenBalancesApi, opaque::Block, AccountId, Balance, Nonce};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::U256;
+use sp_runtime::traits::Block as BlockT;
+
+/// Spendable balances of an account, hex encoded to keep their precision in JSON.
+#[derive(Serialize, Deserialize)]
+pub struct TokenBalances {
+	/// Native token balance
+	pub native: U256,
+	/// BICY reward token balance
+	pub bicy: U256,
+}
+
+/// Game token RPC methods.
+#[rpc(client, server)]
+pub trait BicyApi<BlockHash, AccountId> {
+	/// Spendable native and BICY balances of `who` at block `at`, or the best block.
+	#[method(name = "bicy_balances")]
+	fn balances(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<TokenBalances>;
+}
+
+/// Game token RPC backed by the `TokenBalancesApi` runtime API.
+pub struct Bicy<C> {
+	client: Arc<C>,
+}
+
+impl<C> Bicy<C> {
+	/// Create a new `Bicy` RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> BicyApiServer<<Block as BlockT>::Hash, AccountId> for Bicy<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TokenBalancesApi<Block, AccountId, Balance>,
+{
+	fn balances(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<TokenBalances> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let balances = self.client.runtime_api().balances(at, who).map_err(|e| {
+			ErrorObject::owned(1, "Unable to query token balances.", Some(e.to_string()))
+		})?;
+
+		Ok(TokenBalances { native: balances.native.into(), bicy: balances.bicy.into() })
+	}
+}
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -32,6 +83,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: TokenBalancesApi<Block, AccountId, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
@@ -42,7 +94,8 @@ where
 	let FullDeps { client, pool } = deps;
 
 	module.merge(System::new(client.clone(), pool).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Bicy::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
@@ -0,0 +1,56 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use sp_std::{vec, vec::Vec};
+
+fn name<T: Config>() -> BoundedVec<u8, T::MaxNameLength> {
+    BoundedVec::truncate_from(vec![b'a'; T::MaxNameLength::get() as usize])
+}
+
+/// Define `n` achievements unlocked by a single race
+fn create_achievements<T: Config>(n: u32) {
+    let achievements = (0..n)
+        .map(|id| Achievement {
+            id,
+            name: name::<T>(),
+            stat: Stat::Races,
+            threshold: 1,
+        })
+        .collect::<Vec<_>>();
+    Achievements::<T>::put(BoundedVec::truncate_from(achievements));
+    NextAchievementId::<T>::put(n);
+}
+
+benchmarks! {
+    create_achievement {
+        create_achievements::<T>(T::MaxAchievements::get() - 1);
+        let origin = T::AchievementOrigin::try_successful_origin()
+            .map_err(|_| "no achievement origin")?;
+    }: _<T::RuntimeOrigin>(origin, name::<T>(), Stat::Distance, 100_000)
+    verify {
+        assert_eq!(Achievements::<T>::get().len() as u32, T::MaxAchievements::get());
+    }
+
+    unlock_achievements {
+        let n in 1 .. T::MaxAchievements::get();
+        let caller: T::AccountId = whitelisted_caller();
+        // Statistics are recorded before the achievements exist, so nothing unlocks yet
+        T::UserStats::set_stats(
+            &caller,
+            pallet_user_profile::UserStats {
+                total_races: 1,
+                ..Default::default()
+            },
+        );
+        create_achievements::<T>(n);
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(T::UserStats::stats(&caller).total_races, 1);
+        assert_eq!(Badges::<T>::get(&caller).len() as u32, n);
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,273 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+pub mod runtime_api;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+use frame_support::{traits::Get, weights::Weight};
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use pallet_user_profile::{UserStats, UserStatsInspect};
+    use sp_std::vec::Vec;
+
+    pub type AchievementId = u32;
+
+    pub type AchievementOf<T> = Achievement<<T as Config>::MaxNameLength>;
+
+    pub type BadgeOf<T> = Badge<BlockNumberFor<T>>;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to define achievements
+        type AchievementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Player statistics achievements are unlocked by
+        type UserStats: UserStatsInspect<Self::AccountId>;
+        /// Largest number of achievements
+        #[pallet::constant]
+        type MaxAchievements: Get<u32>;
+        /// Maximum length of an achievement name
+        #[pallet::constant]
+        type MaxNameLength: Get<u32>;
+    }
+
+    /// Player statistic an achievement is unlocked by
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum Stat {
+        Races,
+        Wins,
+        /// Distance ridden in meters
+        Distance,
+        Rewards,
+        TournamentEntries,
+    }
+
+    /// Achievement unlocked once a statistic reaches its threshold
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(NameLimit))]
+    pub struct Achievement<NameLimit: Get<u32>> {
+        pub id: AchievementId,
+        pub name: BoundedVec<u8, NameLimit>,
+        pub stat: Stat,
+        pub threshold: u128,
+    }
+
+    /// Achievement held by an account
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Badge<BlockNumber> {
+        pub achievement_id: AchievementId,
+        /// Block the achievement was unlocked at
+        pub unlocked_at: BlockNumber,
+    }
+
+    /// Badge with the name of its achievement, as shown on a profile
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub struct BadgeInfo<BlockNumber> {
+        pub achievement_id: AchievementId,
+        pub name: Vec<u8>,
+        pub unlocked_at: BlockNumber,
+    }
+
+    #[pallet::storage]
+    pub type NextAchievementId<T> = StorageValue<_, AchievementId, ValueQuery>;
+
+    /// Defined achievements, checked whenever player statistics change
+    #[pallet::storage]
+    pub type Achievements<T: Config> =
+        StorageValue<_, BoundedVec<AchievementOf<T>, T::MaxAchievements>, ValueQuery>;
+
+    /// Badges of each account, oldest first. Badges are soulbound: they cannot be
+    /// transferred or burned.
+    #[pallet::storage]
+    pub type Badges<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<BadgeOf<T>, T::MaxAchievements>,
+        ValueQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Achievement defined
+        AchievementCreated {
+            achievement_id: AchievementId,
+            stat: Stat,
+            threshold: u128,
+        },
+        /// Achievement unlocked and its badge minted to the account
+        AchievementUnlocked {
+            who: T::AccountId,
+            achievement_id: AchievementId,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Achievement threshold must be positive
+        InvalidThreshold,
+        /// Number of achievements reached `MaxAchievements`
+        TooManyAchievements,
+        /// No new achievement was unlocked
+        NothingToUnlock,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Define an achievement unlocked once `stat` reaches `threshold`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::create_achievement())]
+        pub fn create_achievement(
+            origin: OriginFor<T>,
+            name: BoundedVec<u8, T::MaxNameLength>,
+            stat: Stat,
+            threshold: u128,
+        ) -> DispatchResult {
+            T::AchievementOrigin::ensure_origin(origin)?;
+            ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+
+            let achievement_id = NextAchievementId::<T>::get();
+            Achievements::<T>::try_mutate(|achievements| {
+                achievements
+                    .try_push(Achievement {
+                        id: achievement_id,
+                        name,
+                        stat,
+                        threshold,
+                    })
+                    .map_err(|_| Error::<T>::TooManyAchievements)
+            })?;
+            NextAchievementId::<T>::put(achievement_id.saturating_add(1));
+
+            Self::deposit_event(Event::AchievementCreated {
+                achievement_id,
+                stat,
+                threshold,
+            });
+
+            Ok(())
+        }
+
+        /// Unlock the achievements the caller's statistics already reached, such as those
+        /// defined after the threshold was crossed
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::unlock_achievements(T::MaxAchievements::get()))]
+        pub fn unlock_achievements(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let unlocked = Self::unlock(&who, &T::UserStats::stats(&who));
+            ensure!(unlocked > 0, Error::<T>::NothingToUnlock);
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Value of `stat` in `stats`
+        pub fn stat_value(stats: &UserStats, stat: Stat) -> u128 {
+            match stat {
+                Stat::Races => stats.total_races.into(),
+                Stat::Wins => stats.wins.into(),
+                Stat::Distance => stats.total_distance.into(),
+                Stat::Rewards => stats.total_rewards,
+                Stat::TournamentEntries => stats.tournament_entries.into(),
+            }
+        }
+
+        /// Mint the badges of the achievements `stats` reached that `who` does not hold yet,
+        /// and return how many were minted
+        pub(crate) fn unlock(who: &T::AccountId, stats: &UserStats) -> u32 {
+            let achievements = Achievements::<T>::get();
+            if achievements.is_empty() {
+                return 0;
+            }
+
+            let mut badges = Badges::<T>::get(who);
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut unlocked = 0;
+            for achievement in achievements {
+                if Self::stat_value(stats, achievement.stat) < achievement.threshold
+                    || badges
+                        .iter()
+                        .any(|badge| badge.achievement_id == achievement.id)
+                {
+                    continue;
+                }
+                // Badges are bounded like achievements, so there is always room
+                if badges
+                    .try_push(Badge {
+                        achievement_id: achievement.id,
+                        unlocked_at: now,
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+                unlocked += 1;
+
+                Self::deposit_event(Event::AchievementUnlocked {
+                    who: who.clone(),
+                    achievement_id: achievement.id,
+                });
+            }
+
+            if unlocked > 0 {
+                Badges::<T>::insert(who, badges);
+            }
+            unlocked
+        }
+
+        /// Badges of `who` with their achievement names
+        pub fn badges_of(who: &T::AccountId) -> Vec<BadgeInfo<BlockNumberFor<T>>> {
+            let achievements = Achievements::<T>::get();
+            Badges::<T>::get(who)
+                .into_iter()
+                .map(|badge| BadgeInfo {
+                    achievement_id: badge.achievement_id,
+                    name: achievements
+                        .iter()
+                        .find(|achievement| achievement.id == badge.achievement_id)
+                        .map(|achievement| achievement.name.to_vec())
+                        .unwrap_or_default(),
+                    unlocked_at: badge.unlocked_at,
+                })
+                .collect()
+        }
+    }
+}
+
+impl<T: Config> pallet_user_profile::OnStatsUpdated<T::AccountId> for Pallet<T> {
+    fn on_stats_updated(who: &T::AccountId, stats: &pallet_user_profile::UserStats) {
+        Self::unlock(who, stats);
+    }
+
+    fn on_stats_updated_weight() -> Weight {
+        T::WeightInfo::unlock_achievements(T::MaxAchievements::get())
+    }
+}
@@ -0,0 +1,62 @@
+use crate as pallet_achievements;
+use frame_support::{derive_impl, traits::ConstU32};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type UserProfile = pallet_user_profile::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type Achievements = pallet_achievements::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_user_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MaxUsernameLength = ConstU32<32>;
+    type MaxBioLength = ConstU32<256>;
+    type QuestProgress = ();
+    type OnStatsUpdated = Achievements;
+}
+
+impl pallet_achievements::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type AchievementOrigin = frame_system::EnsureRoot<u64>;
+    type UserStats = UserProfile;
+    type MaxAchievements = ConstU32<3>;
+    type MaxNameLength = ConstU32<32>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap()
+        .into()
+}
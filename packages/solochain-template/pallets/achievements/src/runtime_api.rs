@@ -0,0 +1,15 @@
+//! Runtime API for querying achievement badges.
+
+use crate::BadgeInfo;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait AchievementsApi<AccountId, BlockNumber>
+    where
+        AccountId: codec::Codec,
+        BlockNumber: codec::Codec,
+    {
+        /// Badges of `who` with their achievement names and the blocks they were unlocked at
+        fn badges(who: AccountId) -> Vec<BadgeInfo<BlockNumber>>;
+    }
+}
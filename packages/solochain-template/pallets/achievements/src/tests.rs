@@ -0,0 +1,144 @@
+use crate::{mock::*, Badge, BadgeInfo, Badges, Error, Event, Stat};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_user_profile::UserStatsHandler;
+use sp_runtime::DispatchError;
+
+fn create(name: &[u8], stat: Stat, threshold: u128) {
+    assert_ok!(Achievements::create_achievement(
+        RuntimeOrigin::root(),
+        BoundedVec::truncate_from(name.to_vec()),
+        stat,
+        threshold
+    ));
+}
+
+fn badge_ids(who: u64) -> Vec<u32> {
+    Badges::<Test>::get(who)
+        .iter()
+        .map(|badge| badge.achievement_id)
+        .collect()
+}
+
+#[test]
+fn create_achievement_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = BoundedVec::truncate_from(b"First Win".to_vec());
+
+        assert_noop!(
+            Achievements::create_achievement(RuntimeOrigin::signed(1), name.clone(), Stat::Wins, 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Achievements::create_achievement(RuntimeOrigin::root(), name, Stat::Wins, 0),
+            Error::<Test>::InvalidThreshold
+        );
+
+        create(b"First Win", Stat::Wins, 1);
+        System::assert_last_event(
+            Event::AchievementCreated {
+                achievement_id: 0,
+                stat: Stat::Wins,
+                threshold: 1,
+            }
+            .into(),
+        );
+
+        create(b"Century Rider", Stat::Distance, 100_000);
+        create(b"Tournament Regular", Stat::TournamentEntries, 10);
+        assert_noop!(
+            Achievements::create_achievement(
+                RuntimeOrigin::root(),
+                BoundedVec::truncate_from(b"Veteran".to_vec()),
+                Stat::Races,
+                100
+            ),
+            Error::<Test>::TooManyAchievements
+        );
+        assert_eq!(crate::Achievements::<Test>::get().len(), 3);
+    });
+}
+
+#[test]
+fn badges_unlock_when_stats_cross_thresholds() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create(b"First Win", Stat::Wins, 1);
+        create(b"Century Rider", Stat::Distance, 100_000);
+        create(b"Tournament Regular", Stat::TournamentEntries, 10);
+
+        UserProfile::record_race(&1, false, 60_000, 0);
+        assert!(badge_ids(1).is_empty());
+
+        System::set_block_number(5);
+        UserProfile::record_race(&1, true, 40_000, 0);
+        System::assert_has_event(
+            Event::AchievementUnlocked {
+                who: 1,
+                achievement_id: 1,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Badges::<Test>::get(1).into_inner(),
+            vec![
+                Badge {
+                    achievement_id: 0,
+                    unlocked_at: 5
+                },
+                Badge {
+                    achievement_id: 1,
+                    unlocked_at: 5
+                },
+            ]
+        );
+
+        System::set_block_number(9);
+        for _ in 0..10 {
+            UserProfile::record_tournament_entry(&1);
+        }
+        assert_eq!(badge_ids(1), vec![0, 1, 2]);
+
+        // Badges are only minted once
+        UserProfile::record_race(&1, true, 1_000, 0);
+        assert_eq!(Badges::<Test>::get(1)[0].unlocked_at, 5);
+        assert_eq!(
+            Achievements::badges_of(&1)[2],
+            BadgeInfo {
+                achievement_id: 2,
+                name: b"Tournament Regular".to_vec(),
+                unlocked_at: 9,
+            }
+        );
+    });
+}
+
+#[test]
+fn unlock_achievements_catches_up_on_new_achievements() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        UserProfile::record_race(&1, true, 1_000, 0);
+
+        create(b"First Win", Stat::Wins, 1);
+        assert!(badge_ids(1).is_empty());
+
+        assert_ok!(Achievements::unlock_achievements(RuntimeOrigin::signed(1)));
+        System::assert_last_event(
+            Event::AchievementUnlocked {
+                who: 1,
+                achievement_id: 0,
+            }
+            .into(),
+        );
+        assert_eq!(badge_ids(1), vec![0]);
+
+        assert_noop!(
+            Achievements::unlock_achievements(RuntimeOrigin::signed(1)),
+            Error::<Test>::NothingToUnlock
+        );
+        assert_noop!(
+            Achievements::unlock_achievements(RuntimeOrigin::signed(2)),
+            Error::<Test>::NothingToUnlock
+        );
+    });
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    sp_runtime::traits::{Hash, Saturating, Zero},
+    traits::fungible::{Inspect, Mutate},
+    BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_std::vec::Vec;
+
+fn amount<T: Config>() -> BalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(10u32.into())
+}
+
+/// Airdrop `0` of `leaves` leaves, funded and expiring at block zero
+fn fund_airdrop<T: Config>(merkle_root: T::Hash, leaves: u32) {
+    T::Currency::set_balance(&Pallet::<T>::account_id(0), amount::<T>());
+    Airdrops::<T>::insert(
+        0,
+        Airdrop {
+            merkle_root,
+            leaves,
+            total: amount::<T>(),
+            remaining: amount::<T>(),
+            expires_at: Zero::zero(),
+        },
+    );
+}
+
+benchmarks! {
+    create_airdrop {
+        T::Currency::set_balance(
+            &T::Treasury::get(),
+            amount::<T>().saturating_mul(2u32.into()),
+        );
+        let origin = T::AirdropOrigin::try_successful_origin().map_err(|_| "no airdrop origin")?;
+        let merkle_root = T::Hashing::hash_of(&0u32);
+    }: _<T::RuntimeOrigin>(origin, merkle_root, T::MaxLeaves::get(), amount::<T>(), 10u32.into())
+    verify {
+        assert!(Airdrops::<T>::contains_key(0));
+    }
+
+    claim {
+        let p in 0 .. T::MaxProofLength::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let proof = (0..p).map(|i| T::Hashing::hash_of(&i)).collect::<Vec<_>>();
+        let leaf = Pallet::<T>::leaf_hash(0, &caller, amount::<T>());
+        fund_airdrop::<T>(Pallet::<T>::root_of(leaf, &proof), 1);
+        let proof: BoundedVec<_, _> = proof.try_into().map_err(|_| "proof too long")?;
+    }: _(RawOrigin::Signed(caller.clone()), 0, 0, amount::<T>(), proof)
+    verify {
+        assert!(Pallet::<T>::is_claimed(0, 0));
+    }
+
+    close_airdrop {
+        let n in 1 .. T::MaxLeaves::get().div_ceil(LEAVES_PER_WORD);
+        fund_airdrop::<T>(T::Hashing::hash_of(&0u32), n.saturating_mul(LEAVES_PER_WORD));
+        for word in 0..n {
+            Claimed::<T>::insert(0, word, u32::MAX);
+        }
+        frame_system::Pallet::<T>::set_block_number(1u32.into());
+        let caller: T::AccountId = account("caller", 0, 0);
+    }: _(RawOrigin::Signed(caller), 0)
+    verify {
+        assert!(!Airdrops::<T>::contains_key(0));
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
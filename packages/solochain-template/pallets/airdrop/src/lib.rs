@@ -0,0 +1,312 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{AccountIdConversion, Hash, Saturating, Zero},
+        traits::{
+            fungible::{self, Inspect, Mutate},
+            tokens::Preservation,
+        },
+        PalletId,
+    };
+    use frame_system::pallet_prelude::*;
+
+    pub type AirdropId = u32;
+
+    pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    pub type AirdropOf<T> =
+        Airdrop<<T as frame_system::Config>::Hash, BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// Leaves tracked by a single word of the claimed bitmap
+    pub const LEAVES_PER_WORD: u32 = u32::BITS;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to post airdrops
+        type AirdropOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Token airdrops are paid in
+        type Currency: fungible::Mutate<Self::AccountId>;
+        /// Account funding airdrops and receiving their unclaimed funds
+        type Treasury: Get<Self::AccountId>;
+        /// Id of the accounts holding the funds of each airdrop
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+        /// Largest number of leaves in an airdrop
+        #[pallet::constant]
+        type MaxLeaves: Get<u32>;
+        /// Largest number of hashes in a Merkle proof
+        #[pallet::constant]
+        type MaxProofLength: Get<u32>;
+    }
+
+    /// Airdrop posted as the Merkle root of its `(index, account, amount)` leaves
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Airdrop<Hash, Balance, BlockNumber> {
+        pub merkle_root: Hash,
+        /// Number of leaves in the tree
+        pub leaves: u32,
+        pub total: Balance,
+        /// Amount not claimed yet
+        pub remaining: Balance,
+        /// Last block claims are accepted at
+        pub expires_at: BlockNumber,
+    }
+
+    #[pallet::storage]
+    pub type NextAirdropId<T> = StorageValue<_, AirdropId, ValueQuery>;
+
+    /// Map from AirdropId to Airdrop
+    #[pallet::storage]
+    pub type Airdrops<T: Config> = StorageMap<_, Twox64Concat, AirdropId, AirdropOf<T>>;
+
+    /// Bitmap of claimed leaves, `LEAVES_PER_WORD` leaves per word
+    #[pallet::storage]
+    pub type Claimed<T> =
+        StorageDoubleMap<_, Twox64Concat, AirdropId, Twox64Concat, u32, u32, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Airdrop posted and funded from the treasury
+        AirdropCreated {
+            airdrop_id: AirdropId,
+            merkle_root: T::Hash,
+            total: BalanceOf<T>,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// Leaf of an airdrop claimed
+        Claimed {
+            airdrop_id: AirdropId,
+            who: T::AccountId,
+            index: u32,
+            amount: BalanceOf<T>,
+        },
+        /// Expired airdrop closed and its unclaimed funds returned to the treasury
+        AirdropClosed {
+            airdrop_id: AirdropId,
+            returned: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Airdrop does not exist
+        UnknownAirdrop,
+        /// Airdrop has no leaves, more than `MaxLeaves`, a zero total or a zero duration
+        InvalidAirdrop,
+        /// Claim period of the airdrop is over
+        Expired,
+        /// Claim period of the airdrop is not over yet
+        NotExpired,
+        /// Leaf index is beyond the airdrop's leaves
+        InvalidIndex,
+        /// Leaf was already claimed
+        AlreadyClaimed,
+        /// Merkle proof does not lead to the airdrop's root
+        InvalidProof,
+        /// Claim is larger than what is left of the airdrop
+        InsufficientFunds,
+        /// Airdrop ids are exhausted
+        Overflow,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Post an airdrop of `total` from the treasury, claimable for `duration` blocks
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::create_airdrop())]
+        pub fn create_airdrop(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            leaves: u32,
+            total: BalanceOf<T>,
+            duration: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            T::AirdropOrigin::ensure_origin(origin)?;
+            ensure!(
+                leaves > 0 && leaves <= T::MaxLeaves::get() && !total.is_zero(),
+                Error::<T>::InvalidAirdrop
+            );
+            ensure!(!duration.is_zero(), Error::<T>::InvalidAirdrop);
+
+            let airdrop_id = NextAirdropId::<T>::get();
+            let next_id = airdrop_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            T::Currency::transfer(
+                &T::Treasury::get(),
+                &Self::account_id(airdrop_id),
+                total,
+                Preservation::Preserve,
+            )?;
+
+            let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+            Airdrops::<T>::insert(
+                airdrop_id,
+                Airdrop {
+                    merkle_root,
+                    leaves,
+                    total,
+                    remaining: total,
+                    expires_at,
+                },
+            );
+            NextAirdropId::<T>::put(next_id);
+
+            Self::deposit_event(Event::AirdropCreated {
+                airdrop_id,
+                merkle_root,
+                total,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Claim leaf `index` of an airdrop, proving it holds the caller and `amount`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::claim(proof.len() as u32))]
+        pub fn claim(
+            origin: OriginFor<T>,
+            airdrop_id: AirdropId,
+            index: u32,
+            amount: BalanceOf<T>,
+            proof: BoundedVec<T::Hash, T::MaxProofLength>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut airdrop = Airdrops::<T>::get(airdrop_id).ok_or(Error::<T>::UnknownAirdrop)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= airdrop.expires_at,
+                Error::<T>::Expired
+            );
+            ensure!(index < airdrop.leaves, Error::<T>::InvalidIndex);
+            ensure!(
+                !Self::is_claimed(airdrop_id, index),
+                Error::<T>::AlreadyClaimed
+            );
+
+            let leaf = Self::leaf_hash(index, &who, amount);
+            ensure!(
+                Self::root_of(leaf, &proof) == airdrop.merkle_root,
+                Error::<T>::InvalidProof
+            );
+            ensure!(amount <= airdrop.remaining, Error::<T>::InsufficientFunds);
+
+            T::Currency::transfer(
+                &Self::account_id(airdrop_id),
+                &who,
+                amount,
+                Preservation::Expendable,
+            )?;
+
+            airdrop.remaining.saturating_reduce(amount);
+            Airdrops::<T>::insert(airdrop_id, airdrop);
+            Claimed::<T>::mutate(airdrop_id, index / LEAVES_PER_WORD, |word| {
+                *word |= 1 << (index % LEAVES_PER_WORD)
+            });
+
+            Self::deposit_event(Event::Claimed {
+                airdrop_id,
+                who,
+                index,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Close an expired airdrop and return its unclaimed funds to the treasury
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::close_airdrop(
+            T::MaxLeaves::get().div_ceil(LEAVES_PER_WORD)
+        ))]
+        pub fn close_airdrop(origin: OriginFor<T>, airdrop_id: AirdropId) -> DispatchResult {
+            ensure_signed(origin)?;
+            let airdrop = Airdrops::<T>::get(airdrop_id).ok_or(Error::<T>::UnknownAirdrop)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > airdrop.expires_at,
+                Error::<T>::NotExpired
+            );
+
+            let account = Self::account_id(airdrop_id);
+            let returned = T::Currency::balance(&account);
+            if !returned.is_zero() {
+                T::Currency::transfer(
+                    &account,
+                    &T::Treasury::get(),
+                    returned,
+                    Preservation::Expendable,
+                )?;
+            }
+
+            Airdrops::<T>::remove(airdrop_id);
+            let _ = Claimed::<T>::clear_prefix(
+                airdrop_id,
+                airdrop.leaves.div_ceil(LEAVES_PER_WORD),
+                None,
+            );
+
+            Self::deposit_event(Event::AirdropClosed {
+                airdrop_id,
+                returned,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Account holding the unclaimed funds of an airdrop
+        pub fn account_id(airdrop_id: AirdropId) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(airdrop_id)
+        }
+
+        /// Whether leaf `index` of an airdrop was claimed
+        pub fn is_claimed(airdrop_id: AirdropId, index: u32) -> bool {
+            let word = Claimed::<T>::get(airdrop_id, index / LEAVES_PER_WORD);
+            word & (1 << (index % LEAVES_PER_WORD)) != 0
+        }
+
+        /// Hash of the leaf paying `amount` to `who`
+        pub fn leaf_hash(index: u32, who: &T::AccountId, amount: BalanceOf<T>) -> T::Hash {
+            T::Hashing::hash_of(&(index, who, amount))
+        }
+
+        /// Hash of two sibling nodes; siblings are hashed in ascending order so proofs do not
+        /// need to carry their side
+        pub fn node_hash(a: T::Hash, b: T::Hash) -> T::Hash {
+            if a <= b {
+                T::Hashing::hash_of(&(a, b))
+            } else {
+                T::Hashing::hash_of(&(b, a))
+            }
+        }
+
+        /// Root of the tree holding `leaf`, given the sibling hashes from the leaf upwards
+        pub fn root_of(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+            proof
+                .iter()
+                .fold(leaf, |node, sibling| Self::node_hash(node, *sibling))
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{mock::*, Airdrops, Claimed, Error, Event};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+/// Leaves of the test airdrop as `(account, amount)`, indexed by position
+const LEAVES: [(u64, u64); 4] = [(1, 100), (2, 200), (3, 50), (4, 25)];
+
+/// Root of the test airdrop and the proof of each of its leaves
+fn tree() -> (H256, Vec<Vec<H256>>) {
+    let leaves = LEAVES
+        .iter()
+        .enumerate()
+        .map(|(index, (who, amount))| Airdrop::leaf_hash(index as u32, who, *amount))
+        .collect::<Vec<_>>();
+    let left = Airdrop::node_hash(leaves[0], leaves[1]);
+    let right = Airdrop::node_hash(leaves[2], leaves[3]);
+    let proofs = vec![
+        vec![leaves[1], right],
+        vec![leaves[0], right],
+        vec![leaves[3], left],
+        vec![leaves[2], left],
+    ];
+    (Airdrop::node_hash(left, right), proofs)
+}
+
+fn create_airdrop(total: u64, duration: u64) -> Vec<Vec<H256>> {
+    let (root, proofs) = tree();
+    assert_ok!(Airdrop::create_airdrop(
+        RuntimeOrigin::root(),
+        root,
+        LEAVES.len() as u32,
+        total,
+        duration
+    ));
+    proofs
+}
+
+fn proof(hashes: &[H256]) -> BoundedVec<H256, <Test as crate::Config>::MaxProofLength> {
+    hashes.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn create_airdrop_moves_funds_from_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (root, _) = tree();
+
+        assert_noop!(
+            Airdrop::create_airdrop(RuntimeOrigin::signed(1), root, 4, 375, 10),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Airdrop::create_airdrop(RuntimeOrigin::root(), root, 0, 375, 10),
+            Error::<Test>::InvalidAirdrop
+        );
+        assert_noop!(
+            Airdrop::create_airdrop(RuntimeOrigin::root(), root, 65, 375, 10),
+            Error::<Test>::InvalidAirdrop
+        );
+        assert_noop!(
+            Airdrop::create_airdrop(RuntimeOrigin::root(), root, 4, 375, 0),
+            Error::<Test>::InvalidAirdrop
+        );
+
+        create_airdrop(375, 10);
+
+        System::assert_last_event(
+            Event::AirdropCreated {
+                airdrop_id: 0,
+                merkle_root: root,
+                total: 375,
+                expires_at: 11,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(TREASURY), 625);
+        assert_eq!(Balances::free_balance(Airdrop::account_id(0)), 375);
+        assert_eq!(Airdrops::<Test>::get(0).unwrap().remaining, 375);
+    });
+}
+
+#[test]
+fn claim_pays_leaf_once() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let proofs = create_airdrop(375, 10);
+
+        assert_ok!(Airdrop::claim(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            200,
+            proof(&proofs[1])
+        ));
+
+        System::assert_last_event(
+            Event::Claimed {
+                airdrop_id: 0,
+                who: 2,
+                index: 1,
+                amount: 200,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(2), 200);
+        assert_eq!(Airdrops::<Test>::get(0).unwrap().remaining, 175);
+        assert!(Airdrop::is_claimed(0, 1));
+        assert!(!Airdrop::is_claimed(0, 0));
+        assert_eq!(Claimed::<Test>::get(0, 0), 0b10);
+
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(2), 0, 1, 200, proof(&proofs[1])),
+            Error::<Test>::AlreadyClaimed
+        );
+    });
+}
+
+#[test]
+fn claim_rejects_invalid_proofs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let proofs = create_airdrop(375, 10);
+
+        // Wrong amount, wrong account and proof of another leaf
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(1), 0, 0, 101, proof(&proofs[0])),
+            Error::<Test>::InvalidProof
+        );
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(5), 0, 0, 100, proof(&proofs[0])),
+            Error::<Test>::InvalidProof
+        );
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(1), 0, 0, 100, proof(&proofs[2])),
+            Error::<Test>::InvalidProof
+        );
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(1), 0, 4, 100, proof(&proofs[0])),
+            Error::<Test>::InvalidIndex
+        );
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(1), 1, 0, 100, proof(&proofs[0])),
+            Error::<Test>::UnknownAirdrop
+        );
+    });
+}
+
+#[test]
+fn claim_is_limited_to_funded_amount() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // Underfunded airdrop only covers the first claims
+        let proofs = create_airdrop(250, 10);
+
+        assert_ok!(Airdrop::claim(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            200,
+            proof(&proofs[1])
+        ));
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(1), 0, 0, 100, proof(&proofs[0])),
+            Error::<Test>::InsufficientFunds
+        );
+    });
+}
+
+#[test]
+fn close_airdrop_returns_unclaimed_funds() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let proofs = create_airdrop(375, 10);
+        assert_ok!(Airdrop::claim(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            100,
+            proof(&proofs[0])
+        ));
+
+        System::set_block_number(11);
+        assert_noop!(
+            Airdrop::close_airdrop(RuntimeOrigin::signed(5), 0),
+            Error::<Test>::NotExpired
+        );
+
+        System::set_block_number(12);
+        assert_noop!(
+            Airdrop::claim(RuntimeOrigin::signed(2), 0, 1, 200, proof(&proofs[1])),
+            Error::<Test>::Expired
+        );
+        assert_ok!(Airdrop::close_airdrop(RuntimeOrigin::signed(5), 0));
+
+        System::assert_last_event(
+            Event::AirdropClosed {
+                airdrop_id: 0,
+                returned: 275,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(TREASURY), 900);
+        assert_eq!(Balances::free_balance(Airdrop::account_id(0)), 0);
+        assert!(Airdrops::<Test>::get(0).is_none());
+        assert_eq!(Claimed::<Test>::get(0, 0), 0);
+    });
+}
@@ -0,0 +1,91 @@
+//! Autogenerated weights for pallet_airdrop
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-01-01, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `localhost`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_airdrop.
+pub trait WeightInfo {
+	fn create_airdrop() -> Weight;
+	fn claim(p: u32, ) -> Weight;
+	fn close_airdrop(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_airdrop using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Airdrop NextAirdropId (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Airdrop Airdrops (r:0 w:1)
+	fn create_airdrop() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 48_000_000 picoseconds.
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Airdrop Airdrops (r:1 w:1)
+	/// Storage: Airdrop Claimed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `p` is `[0, 16]`.
+	fn claim(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(52_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Airdrop Airdrops (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Airdrop Claimed (r:0 w:512)
+	/// The range of component `n` is `[1, 512]`.
+	fn close_airdrop(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 40_000_000 picoseconds.
+		Weight::from_parts(40_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(1_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_airdrop() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn claim(p: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn close_airdrop(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(1_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
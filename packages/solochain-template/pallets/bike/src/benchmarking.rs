@@ -0,0 +1,138 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    sp_runtime::traits::Saturating,
+    traits::fungible::{Inspect, Mutate},
+};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+fn part_stats() -> PartStats {
+    PartStats {
+        speed: 10,
+        acceleration: 10,
+        durability: 100,
+        weight: 5,
+        rarity: Rarity::Common,
+    }
+}
+
+fn insert_part<T: Config>(owner: &T::AccountId, part_type: PartType) -> PartId {
+    let part_id = NextPartId::<T>::get();
+    NextPartId::<T>::put(part_id + 1);
+    Parts::<T>::insert(
+        part_id,
+        Part {
+            owner: owner.clone(),
+            part_type,
+            stats: part_stats(),
+            assembled_in: None,
+            wear: 0,
+        },
+    );
+    part_id
+}
+
+pub(crate) fn insert_bike<T: Config>(owner: &T::AccountId) -> BikeId {
+    let parts = [
+        insert_part::<T>(owner, PartType::Frame),
+        insert_part::<T>(owner, PartType::Wheel),
+        insert_part::<T>(owner, PartType::Pedal),
+        insert_part::<T>(owner, PartType::Handlebar),
+    ];
+    let bike_id = NextBikeId::<T>::get();
+    NextBikeId::<T>::put(bike_id + 1);
+    for part_id in parts {
+        Parts::<T>::mutate(part_id, |part| {
+            part.as_mut().unwrap().assembled_in = Some(bike_id);
+        });
+    }
+    Bikes::<T>::insert(
+        bike_id,
+        Bike {
+            owner: owner.clone(),
+            frame: parts[0],
+            wheel: parts[1],
+            pedal: parts[2],
+            handlebar: parts[3],
+            stats: BikeStats::from_parts(&[part_stats(), part_stats(), part_stats(), part_stats()]),
+        },
+    );
+    bike_id
+}
+
+benchmarks! {
+    mint_part {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let origin = T::MintOrigin::try_successful_origin().map_err(|_| "no mint origin")?;
+    }: _<T::RuntimeOrigin>(origin, owner.clone(), PartType::Frame, part_stats())
+    verify {
+        assert_eq!(Parts::<T>::get(0).unwrap().owner, owner);
+    }
+
+    transfer_part {
+        let caller: T::AccountId = whitelisted_caller();
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let part_id = insert_part::<T>(&caller, PartType::Wheel);
+    }: _(RawOrigin::Signed(caller), part_id, dest.clone())
+    verify {
+        assert_eq!(Parts::<T>::get(part_id).unwrap().owner, dest);
+    }
+
+    burn_part {
+        let caller: T::AccountId = whitelisted_caller();
+        let part_id = insert_part::<T>(&caller, PartType::Pedal);
+    }: _(RawOrigin::Signed(caller), part_id)
+    verify {
+        assert!(!Parts::<T>::contains_key(part_id));
+    }
+
+    assemble_bike {
+        let caller: T::AccountId = whitelisted_caller();
+        let frame = insert_part::<T>(&caller, PartType::Frame);
+        let wheel = insert_part::<T>(&caller, PartType::Wheel);
+        let pedal = insert_part::<T>(&caller, PartType::Pedal);
+        let handlebar = insert_part::<T>(&caller, PartType::Handlebar);
+    }: _(RawOrigin::Signed(caller.clone()), frame, wheel, pedal, handlebar)
+    verify {
+        assert_eq!(Bikes::<T>::get(0).unwrap().owner, caller);
+    }
+
+    customize_bike {
+        let caller: T::AccountId = whitelisted_caller();
+        let bike_id = insert_bike::<T>(&caller);
+        let new_wheel = insert_part::<T>(&caller, PartType::Wheel);
+    }: _(RawOrigin::Signed(caller), bike_id, PartType::Wheel, new_wheel)
+    verify {
+        assert_eq!(Bikes::<T>::get(bike_id).unwrap().wheel, new_wheel);
+    }
+
+    disassemble_bike {
+        let caller: T::AccountId = whitelisted_caller();
+        let bike_id = insert_bike::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), bike_id)
+    verify {
+        assert!(!Bikes::<T>::contains_key(bike_id));
+    }
+
+    repair {
+        let caller: T::AccountId = whitelisted_caller();
+        let part_id = insert_part::<T>(&caller, PartType::Frame);
+        Parts::<T>::mutate(part_id, |part| {
+            part.as_mut().unwrap().wear = 50;
+        });
+        let cost = Pallet::<T>::repair_cost(&part_stats(), 50);
+        T::RewardToken::set_balance(
+            &caller,
+            cost.saturating_add(T::RewardToken::minimum_balance()),
+        );
+    }: _(RawOrigin::Signed(caller), part_id)
+    verify {
+        assert_eq!(Parts::<T>::get(part_id).unwrap().wear, 0);
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
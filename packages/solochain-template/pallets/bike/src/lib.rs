@@ -0,0 +1,650 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+use frame_support::{dispatch::DispatchResult, ensure, pallet_prelude::DispatchError};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::Saturating,
+        traits::{
+            fungible,
+            tokens::{Fortitude, Precision, Preservation},
+        },
+    };
+    use frame_system::pallet_prelude::*;
+
+    /// Identifier of a bike part NFT
+    pub type PartId = u64;
+    /// Identifier of an assembled bike
+    pub type BikeId = u64;
+
+    pub type BalanceOf<T> = <<T as Config>::RewardToken as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to mint new bike parts
+        type MintOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Reward token burned to pay for repairs
+        type RewardToken: fungible::Mutate<Self::AccountId>;
+        /// Durability lost by every part of a bike per point of map difficulty
+        #[pallet::constant]
+        type WearPerDifficulty: Get<u32>;
+        /// Reward tokens burned per durability point restored on a common part
+        #[pallet::constant]
+        type RepairCostPerPoint: Get<BalanceOf<Self>>;
+    }
+
+    /// Slot of a bike that a part fits into
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum PartType {
+        Frame,
+        Wheel,
+        Pedal,
+        Handlebar,
+    }
+
+    /// Rarity tier of a bike part
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        Default,
+    )]
+    pub enum Rarity {
+        #[default]
+        Common,
+        Uncommon,
+        Rare,
+        Epic,
+        Legendary,
+    }
+
+    impl Rarity {
+        /// Percentage applied to a part's speed and acceleration when aggregated into a bike
+        pub fn bonus_percent(&self) -> u32 {
+            match self {
+                Rarity::Common => 100,
+                Rarity::Uncommon => 110,
+                Rarity::Rare => 125,
+                Rarity::Epic => 150,
+                Rarity::Legendary => 200,
+            }
+        }
+    }
+
+    /// Stat block of a bike part
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        Default,
+    )]
+    pub struct PartStats {
+        pub speed: u32,
+        pub acceleration: u32,
+        pub durability: u32,
+        pub weight: u32,
+        pub rarity: Rarity,
+    }
+
+    /// Aggregate stats of an assembled bike
+    #[derive(
+        Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct BikeStats {
+        pub speed: u32,
+        pub acceleration: u32,
+        /// Durability of the weakest part
+        pub durability: u32,
+        pub weight: u32,
+    }
+
+    impl BikeStats {
+        /// Aggregate part stats into bike stats.
+        /// Speed and acceleration are summed after applying the rarity bonus, weight is summed
+        /// and durability is the minimum over all parts.
+        pub fn from_parts<'a>(parts: impl IntoIterator<Item = &'a PartStats>) -> Self {
+            let mut stats = BikeStats {
+                durability: u32::MAX,
+                ..Default::default()
+            };
+            let mut any = false;
+            for part in parts {
+                any = true;
+                let bonus = part.rarity.bonus_percent();
+                stats.speed = stats
+                    .speed
+                    .saturating_add(part.speed.saturating_mul(bonus) / 100);
+                stats.acceleration = stats
+                    .acceleration
+                    .saturating_add(part.acceleration.saturating_mul(bonus) / 100);
+                stats.durability = stats.durability.min(part.durability);
+                stats.weight = stats.weight.saturating_add(part.weight);
+            }
+            if !any {
+                stats.durability = 0;
+            }
+            stats
+        }
+    }
+
+    /// Bike part NFT
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Part<AccountId> {
+        pub owner: AccountId,
+        pub part_type: PartType,
+        pub stats: PartStats,
+        /// Bike this part is currently assembled into, if any
+        pub assembled_in: Option<BikeId>,
+        /// Durability lost through racing since the last repair
+        pub wear: u32,
+    }
+
+    impl<AccountId> Part<AccountId> {
+        /// Durability left before the part breaks
+        pub fn remaining_durability(&self) -> u32 {
+            self.stats.durability.saturating_sub(self.wear)
+        }
+    }
+
+    /// Bike assembled from one part of each type
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Bike<AccountId> {
+        pub owner: AccountId,
+        pub frame: PartId,
+        pub wheel: PartId,
+        pub pedal: PartId,
+        pub handlebar: PartId,
+        pub stats: BikeStats,
+    }
+
+    impl<AccountId> Bike<AccountId> {
+        /// Part currently fitted in `slot`
+        pub fn part(&self, slot: PartType) -> PartId {
+            match slot {
+                PartType::Frame => self.frame,
+                PartType::Wheel => self.wheel,
+                PartType::Pedal => self.pedal,
+                PartType::Handlebar => self.handlebar,
+            }
+        }
+
+        /// Fit `part_id` into `slot`, returning the part it replaces
+        pub fn set_part(&mut self, slot: PartType, part_id: PartId) -> PartId {
+            let current = match slot {
+                PartType::Frame => &mut self.frame,
+                PartType::Wheel => &mut self.wheel,
+                PartType::Pedal => &mut self.pedal,
+                PartType::Handlebar => &mut self.handlebar,
+            };
+            core::mem::replace(current, part_id)
+        }
+
+        /// All parts of the bike, in slot order
+        pub fn parts(&self) -> [PartId; 4] {
+            [self.frame, self.wheel, self.pedal, self.handlebar]
+        }
+    }
+
+    /// Map from PartId to Part
+    #[pallet::storage]
+    pub type Parts<T: Config> = StorageMap<_, Blake2_128Concat, PartId, Part<T::AccountId>>;
+
+    /// Next PartId to assign
+    #[pallet::storage]
+    pub type NextPartId<T> = StorageValue<_, PartId, ValueQuery>;
+
+    /// Map from BikeId to Bike
+    #[pallet::storage]
+    pub type Bikes<T: Config> = StorageMap<_, Blake2_128Concat, BikeId, Bike<T::AccountId>>;
+
+    /// Next BikeId to assign
+    #[pallet::storage]
+    pub type NextBikeId<T> = StorageValue<_, BikeId, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// New part minted
+        PartMinted {
+            part_id: PartId,
+            owner: T::AccountId,
+            part_type: PartType,
+            stats: PartStats,
+        },
+        /// Part transferred to another account
+        PartTransferred {
+            part_id: PartId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// Part burned by its owner
+        PartBurned {
+            part_id: PartId,
+            owner: T::AccountId,
+        },
+        /// Bike assembled from four parts
+        BikeAssembled {
+            bike_id: BikeId,
+            owner: T::AccountId,
+            parts: [PartId; 4],
+            stats: BikeStats,
+        },
+        /// Part in a bike slot swapped for another one
+        BikeCustomized {
+            bike_id: BikeId,
+            slot: PartType,
+            old_part: PartId,
+            new_part: PartId,
+            stats: BikeStats,
+        },
+        /// Bike taken apart and its parts released
+        BikeDisassembled {
+            bike_id: BikeId,
+            owner: T::AccountId,
+        },
+        /// Parts of a bike lost durability in a race
+        BikeWorn { bike_id: BikeId, wear: u32 },
+        /// Part durability restored by burning reward tokens
+        PartRepaired {
+            part_id: PartId,
+            owner: T::AccountId,
+            restored: u32,
+            cost: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Part does not exist
+        PartNotFound,
+        /// Caller does not own the part
+        NotPartOwner,
+        /// Part is assembled into a bike and cannot be used
+        PartLocked,
+        /// Part stats are not valid (e.g. zero durability)
+        InvalidStats,
+        /// No more ids available
+        Overflow,
+        /// Bike does not exist
+        BikeNotFound,
+        /// Caller does not own the bike
+        NotBikeOwner,
+        /// Part does not fit the requested slot
+        WrongPartType,
+        /// Bike has a part with no durability left
+        BikeBroken,
+        /// Part has no wear to repair
+        NothingToRepair,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Mint a new part to `owner`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::mint_part())]
+        pub fn mint_part(
+            origin: OriginFor<T>,
+            owner: T::AccountId,
+            part_type: PartType,
+            stats: PartStats,
+        ) -> DispatchResult {
+            T::MintOrigin::ensure_origin(origin)?;
+            ensure!(stats.durability > 0, Error::<T>::InvalidStats);
+
+            let part_id = NextPartId::<T>::get();
+            let next = part_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            NextPartId::<T>::put(next);
+
+            Parts::<T>::insert(
+                part_id,
+                Part {
+                    owner: owner.clone(),
+                    part_type,
+                    stats: stats.clone(),
+                    assembled_in: None,
+                    wear: 0,
+                },
+            );
+
+            Self::deposit_event(Event::PartMinted {
+                part_id,
+                owner,
+                part_type,
+                stats,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer an owned, unassembled part to `dest`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::transfer_part())]
+        pub fn transfer_part(
+            origin: OriginFor<T>,
+            part_id: PartId,
+            dest: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Parts::<T>::try_mutate(part_id, |maybe_part| -> DispatchResult {
+                let part = maybe_part.as_mut().ok_or(Error::<T>::PartNotFound)?;
+                Self::ensure_usable(part, &who)?;
+                part.owner = dest.clone();
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::PartTransferred {
+                part_id,
+                from: who,
+                to: dest,
+            });
+
+            Ok(())
+        }
+
+        /// Burn an owned, unassembled part
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::burn_part())]
+        pub fn burn_part(origin: OriginFor<T>, part_id: PartId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let part = Parts::<T>::get(part_id).ok_or(Error::<T>::PartNotFound)?;
+            Self::ensure_usable(&part, &who)?;
+            Parts::<T>::remove(part_id);
+
+            Self::deposit_event(Event::PartBurned {
+                part_id,
+                owner: who,
+            });
+
+            Ok(())
+        }
+
+        /// Assemble a new bike from four owned, unassembled parts.
+        /// The parts stay locked in the bike until it is disassembled or they are swapped out.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::assemble_bike())]
+        pub fn assemble_bike(
+            origin: OriginFor<T>,
+            frame: PartId,
+            wheel: PartId,
+            pedal: PartId,
+            handlebar: PartId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bike_id = NextBikeId::<T>::get();
+            let next = bike_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+
+            let parts = [frame, wheel, pedal, handlebar];
+            let slots = [
+                PartType::Frame,
+                PartType::Wheel,
+                PartType::Pedal,
+                PartType::Handlebar,
+            ];
+            let mut part_stats: [PartStats; 4] = Default::default();
+            for ((part_id, slot), stats) in parts.iter().zip(slots).zip(part_stats.iter_mut()) {
+                *stats = Self::ensure_fits(*part_id, &who, slot)?;
+            }
+            for part_id in parts {
+                Self::set_assembled(part_id, Some(bike_id));
+            }
+
+            let stats = BikeStats::from_parts(part_stats.iter());
+            Bikes::<T>::insert(
+                bike_id,
+                Bike {
+                    owner: who.clone(),
+                    frame,
+                    wheel,
+                    pedal,
+                    handlebar,
+                    stats: stats.clone(),
+                },
+            );
+            NextBikeId::<T>::put(next);
+
+            Self::deposit_event(Event::BikeAssembled {
+                bike_id,
+                owner: who,
+                parts,
+                stats,
+            });
+
+            Ok(())
+        }
+
+        /// Swap the part in `slot` of an owned bike for another owned, unassembled part.
+        /// The replaced part is released back to the owner.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::customize_bike())]
+        pub fn customize_bike(
+            origin: OriginFor<T>,
+            bike_id: BikeId,
+            slot: PartType,
+            new_part: PartId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut bike = Bikes::<T>::get(bike_id).ok_or(Error::<T>::BikeNotFound)?;
+            ensure!(bike.owner == who, Error::<T>::NotBikeOwner);
+
+            Self::ensure_fits(new_part, &who, slot)?;
+            let old_part = bike.set_part(slot, new_part);
+            Self::set_assembled(old_part, None);
+            Self::set_assembled(new_part, Some(bike_id));
+
+            bike.stats = Self::compute_stats(&bike)?;
+            let stats = bike.stats.clone();
+            Bikes::<T>::insert(bike_id, bike);
+
+            Self::deposit_event(Event::BikeCustomized {
+                bike_id,
+                slot,
+                old_part,
+                new_part,
+                stats,
+            });
+
+            Ok(())
+        }
+
+        /// Take an owned bike apart, releasing all of its parts
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::disassemble_bike())]
+        pub fn disassemble_bike(origin: OriginFor<T>, bike_id: BikeId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let bike = Bikes::<T>::get(bike_id).ok_or(Error::<T>::BikeNotFound)?;
+            ensure!(bike.owner == who, Error::<T>::NotBikeOwner);
+
+            for part_id in bike.parts() {
+                Self::set_assembled(part_id, None);
+            }
+            Bikes::<T>::remove(bike_id);
+
+            Self::deposit_event(Event::BikeDisassembled {
+                bike_id,
+                owner: who,
+            });
+
+            Ok(())
+        }
+
+        /// Restore the full durability of an owned part, assembled or not.
+        /// Burns reward tokens scaled by the part's rarity and the durability restored.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::repair())]
+        pub fn repair(origin: OriginFor<T>, part_id: PartId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut part = Parts::<T>::get(part_id).ok_or(Error::<T>::PartNotFound)?;
+            ensure!(part.owner == who, Error::<T>::NotPartOwner);
+            let restored = part.wear;
+            ensure!(restored > 0, Error::<T>::NothingToRepair);
+
+            let cost = Self::repair_cost(&part.stats, restored);
+            T::RewardToken::burn_from(
+                &who,
+                cost,
+                Preservation::Expendable,
+                Precision::Exact,
+                Fortitude::Polite,
+            )?;
+
+            part.wear = 0;
+            Parts::<T>::insert(part_id, part);
+
+            Self::deposit_event(Event::PartRepaired {
+                part_id,
+                owner: who,
+                restored,
+                cost,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Ensure `who` owns the part and it is not assembled into a bike
+        pub(crate) fn ensure_usable(
+            part: &Part<T::AccountId>,
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            ensure!(part.owner == *who, Error::<T>::NotPartOwner);
+            ensure!(part.assembled_in.is_none(), Error::<T>::PartLocked);
+            Ok(())
+        }
+
+        /// Check that `who` can fit `part_id` into `slot`, returning the part's stats
+        fn ensure_fits(
+            part_id: PartId,
+            who: &T::AccountId,
+            slot: PartType,
+        ) -> Result<PartStats, DispatchError> {
+            let part = Parts::<T>::get(part_id).ok_or(Error::<T>::PartNotFound)?;
+            Self::ensure_usable(&part, who)?;
+            ensure!(part.part_type == slot, Error::<T>::WrongPartType);
+            Ok(part.stats)
+        }
+
+        /// Record which bike a part is assembled into
+        fn set_assembled(part_id: PartId, bike_id: Option<BikeId>) {
+            Parts::<T>::mutate(part_id, |maybe_part| {
+                if let Some(part) = maybe_part {
+                    part.assembled_in = bike_id;
+                }
+            });
+        }
+
+        /// Reward tokens needed to restore `points` of durability on a part with `stats`
+        pub fn repair_cost(stats: &PartStats, points: u32) -> BalanceOf<T> {
+            T::RepairCostPerPoint::get()
+                .saturating_mul(points.into())
+                .saturating_mul(stats.rarity.bonus_percent().into())
+                / 100u32.into()
+        }
+
+        /// Recompute the aggregate stats of a bike from its current parts
+        pub fn compute_stats(bike: &Bike<T::AccountId>) -> Result<BikeStats, DispatchError> {
+            let mut part_stats: [PartStats; 4] = Default::default();
+            for (part_id, stats) in bike.parts().into_iter().zip(part_stats.iter_mut()) {
+                *stats = Parts::<T>::get(part_id)
+                    .ok_or(Error::<T>::PartNotFound)?
+                    .stats;
+            }
+            Ok(BikeStats::from_parts(part_stats.iter()))
+        }
+    }
+}
+
+/// Bike access for the race logic
+pub trait BikeRacing<AccountId> {
+    /// Ensure `who` owns `bike_id` and none of its parts is broken, returning the bike stats
+    fn ensure_can_race(who: &AccountId, bike_id: BikeId) -> Result<BikeStats, DispatchError>;
+    /// Wear down every part of `bike_id` after a race on a map of the given difficulty
+    fn apply_wear(bike_id: BikeId, difficulty: u32) -> DispatchResult;
+    /// Create an assembled bike owned by `owner` for benchmarking
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_for_benchmark(owner: &AccountId) -> BikeId;
+}
+
+impl<T: Config> BikeRacing<T::AccountId> for Pallet<T> {
+    fn ensure_can_race(who: &T::AccountId, bike_id: BikeId) -> Result<BikeStats, DispatchError> {
+        let bike = Bikes::<T>::get(bike_id).ok_or(Error::<T>::BikeNotFound)?;
+        ensure!(bike.owner == *who, Error::<T>::NotBikeOwner);
+        for part_id in bike.parts() {
+            let part = Parts::<T>::get(part_id).ok_or(Error::<T>::PartNotFound)?;
+            ensure!(part.remaining_durability() > 0, Error::<T>::BikeBroken);
+        }
+        Ok(bike.stats)
+    }
+
+    fn apply_wear(bike_id: BikeId, difficulty: u32) -> DispatchResult {
+        let bike = Bikes::<T>::get(bike_id).ok_or(Error::<T>::BikeNotFound)?;
+        let wear = T::WearPerDifficulty::get().saturating_mul(difficulty);
+        for part_id in bike.parts() {
+            Parts::<T>::mutate(part_id, |maybe_part| {
+                if let Some(part) = maybe_part {
+                    part.wear = part.wear.saturating_add(wear).min(part.stats.durability);
+                }
+            });
+        }
+        Pallet::<T>::deposit_event(Event::BikeWorn { bike_id, wear });
+        Ok(())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_for_benchmark(owner: &T::AccountId) -> BikeId {
+        benchmarking::insert_bike::<T>(owner)
+    }
+}
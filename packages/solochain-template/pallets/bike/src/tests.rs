@@ -0,0 +1,433 @@
+use crate::{
+    mock::*, BikeRacing, BikeStats, Bikes, Error, Event, NextBikeId, NextPartId, PartStats,
+    PartType, Parts, Rarity,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn stats() -> PartStats {
+    PartStats {
+        speed: 10,
+        acceleration: 5,
+        durability: 100,
+        weight: 3,
+        rarity: Rarity::Rare,
+    }
+}
+
+#[test]
+fn mint_part_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Bike::mint_part(
+            RuntimeOrigin::root(),
+            1,
+            PartType::Frame,
+            stats()
+        ));
+
+        let part = Parts::<Test>::get(0).unwrap();
+        assert_eq!(part.owner, 1);
+        assert_eq!(part.part_type, PartType::Frame);
+        assert_eq!(part.stats, stats());
+        assert_eq!(part.assembled_in, None);
+        assert_eq!(NextPartId::<Test>::get(), 1);
+
+        System::assert_last_event(
+            Event::PartMinted {
+                part_id: 0,
+                owner: 1,
+                part_type: PartType::Frame,
+                stats: stats(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn mint_part_requires_mint_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Bike::mint_part(RuntimeOrigin::signed(1), 1, PartType::Wheel, stats()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn mint_part_rejects_zero_durability() {
+    new_test_ext().execute_with(|| {
+        let mut broken = stats();
+        broken.durability = 0;
+        assert_noop!(
+            Bike::mint_part(RuntimeOrigin::root(), 1, PartType::Wheel, broken),
+            Error::<Test>::InvalidStats
+        );
+    });
+}
+
+#[test]
+fn transfer_part_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Bike::mint_part(
+            RuntimeOrigin::root(),
+            1,
+            PartType::Pedal,
+            stats()
+        ));
+
+        assert_noop!(
+            Bike::transfer_part(RuntimeOrigin::signed(2), 0, 3),
+            Error::<Test>::NotPartOwner
+        );
+        assert_ok!(Bike::transfer_part(RuntimeOrigin::signed(1), 0, 2));
+        assert_eq!(Parts::<Test>::get(0).unwrap().owner, 2);
+
+        System::assert_last_event(
+            Event::PartTransferred {
+                part_id: 0,
+                from: 1,
+                to: 2,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn burn_part_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Bike::mint_part(
+            RuntimeOrigin::root(),
+            1,
+            PartType::Handlebar,
+            stats()
+        ));
+
+        assert_ok!(Bike::burn_part(RuntimeOrigin::signed(1), 0));
+        assert!(!Parts::<Test>::contains_key(0));
+        assert_noop!(
+            Bike::burn_part(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::PartNotFound
+        );
+    });
+}
+
+#[test]
+fn assembled_part_is_locked() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bike::mint_part(
+            RuntimeOrigin::root(),
+            1,
+            PartType::Frame,
+            stats()
+        ));
+        Parts::<Test>::mutate(0, |part| {
+            part.as_mut().unwrap().assembled_in = Some(0);
+        });
+
+        assert_noop!(
+            Bike::transfer_part(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::PartLocked
+        );
+        assert_noop!(
+            Bike::burn_part(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::PartLocked
+        );
+    });
+}
+
+fn mint(owner: u64, part_type: PartType, speed: u32, rarity: Rarity) -> u64 {
+    let part_id = NextPartId::<Test>::get();
+    assert_ok!(Bike::mint_part(
+        RuntimeOrigin::root(),
+        owner,
+        part_type,
+        PartStats {
+            speed,
+            acceleration: 4,
+            durability: 50 + speed,
+            weight: 2,
+            rarity,
+        }
+    ));
+    part_id
+}
+
+fn mint_set(owner: u64) -> [u64; 4] {
+    [
+        mint(owner, PartType::Frame, 10, Rarity::Common),
+        mint(owner, PartType::Wheel, 20, Rarity::Rare),
+        mint(owner, PartType::Pedal, 30, Rarity::Common),
+        mint(owner, PartType::Handlebar, 40, Rarity::Legendary),
+    ]
+}
+
+#[test]
+fn assemble_bike_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let [frame, wheel, pedal, handlebar] = mint_set(1);
+
+        assert_ok!(Bike::assemble_bike(
+            RuntimeOrigin::signed(1),
+            frame,
+            wheel,
+            pedal,
+            handlebar
+        ));
+
+        let expected = BikeStats {
+            // 10 + 20 * 1.25 + 30 + 40 * 2
+            speed: 145,
+            // 4 + 5 + 4 + 8
+            acceleration: 21,
+            durability: 60,
+            weight: 8,
+        };
+        let bike = Bikes::<Test>::get(0).unwrap();
+        assert_eq!(bike.owner, 1);
+        assert_eq!(bike.parts(), [frame, wheel, pedal, handlebar]);
+        assert_eq!(bike.stats, expected);
+        for part_id in bike.parts() {
+            assert_eq!(Parts::<Test>::get(part_id).unwrap().assembled_in, Some(0));
+        }
+
+        System::assert_last_event(
+            Event::BikeAssembled {
+                bike_id: 0,
+                owner: 1,
+                parts: [frame, wheel, pedal, handlebar],
+                stats: expected,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn assemble_bike_rejects_wrong_part_type() {
+    new_test_ext().execute_with(|| {
+        let [frame, wheel, pedal, handlebar] = mint_set(1);
+        assert_noop!(
+            Bike::assemble_bike(RuntimeOrigin::signed(1), wheel, frame, pedal, handlebar),
+            Error::<Test>::WrongPartType
+        );
+    });
+}
+
+#[test]
+fn assemble_bike_rejects_parts_of_others() {
+    new_test_ext().execute_with(|| {
+        let [frame, wheel, pedal, _] = mint_set(1);
+        let foreign = mint(2, PartType::Handlebar, 5, Rarity::Common);
+        assert_noop!(
+            Bike::assemble_bike(RuntimeOrigin::signed(1), frame, wheel, pedal, foreign),
+            Error::<Test>::NotPartOwner
+        );
+    });
+}
+
+#[test]
+fn assemble_bike_rejects_assembled_parts() {
+    new_test_ext().execute_with(|| {
+        let [frame, wheel, pedal, handlebar] = mint_set(1);
+        assert_ok!(Bike::assemble_bike(
+            RuntimeOrigin::signed(1),
+            frame,
+            wheel,
+            pedal,
+            handlebar
+        ));
+        let [_, wheel2, pedal2, handlebar2] = mint_set(1);
+        assert_noop!(
+            Bike::assemble_bike(RuntimeOrigin::signed(1), frame, wheel2, pedal2, handlebar2),
+            Error::<Test>::PartLocked
+        );
+    });
+}
+
+#[test]
+fn customize_bike_swaps_part() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let [frame, wheel, pedal, handlebar] = mint_set(1);
+        assert_ok!(Bike::assemble_bike(
+            RuntimeOrigin::signed(1),
+            frame,
+            wheel,
+            pedal,
+            handlebar
+        ));
+        let new_wheel = mint(1, PartType::Wheel, 100, Rarity::Common);
+
+        assert_noop!(
+            Bike::customize_bike(RuntimeOrigin::signed(1), 0, PartType::Frame, new_wheel),
+            Error::<Test>::WrongPartType
+        );
+        assert_noop!(
+            Bike::customize_bike(RuntimeOrigin::signed(2), 0, PartType::Wheel, new_wheel),
+            Error::<Test>::NotBikeOwner
+        );
+        assert_ok!(Bike::customize_bike(
+            RuntimeOrigin::signed(1),
+            0,
+            PartType::Wheel,
+            new_wheel
+        ));
+
+        let bike = Bikes::<Test>::get(0).unwrap();
+        assert_eq!(bike.wheel, new_wheel);
+        // 10 + 100 + 30 + 80
+        assert_eq!(bike.stats.speed, 220);
+        assert_eq!(Parts::<Test>::get(wheel).unwrap().assembled_in, None);
+        assert_eq!(Parts::<Test>::get(new_wheel).unwrap().assembled_in, Some(0));
+
+        System::assert_last_event(
+            Event::BikeCustomized {
+                bike_id: 0,
+                slot: PartType::Wheel,
+                old_part: wheel,
+                new_part: new_wheel,
+                stats: bike.stats,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn disassemble_bike_releases_parts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let parts = mint_set(1);
+        let [frame, wheel, pedal, handlebar] = parts;
+        assert_ok!(Bike::assemble_bike(
+            RuntimeOrigin::signed(1),
+            frame,
+            wheel,
+            pedal,
+            handlebar
+        ));
+
+        assert_noop!(
+            Bike::disassemble_bike(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotBikeOwner
+        );
+        assert_ok!(Bike::disassemble_bike(RuntimeOrigin::signed(1), 0));
+
+        assert!(!Bikes::<Test>::contains_key(0));
+        for part_id in parts {
+            assert_eq!(Parts::<Test>::get(part_id).unwrap().assembled_in, None);
+        }
+        assert_ok!(Bike::transfer_part(RuntimeOrigin::signed(1), frame, 2));
+
+        System::assert_last_event(
+            Event::PartTransferred {
+                part_id: frame,
+                from: 1,
+                to: 2,
+            }
+            .into(),
+        );
+    });
+}
+
+fn assembled_bike(owner: u64) -> u64 {
+    let [frame, wheel, pedal, handlebar] = mint_set(owner);
+    let bike_id = NextBikeId::<Test>::get();
+    assert_ok!(Bike::assemble_bike(
+        RuntimeOrigin::signed(owner),
+        frame,
+        wheel,
+        pedal,
+        handlebar
+    ));
+    bike_id
+}
+
+#[test]
+fn racing_wears_parts_until_bike_is_broken() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bike_id = assembled_bike(1);
+
+        assert_ok!(<Bike as BikeRacing<u64>>::ensure_can_race(&1, bike_id));
+        assert_noop!(
+            <Bike as BikeRacing<u64>>::ensure_can_race(&2, bike_id),
+            Error::<Test>::NotBikeOwner
+        );
+
+        assert_ok!(<Bike as BikeRacing<u64>>::apply_wear(bike_id, 3));
+        System::assert_last_event(Event::BikeWorn { bike_id, wear: 30 }.into());
+        let frame = Bikes::<Test>::get(bike_id).unwrap().frame;
+        assert_eq!(
+            Parts::<Test>::get(frame).unwrap().remaining_durability(),
+            30
+        );
+        assert_ok!(<Bike as BikeRacing<u64>>::ensure_can_race(&1, bike_id));
+
+        // The frame has the lowest durability and breaks first
+        assert_ok!(<Bike as BikeRacing<u64>>::apply_wear(bike_id, 3));
+        assert_eq!(Parts::<Test>::get(frame).unwrap().remaining_durability(), 0);
+        assert_noop!(
+            <Bike as BikeRacing<u64>>::ensure_can_race(&1, bike_id),
+            Error::<Test>::BikeBroken
+        );
+    });
+}
+
+#[test]
+fn repair_burns_tokens_scaled_by_rarity_and_damage() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bike_id = assembled_bike(1);
+        let bike = Bikes::<Test>::get(bike_id).unwrap();
+        assert_ok!(<Bike as BikeRacing<u64>>::apply_wear(bike_id, 6));
+
+        assert_noop!(
+            Bike::repair(RuntimeOrigin::signed(2), bike.frame),
+            Error::<Test>::NotPartOwner
+        );
+
+        // Common frame: 60 points * 2 per point
+        assert_ok!(Bike::repair(RuntimeOrigin::signed(1), bike.frame));
+        assert_eq!(Balances::free_balance(1), 10_000 - 120);
+        assert_eq!(Parts::<Test>::get(bike.frame).unwrap().wear, 0);
+        System::assert_last_event(
+            Event::PartRepaired {
+                part_id: bike.frame,
+                owner: 1,
+                restored: 60,
+                cost: 120,
+            }
+            .into(),
+        );
+
+        // Rare wheel: 60 points * 2 per point * 125%
+        assert_ok!(Bike::repair(RuntimeOrigin::signed(1), bike.wheel));
+        assert_eq!(Balances::free_balance(1), 10_000 - 120 - 150);
+        assert_ok!(<Bike as BikeRacing<u64>>::ensure_can_race(&1, bike_id));
+
+        assert_noop!(
+            Bike::repair(RuntimeOrigin::signed(1), bike.frame),
+            Error::<Test>::NothingToRepair
+        );
+    });
+}
+
+#[test]
+fn repair_fails_without_enough_tokens() {
+    new_test_ext().execute_with(|| {
+        let bike_id = assembled_bike(2);
+        let frame = Bikes::<Test>::get(bike_id).unwrap().frame;
+        assert_ok!(<Bike as BikeRacing<u64>>::apply_wear(bike_id, 1));
+
+        assert!(Bike::repair(RuntimeOrigin::signed(2), frame).is_err());
+        assert_eq!(Parts::<Test>::get(frame).unwrap().wear, 10);
+    });
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+    mint_character {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let origin = T::MintOrigin::try_successful_origin().map_err(|_| "no mint origin")?;
+    }: _<T::RuntimeOrigin>(origin, owner.clone(), CharacterStats::default())
+    verify {
+        assert_eq!(Characters::<T>::get(0).unwrap().owner, owner);
+    }
+
+    transfer_character {
+        let caller: T::AccountId = whitelisted_caller();
+        let dest: T::AccountId = account("dest", 0, SEED);
+        Characters::<T>::insert(0, Character {
+            owner: caller.clone(),
+            stats: CharacterStats::default(),
+        });
+    }: _(RawOrigin::Signed(caller), 0, dest.clone())
+    verify {
+        assert_eq!(Characters::<T>::get(0).unwrap().owner, dest);
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,363 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+use frame_support::{dispatch::DispatchResult, ensure};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::sp_runtime::traits::{SaturatedConversion, Saturating, Zero};
+    use frame_system::pallet_prelude::*;
+
+    /// Identifier of a character NFT
+    pub type CharacterId = u64;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to mint new characters
+        type MintOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Energy of a fully rested character
+        #[pallet::constant]
+        type MaxEnergy: Get<u32>;
+        /// Blocks needed to regenerate one point of energy
+        #[pallet::constant]
+        type EnergyRegenPeriod: Get<BlockNumberFor<Self>>;
+        /// Water of a fully hydrated character
+        #[pallet::constant]
+        type MaxWater: Get<u32>;
+        /// Blocks needed to regenerate one point of water
+        #[pallet::constant]
+        type WaterRegenPeriod: Get<BlockNumberFor<Self>>;
+        /// Items attached to characters, which keep them from being transferred
+        type Attachments: CharacterAttachments;
+    }
+
+    /// Racing stats of a character
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        Default,
+    )]
+    pub struct CharacterStats {
+        pub speed: u32,
+        pub acceleration: u32,
+        pub stamina: u32,
+    }
+
+    impl CharacterStats {
+        /// Add `other` on top of these stats
+        pub fn saturating_add(&self, other: &CharacterStats) -> CharacterStats {
+            CharacterStats {
+                speed: self.speed.saturating_add(other.speed),
+                acceleration: self.acceleration.saturating_add(other.acceleration),
+                stamina: self.stamina.saturating_add(other.stamina),
+            }
+        }
+    }
+
+    /// Character NFT
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Character<AccountId> {
+        pub owner: AccountId,
+        pub stats: CharacterStats,
+    }
+
+    /// Energy or water of a character at the block it was last spent
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct VitalState<BlockNumber> {
+        pub amount: u32,
+        pub updated_at: BlockNumber,
+    }
+
+    /// Map from CharacterId to Character
+    #[pallet::storage]
+    pub type Characters<T: Config> =
+        StorageMap<_, Blake2_128Concat, CharacterId, Character<T::AccountId>>;
+
+    /// Next CharacterId to assign
+    #[pallet::storage]
+    pub type NextCharacterId<T> = StorageValue<_, CharacterId, ValueQuery>;
+
+    /// Energy of characters that spent some; absent means fully rested
+    #[pallet::storage]
+    pub type Energy<T: Config> =
+        StorageMap<_, Blake2_128Concat, CharacterId, VitalState<BlockNumberFor<T>>>;
+
+    /// Water of characters that spent some; absent means fully hydrated
+    #[pallet::storage]
+    pub type Water<T: Config> =
+        StorageMap<_, Blake2_128Concat, CharacterId, VitalState<BlockNumberFor<T>>>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// New character minted
+        CharacterMinted {
+            character_id: CharacterId,
+            owner: T::AccountId,
+            stats: CharacterStats,
+        },
+        /// Character transferred to another account
+        CharacterTransferred {
+            character_id: CharacterId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// Character spent energy
+        EnergySpent {
+            character_id: CharacterId,
+            amount: u32,
+            remaining: u32,
+        },
+        /// Character spent water
+        WaterSpent {
+            character_id: CharacterId,
+            amount: u32,
+            remaining: u32,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Character does not exist
+        CharacterNotFound,
+        /// Caller does not own the character
+        NotCharacterOwner,
+        /// No more ids available
+        Overflow,
+        /// Character does not have enough energy left
+        NotEnoughEnergy,
+        /// Character does not have enough water left
+        NotEnoughWater,
+        /// Character has items attached and cannot be transferred
+        ItemsAttached,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Mint a new character to `owner`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::mint_character())]
+        pub fn mint_character(
+            origin: OriginFor<T>,
+            owner: T::AccountId,
+            stats: CharacterStats,
+        ) -> DispatchResult {
+            T::MintOrigin::ensure_origin(origin)?;
+
+            let character_id = NextCharacterId::<T>::get();
+            let next = character_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            NextCharacterId::<T>::put(next);
+
+            Characters::<T>::insert(
+                character_id,
+                Character {
+                    owner: owner.clone(),
+                    stats: stats.clone(),
+                },
+            );
+
+            Self::deposit_event(Event::CharacterMinted {
+                character_id,
+                owner,
+                stats,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer an owned character to `dest`. Attached items such as skills must be
+        /// detached first.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::transfer_character())]
+        pub fn transfer_character(
+            origin: OriginFor<T>,
+            character_id: CharacterId,
+            dest: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Characters::<T>::try_mutate(character_id, |maybe_character| -> DispatchResult {
+                let character = maybe_character
+                    .as_mut()
+                    .ok_or(Error::<T>::CharacterNotFound)?;
+                ensure!(character.owner == who, Error::<T>::NotCharacterOwner);
+                ensure!(
+                    !T::Attachments::has_attachments(character_id),
+                    Error::<T>::ItemsAttached
+                );
+                character.owner = dest.clone();
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::CharacterTransferred {
+                character_id,
+                from: who,
+                to: dest,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Energy of `character_id` at the current block, including regeneration
+        pub fn current_energy(character_id: CharacterId) -> u32 {
+            Self::regenerated(
+                Energy::<T>::get(character_id),
+                T::MaxEnergy::get(),
+                T::EnergyRegenPeriod::get(),
+            )
+        }
+
+        /// Water of `character_id` at the current block, including regeneration
+        pub fn current_water(character_id: CharacterId) -> u32 {
+            Self::regenerated(
+                Water::<T>::get(character_id),
+                T::MaxWater::get(),
+                T::WaterRegenPeriod::get(),
+            )
+        }
+
+        /// Spend `energy` and `water` of `character_id`, failing without changes if either
+        /// is insufficient
+        pub fn spend_vitals(character_id: CharacterId, energy: u32, water: u32) -> DispatchResult {
+            let current_energy = Self::current_energy(character_id);
+            let current_water = Self::current_water(character_id);
+            ensure!(current_energy >= energy, Error::<T>::NotEnoughEnergy);
+            ensure!(current_water >= water, Error::<T>::NotEnoughWater);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if energy > 0 {
+                let remaining = current_energy - energy;
+                Energy::<T>::insert(
+                    character_id,
+                    VitalState {
+                        amount: remaining,
+                        updated_at: now,
+                    },
+                );
+                Self::deposit_event(Event::EnergySpent {
+                    character_id,
+                    amount: energy,
+                    remaining,
+                });
+            }
+            if water > 0 {
+                let remaining = current_water - water;
+                Water::<T>::insert(
+                    character_id,
+                    VitalState {
+                        amount: remaining,
+                        updated_at: now,
+                    },
+                );
+                Self::deposit_event(Event::WaterSpent {
+                    character_id,
+                    amount: water,
+                    remaining,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Amount of a vital at the current block, regenerating one point per `period`
+        fn regenerated(
+            state: Option<VitalState<BlockNumberFor<T>>>,
+            max: u32,
+            period: BlockNumberFor<T>,
+        ) -> u32 {
+            let Some(state) = state else {
+                return max;
+            };
+            if period.is_zero() {
+                return max;
+            }
+            let elapsed =
+                frame_system::Pallet::<T>::block_number().saturating_sub(state.updated_at);
+            let regenerated = (elapsed / period).saturated_into::<u32>();
+            state.amount.saturating_add(regenerated).min(max)
+        }
+    }
+}
+
+/// Read access to characters for other game pallets
+pub trait CharacterInspect<AccountId> {
+    /// Current owner of `character_id`
+    fn owner_of(character_id: CharacterId) -> Option<AccountId>;
+    /// Base stats of `character_id`, before any skill is applied
+    fn base_stats(character_id: CharacterId) -> Option<CharacterStats>;
+    /// Spend `energy` and `water` of `character_id`, failing if it is too tired or thirsty
+    fn consume_vitals(character_id: CharacterId, energy: u32, water: u32) -> DispatchResult;
+    /// Create a character owned by `owner` for benchmarking
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_for_benchmark(owner: &AccountId) -> CharacterId;
+}
+
+impl<T: Config> CharacterInspect<T::AccountId> for Pallet<T> {
+    fn owner_of(character_id: CharacterId) -> Option<T::AccountId> {
+        Characters::<T>::get(character_id).map(|character| character.owner)
+    }
+
+    fn base_stats(character_id: CharacterId) -> Option<CharacterStats> {
+        Characters::<T>::get(character_id).map(|character| character.stats)
+    }
+
+    fn consume_vitals(character_id: CharacterId, energy: u32, water: u32) -> DispatchResult {
+        ensure!(
+            Characters::<T>::contains_key(character_id),
+            Error::<T>::CharacterNotFound
+        );
+        Self::spend_vitals(character_id, energy, water)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn create_for_benchmark(owner: &T::AccountId) -> CharacterId {
+        let character_id = NextCharacterId::<T>::get();
+        NextCharacterId::<T>::put(character_id + 1);
+        Characters::<T>::insert(
+            character_id,
+            Character {
+                owner: owner.clone(),
+                stats: CharacterStats::default(),
+            },
+        );
+        character_id
+    }
+}
+
+/// Items other pallets attach to characters
+pub trait CharacterAttachments {
+    /// Whether anything is attached to `character_id`
+    fn has_attachments(character_id: CharacterId) -> bool;
+}
+
+impl CharacterAttachments for () {
+    fn has_attachments(_character_id: CharacterId) -> bool {
+        false
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{mock::*, CharacterInspect, CharacterStats, Characters, Energy, Error, Event, Water};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn stats() -> CharacterStats {
+    CharacterStats {
+        speed: 12,
+        acceleration: 8,
+        stamina: 20,
+    }
+}
+
+#[test]
+fn mint_character_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Character::mint_character(RuntimeOrigin::signed(1), 1, stats()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Character::mint_character(RuntimeOrigin::root(), 1, stats()));
+
+        let character = Characters::<Test>::get(0).unwrap();
+        assert_eq!(character.owner, 1);
+        assert_eq!(character.stats, stats());
+        assert_eq!(<Character as CharacterInspect<u64>>::owner_of(0), Some(1));
+        assert_eq!(
+            <Character as CharacterInspect<u64>>::base_stats(0),
+            Some(stats())
+        );
+
+        System::assert_last_event(
+            Event::CharacterMinted {
+                character_id: 0,
+                owner: 1,
+                stats: stats(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn transfer_character_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Character::mint_character(RuntimeOrigin::root(), 1, stats()));
+
+        assert_noop!(
+            Character::transfer_character(RuntimeOrigin::signed(2), 0, 2),
+            Error::<Test>::NotCharacterOwner
+        );
+        assert_ok!(Character::transfer_character(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert_eq!(Characters::<Test>::get(0).unwrap().owner, 2);
+
+        System::assert_last_event(
+            Event::CharacterTransferred {
+                character_id: 0,
+                from: 1,
+                to: 2,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn energy_is_spent_and_regenerates() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Character::mint_character(RuntimeOrigin::root(), 1, stats()));
+        assert_eq!(Character::current_energy(0), 100);
+
+        assert_ok!(<Character as CharacterInspect<u64>>::consume_vitals(
+            0, 70, 0
+        ));
+        assert_eq!(Character::current_energy(0), 30);
+        System::assert_last_event(
+            Event::EnergySpent {
+                character_id: 0,
+                amount: 70,
+                remaining: 30,
+            }
+            .into(),
+        );
+        assert_noop!(
+            <Character as CharacterInspect<u64>>::consume_vitals(0, 31, 0),
+            Error::<Test>::NotEnoughEnergy
+        );
+        assert_noop!(
+            <Character as CharacterInspect<u64>>::consume_vitals(1, 1, 0),
+            Error::<Test>::CharacterNotFound
+        );
+
+        // One point every 10 blocks, capped at the maximum
+        System::set_block_number(51);
+        assert_eq!(Character::current_energy(0), 35);
+        System::set_block_number(10_000);
+        assert_eq!(Character::current_energy(0), 100);
+        assert_eq!(Energy::<Test>::get(0).unwrap().amount, 30);
+    });
+}
+
+#[test]
+fn water_is_spent_with_energy() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Character::mint_character(RuntimeOrigin::root(), 1, stats()));
+
+        // Failing on water leaves energy untouched
+        assert_noop!(
+            <Character as CharacterInspect<u64>>::consume_vitals(0, 10, 60),
+            Error::<Test>::NotEnoughWater
+        );
+        assert_eq!(Character::current_energy(0), 100);
+
+        assert_ok!(<Character as CharacterInspect<u64>>::consume_vitals(
+            0, 10, 20
+        ));
+        assert_eq!(Character::current_energy(0), 90);
+        assert_eq!(Character::current_water(0), 30);
+        System::assert_last_event(
+            Event::WaterSpent {
+                character_id: 0,
+                amount: 20,
+                remaining: 30,
+            }
+            .into(),
+        );
+
+        // One point of water every 20 blocks
+        System::set_block_number(101);
+        assert_eq!(Character::current_water(0), 35);
+        assert_eq!(Water::<Test>::get(0).unwrap().amount, 30);
+    });
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+    check_in {
+        let caller: T::AccountId = whitelisted_caller();
+        let day = Pallet::<T>::current_day();
+        // Extend a streak from yesterday where the clock allows it
+        if day > 0 {
+            Streaks::<T>::insert(&caller, Streak { last_day: day - 1, current: 1, longest: 1 });
+        }
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(Streaks::<T>::get(&caller).map(|streak| streak.last_day), Some(day));
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,150 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, Zero},
+        traits::UnixTime,
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_rewards::{RewardAccrual, RewardReason};
+
+    /// Seconds in a check-in day
+    pub const SECONDS_PER_DAY: u64 = 86_400;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Balance of check-in rewards
+        type Balance: AtLeast32BitUnsigned + Parameter + Member + MaxEncodedLen + Copy;
+        /// Credits check-in rewards as pending check-in rewards
+        type Rewards: RewardAccrual<Self::AccountId, Self::Balance>;
+        /// Wall clock the check-in day is derived from
+        type TimeProvider: UnixTime;
+        /// Reward of the first day of a streak
+        #[pallet::constant]
+        type BaseReward: Get<Self::Balance>;
+        /// Reward added for each further consecutive day
+        #[pallet::constant]
+        type StreakBonus: Get<Self::Balance>;
+        /// Largest reward of a single check-in
+        #[pallet::constant]
+        type MaxReward: Get<Self::Balance>;
+    }
+
+    /// Consecutive days an account checked in on
+    #[derive(
+        Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct Streak {
+        /// Day of the latest check-in
+        pub last_day: u32,
+        /// Length of the current streak
+        pub current: u32,
+        /// Length of the longest streak
+        pub longest: u32,
+    }
+
+    /// Check-in streak of each account
+    #[pallet::storage]
+    pub type Streaks<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Streak>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Account checked in and earned a reward for its streak
+        CheckedIn {
+            who: T::AccountId,
+            day: u32,
+            streak: u32,
+            reward: T::Balance,
+        },
+        /// Account missed a day and its streak started over
+        StreakReset { who: T::AccountId, previous: u32 },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Account already checked in today
+        AlreadyCheckedIn,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Check in for today, extending the caller's streak if they checked in yesterday
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::check_in())]
+        pub fn check_in(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let day = Self::current_day();
+
+            let mut streak = match Streaks::<T>::get(&who) {
+                Some(streak) => {
+                    ensure!(streak.last_day < day, Error::<T>::AlreadyCheckedIn);
+                    streak
+                }
+                None => Streak::default(),
+            };
+            if streak.current > 0 && streak.last_day.saturating_add(1) == day {
+                streak.current.saturating_inc();
+            } else {
+                if streak.current > 0 {
+                    Self::deposit_event(Event::StreakReset {
+                        who: who.clone(),
+                        previous: streak.current,
+                    });
+                }
+                streak.current = 1;
+            }
+            streak.last_day = day;
+            streak.longest = streak.longest.max(streak.current);
+            Streaks::<T>::insert(&who, streak);
+
+            let reward = Self::reward_for(streak.current);
+            if !reward.is_zero() {
+                T::Rewards::accrue(&who, RewardReason::CheckIn, reward)?;
+            }
+
+            Self::deposit_event(Event::CheckedIn {
+                who,
+                day,
+                streak: streak.current,
+                reward,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Day of the check-in calendar, counted in days since the Unix epoch
+        pub fn current_day() -> u32 {
+            (T::TimeProvider::now().as_secs() / SECONDS_PER_DAY) as u32
+        }
+
+        /// Reward of the `streak`th consecutive check-in
+        pub fn reward_for(streak: u32) -> T::Balance {
+            let bonus = T::StreakBonus::get().saturating_mul(streak.saturating_sub(1).into());
+            T::BaseReward::get()
+                .saturating_add(bonus)
+                .min(T::MaxReward::get())
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{mock::*, Error, Event, Streak, Streaks};
+use frame_support::{assert_noop, assert_ok};
+use pallet_rewards::RewardReason;
+
+fn pending(who: u64) -> u64 {
+    pallet_rewards::Rewards::<Test>::get(who, RewardReason::CheckIn)
+}
+
+/// Move the clock to the middle of `day`
+fn set_day(day: u64) {
+    Timestamp::set_timestamp(day * DAY + DAY / 2);
+}
+
+#[test]
+fn check_in_starts_a_streak() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        set_day(100);
+
+        assert_ok!(CheckIn::check_in(RuntimeOrigin::signed(1)));
+        System::assert_last_event(
+            Event::CheckedIn {
+                who: 1,
+                day: 100,
+                streak: 1,
+                reward: 10,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Streaks::<Test>::get(1),
+            Some(Streak {
+                last_day: 100,
+                current: 1,
+                longest: 1,
+            })
+        );
+        assert_eq!(pending(1), 10);
+
+        assert_noop!(
+            CheckIn::check_in(RuntimeOrigin::signed(1)),
+            Error::<Test>::AlreadyCheckedIn
+        );
+    });
+}
+
+#[test]
+fn consecutive_days_escalate_rewards_up_to_the_cap() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for (day, reward) in [(1, 10), (2, 15), (3, 20), (4, 25), (5, 30), (6, 30)] {
+            set_day(day);
+            assert_ok!(CheckIn::check_in(RuntimeOrigin::signed(1)));
+            System::assert_last_event(
+                Event::CheckedIn {
+                    who: 1,
+                    day: day as u32,
+                    streak: day as u32,
+                    reward,
+                }
+                .into(),
+            );
+        }
+        assert_eq!(pending(1), 130);
+        assert_eq!(Streaks::<Test>::get(1).unwrap().current, 6);
+    });
+}
+
+#[test]
+fn missed_day_resets_the_streak() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for day in 1..=3 {
+            set_day(day);
+            assert_ok!(CheckIn::check_in(RuntimeOrigin::signed(1)));
+        }
+
+        set_day(5);
+        assert_ok!(CheckIn::check_in(RuntimeOrigin::signed(1)));
+        System::assert_has_event(
+            Event::StreakReset {
+                who: 1,
+                previous: 3,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::CheckedIn {
+                who: 1,
+                day: 5,
+                streak: 1,
+                reward: 10,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Streaks::<Test>::get(1),
+            Some(Streak {
+                last_day: 5,
+                current: 1,
+                longest: 3,
+            })
+        );
+    });
+}
+
+#[test]
+fn reward_for_is_capped() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(CheckIn::reward_for(1), 10);
+        assert_eq!(CheckIn::reward_for(3), 20);
+        assert_eq!(CheckIn::reward_for(u32::MAX), 30);
+    });
+}
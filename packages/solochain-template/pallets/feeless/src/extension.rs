@@ -0,0 +1,163 @@
+//! Transaction extension waiving the fees of game calls signed by registered players.
+
+use crate::{Config, Event, Pallet};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::{fmt, marker::PhantomData};
+use frame_support::{
+    sp_runtime::{
+        traits::{
+            AsSystemOriginSigner, DispatchInfoOf, DispatchOriginOf, Implication,
+            PostDispatchInfoOf, TransactionExtension, TransactionExtensionMetadata, ValidateResult,
+        },
+        transaction_validity::{
+            InvalidTransaction, TransactionSource, TransactionValidityError, ValidTransaction,
+        },
+        DispatchResult,
+    },
+    traits::Get,
+    weights::Weight,
+};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// Wraps the fee payment extension `S` and skips it for the calls `Config::FeelessCalls`
+/// lets through when they are signed by an account with a profile.
+///
+/// To keep free transactions from flooding blocks, an account gets at most
+/// `Config::MaxFeelessPerBlock` of them in a block. Further ones fail validation as
+/// exhausting the block's resources, so block authors leave them in the pool for a later
+/// block instead of charging for them. Transactions pooled for the same block are all
+/// validated against the same usage, so the limit is checked again as each one is applied.
+///
+/// The wrapper encodes and reports its metadata exactly like `S`, so wallets keep signing
+/// transactions as if `S` was used directly.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct FeelessGameplay<T, S>(pub S, PhantomData<T>);
+
+impl<T, S> From<S> for FeelessGameplay<T, S> {
+    fn from(inner: S) -> Self {
+        Self(inner, PhantomData)
+    }
+}
+
+impl<T, S: fmt::Debug> fmt::Debug for FeelessGameplay<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FeelessGameplay({:?})", self.0)
+    }
+}
+
+/// Whether the wrapped extension runs or the fee of the signer is waived
+#[derive(Clone)]
+pub enum Intermediate<Inner, AccountId> {
+    Apply(Inner),
+    Waive(AccountId),
+}
+
+impl<T, S> TransactionExtension<T::RuntimeCall> for FeelessGameplay<T, S>
+where
+    T: Config + Send + Sync,
+    S: TransactionExtension<T::RuntimeCall>,
+    DispatchOriginOf<T::RuntimeCall>: AsSystemOriginSigner<T::AccountId>,
+{
+    const IDENTIFIER: &'static str = S::IDENTIFIER;
+    type Implicit = S::Implicit;
+    type Val = Intermediate<S::Val, T::AccountId>;
+    type Pre = Intermediate<S::Pre, T::AccountId>;
+
+    fn implicit(&self) -> Result<Self::Implicit, TransactionValidityError> {
+        self.0.implicit()
+    }
+
+    fn metadata() -> Vec<TransactionExtensionMetadata> {
+        S::metadata()
+    }
+
+    fn weight(&self, call: &T::RuntimeCall) -> Weight {
+        // Profile lookup and feeless usage of the signer
+        self.0
+            .weight(call)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 1))
+    }
+
+    fn validate(
+        &self,
+        origin: DispatchOriginOf<T::RuntimeCall>,
+        call: &T::RuntimeCall,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+        self_implicit: Self::Implicit,
+        inherited_implication: &impl Implication,
+        source: TransactionSource,
+    ) -> ValidateResult<Self::Val, T::RuntimeCall> {
+        let feeless = origin
+            .as_system_origin_signer()
+            .filter(|who| Pallet::<T>::is_feeless(who, call))
+            .cloned();
+        if let Some(who) = feeless {
+            ensure_feeless_left::<T>(&who)?;
+            return Ok((
+                ValidTransaction::default(),
+                Intermediate::Waive(who),
+                origin,
+            ));
+        }
+
+        let (valid, val, origin) = self.0.validate(
+            origin,
+            call,
+            info,
+            len,
+            self_implicit,
+            inherited_implication,
+            source,
+        )?;
+        Ok((valid, Intermediate::Apply(val), origin))
+    }
+
+    fn prepare(
+        self,
+        val: Self::Val,
+        origin: &DispatchOriginOf<T::RuntimeCall>,
+        call: &T::RuntimeCall,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        match val {
+            Intermediate::Apply(val) => self
+                .0
+                .prepare(val, origin, call, info, len)
+                .map(Intermediate::Apply),
+            Intermediate::Waive(who) => {
+                ensure_feeless_left::<T>(&who)?;
+                Pallet::<T>::note_feeless(&who);
+                Ok(Intermediate::Waive(who))
+            }
+        }
+    }
+
+    fn post_dispatch_details(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<Weight, TransactionValidityError> {
+        match pre {
+            Intermediate::Apply(pre) => S::post_dispatch_details(pre, info, post_info, len, result),
+            Intermediate::Waive(who) => {
+                Pallet::<T>::deposit_event(Event::FeeWaived { who });
+                Ok(Weight::zero())
+            }
+        }
+    }
+}
+
+/// Fail with `ExhaustsResources` once `who` sent `MaxFeelessPerBlock` feeless transactions in
+/// the current block
+fn ensure_feeless_left<T: Config>(who: &T::AccountId) -> Result<(), TransactionValidityError> {
+    if Pallet::<T>::feeless_in_block(who) >= T::MaxFeelessPerBlock::get() {
+        return Err(InvalidTransaction::ExhaustsResources.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+pub mod extension;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use extension::FeelessGameplay;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{One, Saturating},
+        traits::Contains,
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_user_profile::UserStatsInspect;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Calls whose fees are waived for accounts with a profile
+        type FeelessCalls: Contains<<Self as frame_system::Config>::RuntimeCall>;
+        /// Profiles of the accounts fees are waived for
+        type UserStats: UserStatsInspect<Self::AccountId>;
+        /// Largest number of feeless transactions of an account in a block
+        #[pallet::constant]
+        type MaxFeelessPerBlock: Get<u32>;
+    }
+
+    /// Feeless transactions of each account in a block. Only the current block is kept, the
+    /// previous one is cleared when the next block starts.
+    #[pallet::storage]
+    pub type FeelessUsage<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Transaction fee of a game call waived for its signer
+        FeeWaived { who: T::AccountId },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            // Bounded by the transactions that fit in the previous block
+            let removed =
+                FeelessUsage::<T>::clear_prefix(n.saturating_sub(One::one()), u32::MAX, None);
+            T::DbWeight::get().reads_writes(removed.loops.into(), removed.unique.into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Whether the fee of `call` is waived when `who` signs it
+        pub fn is_feeless(
+            who: &T::AccountId,
+            call: &<T as frame_system::Config>::RuntimeCall,
+        ) -> bool {
+            T::FeelessCalls::contains(call) && T::UserStats::has_profile(who)
+        }
+
+        /// Feeless transactions `who` sent in the current block
+        pub fn feeless_in_block(who: &T::AccountId) -> u32 {
+            FeelessUsage::<T>::get(frame_system::Pallet::<T>::block_number(), who)
+        }
+
+        /// Count a feeless transaction of `who` in the current block
+        pub(crate) fn note_feeless(who: &T::AccountId) {
+            FeelessUsage::<T>::mutate(frame_system::Pallet::<T>::block_number(), who, |count| {
+                count.saturating_inc()
+            });
+        }
+    }
+}
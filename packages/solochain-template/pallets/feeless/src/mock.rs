@@ -0,0 +1,109 @@
+use crate as pallet_feeless;
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use frame_support::{
+    derive_impl,
+    traits::{ConstU32, Contains},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    impl_tx_ext_default,
+    traits::{DispatchInfoOf, DispatchOriginOf, Implication, TransactionExtension, ValidateResult},
+    transaction_validity::{InvalidTransaction, TransactionSource},
+    BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type UserProfile = pallet_user_profile::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type Feeless = pallet_feeless::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_user_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MaxUsernameLength = ConstU32<32>;
+    type MaxBioLength = ConstU32<256>;
+    type QuestProgress = ();
+    type OnStatsUpdated = ();
+}
+
+/// Remarks stand in for the game calls of the runtime
+pub struct GameCalls;
+
+impl Contains<RuntimeCall> for GameCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::System(frame_system::Call::remark { .. })
+                | RuntimeCall::UserProfile(pallet_user_profile::Call::set_username { .. })
+        )
+    }
+}
+
+impl pallet_feeless::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type FeelessCalls = GameCalls;
+    type UserStats = UserProfile;
+    type MaxFeelessPerBlock = ConstU32<2>;
+}
+
+/// Stand-in for the fee payment extension, rejecting every transaction as unable to pay
+#[derive(Clone, Debug, Encode, Decode, DecodeWithMemTracking, PartialEq, Eq, TypeInfo)]
+pub struct Charge;
+
+impl TransactionExtension<RuntimeCall> for Charge {
+    const IDENTIFIER: &'static str = "Charge";
+    type Implicit = ();
+    type Val = ();
+    type Pre = ();
+
+    fn validate(
+        &self,
+        _origin: DispatchOriginOf<RuntimeCall>,
+        _call: &RuntimeCall,
+        _info: &DispatchInfoOf<RuntimeCall>,
+        _len: usize,
+        _self_implicit: Self::Implicit,
+        _inherited_implication: &impl Implication,
+        _source: TransactionSource,
+    ) -> ValidateResult<Self::Val, RuntimeCall> {
+        Err(InvalidTransaction::Payment.into())
+    }
+
+    impl_tx_ext_default!(RuntimeCall; weight prepare);
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap()
+        .into()
+}
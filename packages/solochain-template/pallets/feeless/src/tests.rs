@@ -0,0 +1,155 @@
+use crate::{mock::*, Event, FeelessGameplay, FeelessUsage};
+use frame_support::{assert_ok, dispatch::GetDispatchInfo, traits::Hooks};
+use sp_runtime::{
+    traits::DispatchTransaction,
+    transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError},
+};
+
+type Extension = FeelessGameplay<Test, Charge>;
+
+fn remark() -> RuntimeCall {
+    RuntimeCall::System(frame_system::Call::remark {
+        remark: b"ride".to_vec(),
+    })
+}
+
+fn dispatch(who: u64, call: RuntimeCall) {
+    let info = call.get_dispatch_info();
+    let result = Extension::from(Charge)
+        .dispatch_transaction(RuntimeOrigin::signed(who), call, &info, 0, 0)
+        .unwrap();
+    assert_ok!(result);
+}
+
+fn validate(who: u64, call: &RuntimeCall) -> Result<(), TransactionValidityError> {
+    Extension::from(Charge)
+        .validate_only(
+            RuntimeOrigin::signed(who),
+            call,
+            &call.get_dispatch_info(),
+            0,
+            TransactionSource::External,
+            0,
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn fees_are_charged_without_a_profile() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            validate(1, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+    });
+}
+
+#[test]
+fn fees_are_waived_for_game_calls_of_players() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+        assert_ok!(validate(1, &remark()));
+
+        dispatch(1, remark());
+        System::assert_last_event(Event::FeeWaived { who: 1 }.into());
+    });
+}
+
+#[test]
+fn other_calls_still_pay_fees() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+        let call = RuntimeCall::UserProfile(pallet_user_profile::Call::update_profile {
+            username: None,
+            avatar: None,
+            bio: Some(Some(b"hello".to_vec())),
+        });
+        assert_eq!(validate(1, &call), Err(InvalidTransaction::Payment.into()));
+    });
+}
+
+#[test]
+fn renames_are_feeless_but_creating_a_profile_is_paid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let rename = |name: &[u8]| {
+            RuntimeCall::UserProfile(pallet_user_profile::Call::set_username {
+                username: name.to_vec(),
+            })
+        };
+        assert_eq!(
+            validate(1, &rename(b"rider")),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+        assert_ok!(validate(1, &rename(b"racer")));
+    });
+}
+
+#[test]
+fn feeless_transactions_are_rate_limited_per_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+
+        dispatch(1, remark());
+        dispatch(1, remark());
+        assert_eq!(FeelessUsage::<Test>::get(1, 1), 2);
+        assert_eq!(
+            validate(1, &remark()),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+
+        // The allowance is per block, and the usage of the previous one is cleared
+        System::set_block_number(2);
+        Feeless::on_initialize(2);
+        assert_eq!(FeelessUsage::<Test>::iter_prefix(1).count(), 0);
+        assert_eq!(Feeless::feeless_in_block(&1), 0);
+        assert_ok!(validate(1, &remark()));
+        dispatch(1, remark());
+        assert_eq!(FeelessUsage::<Test>::get(2, 1), 1);
+    });
+}
+
+#[test]
+fn limit_is_enforced_when_pooled_transactions_are_applied() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+
+        // Every pooled transaction is validated against the same usage
+        for _ in 0..3 {
+            assert_ok!(validate(1, &remark()));
+        }
+
+        dispatch(1, remark());
+        dispatch(1, remark());
+        let call = remark();
+        let info = call.get_dispatch_info();
+        assert_eq!(
+            Extension::from(Charge)
+                .dispatch_transaction(RuntimeOrigin::signed(1), call, &info, 0, 0)
+                .map(|_| ()),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+        assert_eq!(FeelessUsage::<Test>::get(1, 1), 2);
+    });
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use pallet_user_profile::Metric;
+use sp_std::vec;
+
+fn name<T: Config>() -> BoundedVec<u8, T::MaxQuestNameLength> {
+    BoundedVec::truncate_from(vec![b'q'; T::MaxQuestNameLength::get() as usize])
+}
+
+/// Requirements of the most expensive quest to verify, an attestation and counter conditions
+fn requirements<T: Config>() -> BoundedVec<Requirement, T::MaxRequirements> {
+    let mut requirements = vec![
+        Requirement::AtLeast {
+            metric: Metric::RacesCompleted,
+            count: 0,
+        };
+        T::MaxRequirements::get() as usize
+    ];
+    requirements[0] = Requirement::Attestation;
+    BoundedVec::truncate_from(requirements)
+}
+
+fn quest<T: Config>() -> QuestOf<T> {
+    Quest {
+        name: name::<T>(),
+        kind: QuestKind::Daily,
+        requirements: requirements::<T>(),
+        reward: 10u32.into(),
+        starts_at: 0u32.into(),
+        ends_at: None,
+    }
+}
+
+benchmarks! {
+    create_quest {
+        let origin = T::QuestOrigin::try_successful_origin().map_err(|_| "no quest origin")?;
+    }: _<T::RuntimeOrigin>(
+        origin,
+        name::<T>(),
+        QuestKind::Daily,
+        requirements::<T>(),
+        10u32.into(),
+        0u32.into(),
+        None
+    )
+    verify {
+        assert!(Quests::<T>::contains_key(0));
+    }
+
+    remove_quest {
+        Quests::<T>::insert(0, quest::<T>());
+        let origin = T::QuestOrigin::try_successful_origin().map_err(|_| "no quest origin")?;
+    }: _<T::RuntimeOrigin>(origin, 0)
+    verify {
+        assert!(!Quests::<T>::contains_key(0));
+    }
+
+    attest_completion {
+        Quests::<T>::insert(0, quest::<T>());
+        let who: T::AccountId = whitelisted_caller();
+        let origin = T::AttesterOrigin::try_successful_origin().map_err(|_| "no attester origin")?;
+    }: _<T::RuntimeOrigin>(origin, who.clone(), 0)
+    verify {
+        assert!(Completions::<T>::contains_key(&who, 0));
+    }
+
+    claim_quest_reward {
+        Quests::<T>::insert(0, quest::<T>());
+        let caller: T::AccountId = whitelisted_caller();
+        let period = Pallet::<T>::period_of(QuestKind::Daily);
+        Completions::<T>::insert(&caller, 0, (period, QuestStatus::Attested));
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert_eq!(Pallet::<T>::status(&caller, 0, period), Some(QuestStatus::Claimed));
+    }
+
+    record_progress {
+        // Every quest on the metric is completed by the recorded activity
+        let q in 0 .. T::MaxQuestsPerMetric::get();
+        let requirements = BoundedVec::truncate_from(vec![Requirement::AtLeast {
+            metric: Metric::RacesCompleted,
+            count: 1,
+        }]);
+        for quest_id in 0..q {
+            let quest = Quest {
+                requirements: requirements.clone(),
+                ..quest::<T>()
+            };
+            Quests::<T>::insert(quest_id, quest);
+        }
+        QuestsByMetric::<T>::insert(
+            Metric::RacesCompleted,
+            BoundedVec::truncate_from((0..q).collect::<sp_std::vec::Vec<_>>()),
+        );
+        let who: T::AccountId = whitelisted_caller();
+        let period = Pallet::<T>::period_of(QuestKind::Daily);
+    }: {
+        Pallet::<T>::record_progress(&who, Metric::RacesCompleted, 1);
+    }
+    verify {
+        for quest_id in 0..q {
+            assert_eq!(Pallet::<T>::status(&who, quest_id, period), Some(QuestStatus::Completed));
+        }
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
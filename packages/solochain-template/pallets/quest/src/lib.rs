@@ -0,0 +1,522 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+use frame_support::{traits::Get, weights::Weight};
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{AtLeast32BitUnsigned, SaturatedConversion, Zero},
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_rewards::{RewardAccrual, RewardReason};
+    use pallet_user_profile::{Metric, UserStatsInspect};
+    use sp_std::vec::Vec;
+
+    pub type QuestId = u32;
+
+    pub type QuestOf<T> = Quest<
+        <T as Config>::Balance,
+        BlockNumberFor<T>,
+        <T as Config>::MaxQuestNameLength,
+        <T as Config>::MaxRequirements,
+    >;
+
+    /// Days in the period of a weekly quest
+    pub const DAYS_PER_WEEK: u32 = 7;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to define quests
+        type QuestOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to attest quest completions
+        type AttesterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Balance of quest rewards
+        type Balance: AtLeast32BitUnsigned + Parameter + Member + MaxEncodedLen + Copy;
+        /// Credits claimed quest rewards as pending quest rewards
+        type Rewards: RewardAccrual<Self::AccountId, Self::Balance>;
+        /// Profiles and statistics that quest requirements are checked against
+        type UserStats: UserStatsInspect<Self::AccountId>;
+        /// Maximum length of a quest name
+        #[pallet::constant]
+        type MaxQuestNameLength: Get<u32>;
+        /// Largest number of requirements of a quest
+        #[pallet::constant]
+        type MaxRequirements: Get<u32>;
+        /// Largest number of quests with conditions on the same metric
+        #[pallet::constant]
+        type MaxQuestsPerMetric: Get<u32>;
+        /// Blocks in a day; daily quests reset every day and weekly quests every seven
+        #[pallet::constant]
+        type BlocksPerDay: Get<BlockNumberFor<Self>>;
+    }
+
+    /// How often a quest can be completed
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum QuestKind {
+        /// Completed once per account
+        Onboarding,
+        /// Completed once per day
+        Daily,
+        /// Completed once per week
+        Weekly,
+    }
+
+    /// Condition to meet before a quest reward can be claimed. Counter conditions of
+    /// onboarding quests are checked against lifetime statistics, those of daily and weekly
+    /// quests against the activity of the current day or week.
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum Requirement {
+        /// Completion confirmed by the attester origin
+        Attestation,
+        /// Counter of `metric` reached at least `count`
+        AtLeast { metric: Metric, count: u64 },
+    }
+
+    /// Quest defined by governance
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(NameLimit, RequirementLimit))]
+    pub struct Quest<Balance, BlockNumber, NameLimit: Get<u32>, RequirementLimit: Get<u32>> {
+        pub name: BoundedVec<u8, NameLimit>,
+        pub kind: QuestKind,
+        pub requirements: BoundedVec<Requirement, RequirementLimit>,
+        pub reward: Balance,
+        /// First block the quest can be claimed at
+        pub starts_at: BlockNumber,
+        /// Last block the quest can be claimed at, if it ends
+        pub ends_at: Option<BlockNumber>,
+    }
+
+    /// Progress of an account on a quest in a period
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum QuestStatus {
+        /// Completion attested, reward not claimed yet
+        Attested,
+        /// Conditions met on chain, reward not claimed yet
+        Completed,
+        /// Reward claimed
+        Claimed,
+    }
+
+    /// Activity of an account on a metric during its latest day and week
+    #[derive(
+        Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct PeriodCounters {
+        pub day: u32,
+        pub daily: u64,
+        pub week: u32,
+        pub weekly: u64,
+    }
+
+    #[pallet::storage]
+    pub type NextQuestId<T> = StorageValue<_, QuestId, ValueQuery>;
+
+    /// Map from QuestId to Quest
+    #[pallet::storage]
+    pub type Quests<T: Config> = StorageMap<_, Twox64Concat, QuestId, QuestOf<T>>;
+
+    /// Period of each account's latest progress on a quest and the progress made in it;
+    /// progress from an earlier period no longer counts
+    #[pallet::storage]
+    pub type Completions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        QuestId,
+        (u32, QuestStatus),
+    >;
+
+    /// Daily and weekly activity of each account by metric
+    #[pallet::storage]
+    pub type Counters<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        Metric,
+        PeriodCounters,
+        ValueQuery,
+    >;
+
+    /// Quests with a condition on each metric, checked when the metric is recorded
+    #[pallet::storage]
+    pub type QuestsByMetric<T: Config> =
+        StorageMap<_, Twox64Concat, Metric, BoundedVec<QuestId, T::MaxQuestsPerMetric>, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Quest defined
+        QuestCreated {
+            quest_id: QuestId,
+            kind: QuestKind,
+            reward: T::Balance,
+        },
+        /// Quest removed
+        QuestRemoved { quest_id: QuestId },
+        /// Completion of a quest attested for an account
+        QuestAttested {
+            quest_id: QuestId,
+            who: T::AccountId,
+            period: u32,
+        },
+        /// Conditions of a quest met on chain for an account
+        QuestCompleted {
+            quest_id: QuestId,
+            who: T::AccountId,
+            period: u32,
+        },
+        /// Quest reward claimed and credited to the account's pending rewards
+        QuestRewardClaimed {
+            quest_id: QuestId,
+            who: T::AccountId,
+            period: u32,
+            reward: T::Balance,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Quest does not exist
+        UnknownQuest,
+        /// Quest ends before it starts
+        InvalidWindow,
+        /// Too many quests have conditions on the same metric
+        TooManyQuests,
+        /// Quest is outside its active window
+        QuestNotActive,
+        /// Quest does not require an attestation
+        NotAttestable,
+        /// Completion was already attested this period
+        AlreadyAttested,
+        /// Reward was already claimed this period
+        AlreadyClaimed,
+        /// Quest requirements are not met
+        RequirementsNotMet,
+        /// Quest ids are exhausted
+        Overflow,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                !T::BlocksPerDay::get().is_zero(),
+                "BlocksPerDay must not be zero"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Define a quest claimable between `starts_at` and `ends_at`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::create_quest())]
+        pub fn create_quest(
+            origin: OriginFor<T>,
+            name: BoundedVec<u8, T::MaxQuestNameLength>,
+            kind: QuestKind,
+            requirements: BoundedVec<Requirement, T::MaxRequirements>,
+            reward: T::Balance,
+            starts_at: BlockNumberFor<T>,
+            ends_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::QuestOrigin::ensure_origin(origin)?;
+            ensure!(
+                ends_at.is_none_or(|ends_at| starts_at <= ends_at),
+                Error::<T>::InvalidWindow
+            );
+
+            let quest_id = NextQuestId::<T>::get();
+            let next_id = quest_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            for metric in Self::metrics(&requirements) {
+                QuestsByMetric::<T>::try_mutate(metric, |quests| {
+                    quests
+                        .try_push(quest_id)
+                        .map_err(|_| Error::<T>::TooManyQuests)
+                })?;
+            }
+            Quests::<T>::insert(
+                quest_id,
+                Quest {
+                    name,
+                    kind,
+                    requirements,
+                    reward,
+                    starts_at,
+                    ends_at,
+                },
+            );
+            NextQuestId::<T>::put(next_id);
+
+            Self::deposit_event(Event::QuestCreated {
+                quest_id,
+                kind,
+                reward,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a quest; progress on it is no longer claimable
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::remove_quest())]
+        pub fn remove_quest(origin: OriginFor<T>, quest_id: QuestId) -> DispatchResult {
+            T::QuestOrigin::ensure_origin(origin)?;
+            let quest = Quests::<T>::take(quest_id).ok_or(Error::<T>::UnknownQuest)?;
+            for metric in Self::metrics(&quest.requirements) {
+                QuestsByMetric::<T>::mutate(metric, |quests| quests.retain(|id| *id != quest_id));
+            }
+
+            Self::deposit_event(Event::QuestRemoved { quest_id });
+
+            Ok(())
+        }
+
+        /// Attest that `who` completed a quest in the current period
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::attest_completion())]
+        pub fn attest_completion(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            quest_id: QuestId,
+        ) -> DispatchResult {
+            T::AttesterOrigin::ensure_origin(origin)?;
+            let quest = Self::active_quest(quest_id)?;
+            ensure!(
+                quest.requirements.contains(&Requirement::Attestation),
+                Error::<T>::NotAttestable
+            );
+
+            let period = Self::period_of(quest.kind);
+            match Self::status(&who, quest_id, period) {
+                Some(QuestStatus::Claimed) => return Err(Error::<T>::AlreadyClaimed.into()),
+                Some(_) => return Err(Error::<T>::AlreadyAttested.into()),
+                None => {}
+            }
+            Completions::<T>::insert(&who, quest_id, (period, QuestStatus::Attested));
+
+            Self::deposit_event(Event::QuestAttested {
+                quest_id,
+                who,
+                period,
+            });
+
+            Ok(())
+        }
+
+        /// Claim the reward of a quest completed in the current period
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::claim_quest_reward())]
+        pub fn claim_quest_reward(origin: OriginFor<T>, quest_id: QuestId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let quest = Self::active_quest(quest_id)?;
+
+            let period = Self::period_of(quest.kind);
+            let status = Self::status(&who, quest_id, period);
+            ensure!(
+                status != Some(QuestStatus::Claimed),
+                Error::<T>::AlreadyClaimed
+            );
+            let attested = status == Some(QuestStatus::Attested);
+            ensure!(
+                Self::requirements_met(&who, &quest, attested),
+                Error::<T>::RequirementsNotMet
+            );
+
+            if !quest.reward.is_zero() {
+                T::Rewards::accrue(&who, RewardReason::Quest, quest.reward)?;
+            }
+            Completions::<T>::insert(&who, quest_id, (period, QuestStatus::Claimed));
+
+            Self::deposit_event(Event::QuestRewardClaimed {
+                quest_id,
+                who,
+                period,
+                reward: quest.reward,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Day index of the current block
+        pub fn current_day() -> u32 {
+            let now = frame_system::Pallet::<T>::block_number();
+            (now / T::BlocksPerDay::get()).saturated_into()
+        }
+
+        /// Current period of quests of `kind`; onboarding quests have a single period
+        pub fn period_of(kind: QuestKind) -> u32 {
+            match kind {
+                QuestKind::Onboarding => 0,
+                QuestKind::Daily => Self::current_day(),
+                QuestKind::Weekly => Self::current_day() / DAYS_PER_WEEK,
+            }
+        }
+
+        /// Progress of `who` on a quest in `period`
+        pub fn status(who: &T::AccountId, quest_id: QuestId, period: u32) -> Option<QuestStatus> {
+            Completions::<T>::get(who, quest_id)
+                .filter(|(completed_in, _)| *completed_in == period)
+                .map(|(_, status)| status)
+        }
+
+        /// Quest `quest_id` if it is inside its active window
+        fn active_quest(quest_id: QuestId) -> Result<QuestOf<T>, DispatchError> {
+            let quest = Quests::<T>::get(quest_id).ok_or(Error::<T>::UnknownQuest)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                quest.starts_at <= now && quest.ends_at.is_none_or(|ends_at| now <= ends_at),
+                Error::<T>::QuestNotActive
+            );
+            Ok(quest)
+        }
+
+        /// Distinct metrics the counter conditions of `requirements` are on
+        fn metrics(requirements: &[Requirement]) -> Vec<Metric> {
+            let mut metrics = Vec::new();
+            for requirement in requirements {
+                if let Requirement::AtLeast { metric, .. } = requirement {
+                    if !metrics.contains(metric) {
+                        metrics.push(*metric);
+                    }
+                }
+            }
+            metrics
+        }
+
+        /// Counter of `metric` for `who` that quests of `kind` are checked against
+        pub fn count(who: &T::AccountId, metric: Metric, kind: QuestKind) -> u64 {
+            match kind {
+                QuestKind::Onboarding => {
+                    let stats = T::UserStats::stats(who);
+                    match metric {
+                        Metric::UsernameSet => u64::from(T::UserStats::has_profile(who)),
+                        Metric::RacesCompleted => stats.total_races.into(),
+                        Metric::RacesWon => stats.wins.into(),
+                        Metric::DistanceRidden => stats.total_distance,
+                    }
+                }
+                QuestKind::Daily => {
+                    let counters = Counters::<T>::get(who, metric);
+                    if counters.day == Self::current_day() {
+                        counters.daily
+                    } else {
+                        0
+                    }
+                }
+                QuestKind::Weekly => {
+                    let counters = Counters::<T>::get(who, metric);
+                    if counters.week == Self::current_day() / DAYS_PER_WEEK {
+                        counters.weekly
+                    } else {
+                        0
+                    }
+                }
+            }
+        }
+
+        /// Whether `who` meets all requirements of `quest`
+        fn requirements_met(who: &T::AccountId, quest: &QuestOf<T>, attested: bool) -> bool {
+            quest
+                .requirements
+                .iter()
+                .all(|requirement| match requirement {
+                    Requirement::Attestation => attested,
+                    Requirement::AtLeast { metric, count } => {
+                        Self::count(who, *metric, quest.kind) >= *count
+                    }
+                })
+        }
+
+        /// Count `amount` more of `metric` for `who` and complete the quests whose
+        /// conditions are now met
+        pub(crate) fn record_progress(who: &T::AccountId, metric: Metric, amount: u64) {
+            let day = Self::current_day();
+            let week = day / DAYS_PER_WEEK;
+            Counters::<T>::mutate(who, metric, |counters| {
+                if counters.day != day {
+                    counters.day = day;
+                    counters.daily = 0;
+                }
+                if counters.week != week {
+                    counters.week = week;
+                    counters.weekly = 0;
+                }
+                counters.daily = counters.daily.saturating_add(amount);
+                counters.weekly = counters.weekly.saturating_add(amount);
+            });
+
+            for quest_id in QuestsByMetric::<T>::get(metric) {
+                let Ok(quest) = Self::active_quest(quest_id) else {
+                    continue;
+                };
+                let period = Self::period_of(quest.kind);
+                if Self::status(who, quest_id, period).is_some()
+                    || !Self::requirements_met(who, &quest, false)
+                {
+                    continue;
+                }
+                Completions::<T>::insert(who, quest_id, (period, QuestStatus::Completed));
+
+                Self::deposit_event(Event::QuestCompleted {
+                    quest_id,
+                    who: who.clone(),
+                    period,
+                });
+            }
+        }
+    }
+}
+
+impl<T: Config> pallet_user_profile::QuestProgress<T::AccountId> for Pallet<T> {
+    fn record(who: &T::AccountId, metric: pallet_user_profile::Metric, amount: u64) {
+        Self::record_progress(who, metric, amount);
+    }
+
+    fn record_weight() -> Weight {
+        T::WeightInfo::record_progress(T::MaxQuestsPerMetric::get())
+    }
+}
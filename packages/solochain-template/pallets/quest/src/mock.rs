@@ -0,0 +1,121 @@
+use crate as pallet_quest;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64, WithdrawReasons},
+};
+use sp_runtime::{traits::ConvertInto, BuildStorage};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type Balances = pallet_balances::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type UserProfile = pallet_user_profile::Pallet<Test>;
+
+    #[runtime::pallet_index(3)]
+    pub type Rewards = pallet_rewards::Pallet<Test>;
+
+    #[runtime::pallet_index(4)]
+    pub type Quest = pallet_quest::Pallet<Test>;
+
+    #[runtime::pallet_index(5)]
+    pub type Vesting = pallet_vesting::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+impl pallet_user_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MaxUsernameLength = ConstU32<32>;
+    type MaxBioLength = ConstU32<256>;
+    type QuestProgress = Quest;
+    type OnStatsUpdated = ();
+}
+
+parameter_types! {
+    pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+        WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = ConstU64<1>;
+    type WeightInfo = ();
+    type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+    type BlockNumberProvider = System;
+    const MAX_VESTING_SCHEDULES: u32 = 3;
+}
+
+impl pallet_rewards::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type DistributionOrigin = frame_system::EnsureRoot<u64>;
+    type EmissionOrigin = frame_system::EnsureRoot<u64>;
+    type RewardToken = Balances;
+    type UserStats = UserProfile;
+    type Vesting = Vesting;
+    type VestingThreshold = ConstU64<300>;
+    type VestingDuration = ConstU64<50>;
+    type MaxHistory = ConstU32<3>;
+    type BlocksPerDay = ConstU64<10>;
+}
+
+impl pallet_quest::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type QuestOrigin = frame_system::EnsureRoot<u64>;
+    type AttesterOrigin = frame_system::EnsureRoot<u64>;
+    type Balance = u64;
+    type Rewards = Rewards;
+    type UserStats = UserProfile;
+    type MaxQuestNameLength = ConstU32<32>;
+    type MaxRequirements = ConstU32<4>;
+    type MaxQuestsPerMetric = ConstU32<2>;
+    type BlocksPerDay = ConstU64<10>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 100), (2, 100)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
+}
@@ -0,0 +1,396 @@
+use crate::{
+    mock::*, Completions, Counters, Error, Event, QuestKind, QuestStatus, QuestsByMetric,
+    Requirement,
+};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_rewards::RewardReason;
+use pallet_user_profile::{Metric, QuestProgress, UserStatsHandler};
+use sp_runtime::DispatchError;
+
+fn pending(who: u64) -> u64 {
+    pallet_rewards::Rewards::<Test>::get(who, RewardReason::Quest)
+}
+
+fn at_least(metric: Metric, count: u64) -> Requirement {
+    Requirement::AtLeast { metric, count }
+}
+
+/// Report the race activity the race pallet would after a finished race
+fn finish_race(who: u64, distance: u64) {
+    UserProfile::record_race(&who, false, distance);
+    Quest::record(&who, Metric::RacesCompleted, 1);
+    Quest::record(&who, Metric::DistanceRidden, distance);
+}
+
+fn create_quest(kind: QuestKind, requirements: Vec<Requirement>) {
+    create_quest_in(kind, requirements, 0, None);
+}
+
+fn create_quest_in(
+    kind: QuestKind,
+    requirements: Vec<Requirement>,
+    starts_at: u64,
+    ends_at: Option<u64>,
+) {
+    assert_ok!(Quest::create_quest(
+        RuntimeOrigin::root(),
+        BoundedVec::truncate_from(b"quest".to_vec()),
+        kind,
+        BoundedVec::truncate_from(requirements),
+        10,
+        starts_at,
+        ends_at
+    ));
+}
+
+#[test]
+fn create_quest_validates_origin_and_window() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let name = BoundedVec::truncate_from(b"quest".to_vec());
+        let races = BoundedVec::truncate_from(vec![at_least(Metric::RacesCompleted, 3)]);
+
+        assert_noop!(
+            Quest::create_quest(
+                RuntimeOrigin::signed(1),
+                name.clone(),
+                QuestKind::Onboarding,
+                races.clone(),
+                10,
+                0,
+                None
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Quest::create_quest(
+                RuntimeOrigin::root(),
+                name,
+                QuestKind::Onboarding,
+                races,
+                10,
+                5,
+                Some(4)
+            ),
+            Error::<Test>::InvalidWindow
+        );
+
+        create_quest(QuestKind::Daily, vec![Requirement::Attestation]);
+        System::assert_last_event(
+            Event::QuestCreated {
+                quest_id: 0,
+                kind: QuestKind::Daily,
+                reward: 10,
+            }
+            .into(),
+        );
+        assert_eq!(crate::NextQuestId::<Test>::get(), 1);
+    });
+}
+
+#[test]
+fn onboarding_quest_is_verified_from_chain_state() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(
+            QuestKind::Onboarding,
+            vec![
+                at_least(Metric::UsernameSet, 1),
+                at_least(Metric::RacesCompleted, 1),
+            ],
+        );
+
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RequirementsNotMet
+        );
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RequirementsNotMet
+        );
+        finish_race(1, 1_000);
+
+        System::assert_last_event(
+            Event::QuestCompleted {
+                quest_id: 0,
+                who: 1,
+                period: 0,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Completions::<Test>::get(1, 0),
+            Some((0, QuestStatus::Completed))
+        );
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+
+        System::assert_last_event(
+            Event::QuestRewardClaimed {
+                quest_id: 0,
+                who: 1,
+                period: 0,
+                reward: 10,
+            }
+            .into(),
+        );
+        assert_eq!(pending(1), 10);
+        assert_eq!(
+            Completions::<Test>::get(1, 0),
+            Some((0, QuestStatus::Claimed))
+        );
+
+        // Onboarding quests never reset
+        System::set_block_number(100);
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::AlreadyClaimed
+        );
+    });
+}
+
+#[test]
+fn attested_daily_quest_resets_every_day() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(QuestKind::Daily, vec![Requirement::Attestation]);
+
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RequirementsNotMet
+        );
+        assert_noop!(
+            Quest::attest_completion(RuntimeOrigin::signed(1), 1, 0),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Quest::attest_completion(RuntimeOrigin::root(), 1, 0));
+        System::assert_last_event(
+            Event::QuestAttested {
+                quest_id: 0,
+                who: 1,
+                period: 0,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Quest::attest_completion(RuntimeOrigin::root(), 1, 0),
+            Error::<Test>::AlreadyAttested
+        );
+
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+        assert_noop!(
+            Quest::attest_completion(RuntimeOrigin::root(), 1, 0),
+            Error::<Test>::AlreadyClaimed
+        );
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::AlreadyClaimed
+        );
+
+        // Next day the quest starts over
+        System::set_block_number(10);
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RequirementsNotMet
+        );
+        assert_ok!(Quest::attest_completion(RuntimeOrigin::root(), 1, 0));
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(pending(1), 20);
+    });
+}
+
+#[test]
+fn weekly_quest_resets_every_seven_days() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(QuestKind::Weekly, vec![Requirement::Attestation]);
+        assert_ok!(Quest::attest_completion(RuntimeOrigin::root(), 1, 0));
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+
+        System::set_block_number(69);
+        assert_noop!(
+            Quest::attest_completion(RuntimeOrigin::root(), 1, 0),
+            Error::<Test>::AlreadyClaimed
+        );
+
+        System::set_block_number(70);
+        assert_ok!(Quest::attest_completion(RuntimeOrigin::root(), 1, 0));
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(
+            Completions::<Test>::get(1, 0),
+            Some((1, QuestStatus::Claimed))
+        );
+    });
+}
+
+#[test]
+fn quests_are_only_claimable_while_active() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest_in(QuestKind::Onboarding, vec![], 5, Some(8));
+        create_quest(
+            QuestKind::Onboarding,
+            vec![at_least(Metric::UsernameSet, 1)],
+        );
+
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::QuestNotActive
+        );
+        assert_noop!(
+            Quest::attest_completion(RuntimeOrigin::root(), 1, 1),
+            Error::<Test>::NotAttestable
+        );
+
+        System::set_block_number(9);
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::QuestNotActive
+        );
+
+        System::set_block_number(8);
+        assert_ok!(Quest::remove_quest(RuntimeOrigin::root(), 0));
+        System::assert_last_event(Event::QuestRemoved { quest_id: 0 }.into());
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::UnknownQuest
+        );
+    });
+}
+
+#[test]
+fn set_username_completes_onboarding_quest() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(
+            QuestKind::Onboarding,
+            vec![at_least(Metric::UsernameSet, 1)],
+        );
+
+        assert_ok!(UserProfile::set_username(
+            RuntimeOrigin::signed(1),
+            b"rider".to_vec()
+        ));
+
+        System::assert_has_event(
+            Event::QuestCompleted {
+                quest_id: 0,
+                who: 1,
+                period: 0,
+            }
+            .into(),
+        );
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(pending(1), 10);
+    });
+}
+
+#[test]
+fn daily_conditions_count_todays_activity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(
+            QuestKind::Daily,
+            vec![
+                at_least(Metric::RacesCompleted, 3),
+                at_least(Metric::DistanceRidden, 500),
+            ],
+        );
+
+        finish_race(1, 200);
+        finish_race(1, 200);
+        assert_eq!(Completions::<Test>::get(1, 0), None);
+        assert_noop!(
+            Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RequirementsNotMet
+        );
+
+        finish_race(1, 200);
+        System::assert_has_event(
+            Event::QuestCompleted {
+                quest_id: 0,
+                who: 1,
+                period: 0,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Quest::count(&1, Metric::RacesCompleted, QuestKind::Daily),
+            3
+        );
+
+        // Yesterday's races no longer count
+        System::set_block_number(10);
+        assert_eq!(
+            Quest::count(&1, Metric::RacesCompleted, QuestKind::Daily),
+            0
+        );
+        finish_race(1, 600);
+        assert_eq!(
+            Completions::<Test>::get(1, 0),
+            Some((0, QuestStatus::Completed))
+        );
+        assert_eq!(Counters::<Test>::get(1, Metric::RacesCompleted).daily, 1);
+        assert_eq!(Counters::<Test>::get(1, Metric::RacesCompleted).weekly, 4);
+        assert_eq!(
+            Quest::count(&1, Metric::RacesCompleted, QuestKind::Onboarding),
+            4
+        );
+    });
+}
+
+#[test]
+fn attested_quests_are_not_completed_automatically() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(
+            QuestKind::Weekly,
+            vec![Requirement::Attestation, at_least(Metric::RacesWon, 1)],
+        );
+
+        Quest::record(&1, Metric::RacesWon, 1);
+        assert_eq!(Completions::<Test>::get(1, 0), None);
+
+        assert_ok!(Quest::attest_completion(RuntimeOrigin::root(), 1, 0));
+        assert_ok!(Quest::claim_quest_reward(RuntimeOrigin::signed(1), 0));
+    });
+}
+
+#[test]
+fn quests_per_metric_are_bounded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_quest(QuestKind::Daily, vec![at_least(Metric::RacesWon, 1)]);
+        create_quest(
+            QuestKind::Weekly,
+            vec![at_least(Metric::RacesWon, 1), at_least(Metric::RacesWon, 2)],
+        );
+        assert_eq!(
+            QuestsByMetric::<Test>::get(Metric::RacesWon).to_vec(),
+            vec![0, 1]
+        );
+
+        assert_noop!(
+            Quest::create_quest(
+                RuntimeOrigin::root(),
+                BoundedVec::truncate_from(b"quest".to_vec()),
+                QuestKind::Daily,
+                BoundedVec::truncate_from(vec![at_least(Metric::RacesWon, 3)]),
+                10,
+                0,
+                None
+            ),
+            Error::<Test>::TooManyQuests
+        );
+
+        assert_ok!(Quest::remove_quest(RuntimeOrigin::root(), 0));
+        assert_eq!(
+            QuestsByMetric::<Test>::get(Metric::RacesWon).to_vec(),
+            vec![1]
+        );
+        create_quest(QuestKind::Daily, vec![at_least(Metric::RacesWon, 3)]);
+    });
+}
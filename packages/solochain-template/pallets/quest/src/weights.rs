@@ -0,0 +1,131 @@
+//! Autogenerated weights for pallet_quest
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-01-01, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `localhost`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_quest.
+pub trait WeightInfo {
+	fn create_quest() -> Weight;
+	fn remove_quest() -> Weight;
+	fn attest_completion() -> Weight;
+	fn claim_quest_reward() -> Weight;
+	fn record_progress(q: u32, ) -> Weight;
+}
+
+/// Weights for pallet_quest using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Quest NextQuestId (r:1 w:1)
+	/// Storage: Quest QuestsByMetric (r:1 w:1)
+	/// Storage: Quest Quests (r:0 w:1)
+	fn create_quest() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Quest Quests (r:1 w:1)
+	/// Storage: Quest QuestsByMetric (r:1 w:1)
+	fn remove_quest() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Quest Quests (r:1 w:0)
+	/// Storage: Quest Completions (r:1 w:1)
+	fn attest_completion() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Quest Quests (r:1 w:0)
+	/// Storage: Quest Completions (r:1 w:1)
+	/// Storage: Quest Counters (r:1 w:0)
+	/// Storage: Rewards Emission (r:1 w:0)
+	/// Storage: Rewards DailyPool (r:1 w:1)
+	/// Storage: Rewards DailyPayouts (r:1 w:1)
+	/// Storage: Rewards Rewards (r:1 w:1)
+	/// Storage: Rewards RewardHistory (r:1 w:1)
+	fn claim_quest_reward() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 58_000_000 picoseconds.
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Quest Counters (r:1 w:1)
+	/// Storage: Quest QuestsByMetric (r:1 w:0)
+	/// Storage: Quest Quests (r:q w:0)
+	/// Storage: Quest Completions (r:q w:q)
+	/// Storage: UserProfile UserStatsMap (r:q w:0)
+	/// Storage: UserProfile Profiles (r:q w:0)
+	/// The range of component `q` is `[0, 32]`.
+	fn record_progress(q: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(14_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(q.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_quest() -> Weight {
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn remove_quest() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn attest_completion() -> Weight {
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn claim_quest_reward() -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn record_progress(q: u32, ) -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(Weight::from_parts(14_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(q.into())))
+	}
+}
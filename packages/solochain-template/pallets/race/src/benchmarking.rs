@@ -0,0 +1,389 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    sp_runtime::{
+        traits::{Saturating, Zero},
+        Percent,
+    },
+    traits::fungible::{Inspect, InspectHold, Mutate},
+    BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_bike::BikeRacing;
+use pallet_character::CharacterInspect;
+use sp_core::{ed25519, Pair, H256};
+
+const SEED: u32 = 0;
+
+fn map<T: Config>() -> MapInfoOf<T> {
+    MapInfo {
+        name: BoundedVec::truncate_from(b"benchmark".to_vec()),
+        distance: 1_000,
+        difficulty: 1,
+        energy_cost: 1,
+        water_cost: 1,
+        base_reward: 1_000u32.into(),
+        enabled: true,
+    }
+}
+
+/// Make map 0 available to races and lobbies
+fn insert_map<T: Config>() {
+    Maps::<T>::insert(0, map::<T>());
+}
+
+fn start<T: Config>(owner: &T::AccountId) -> RaceId {
+    insert_map::<T>();
+    let character_id = T::Characters::create_for_benchmark(owner);
+    let bike_id = T::Bikes::create_for_benchmark(owner);
+    Pallet::<T>::start_race(
+        RawOrigin::Signed(owner.clone()).into(),
+        character_id,
+        bike_id,
+        0,
+    )
+    .unwrap();
+    NextRaceId::<T>::get() - 1
+}
+
+fn entry_fee<T: Config>() -> NativeBalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(10u32.into())
+}
+
+fn funded<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+    let who: T::AccountId = account(name, index, SEED);
+    let balance = entry_fee::<T>()
+        .saturating_mul(10u32.into())
+        .saturating_add(T::DisputeBond::get());
+    T::Currency::set_balance(&who, balance);
+    who
+}
+
+/// Create a lobby with `n` participants, returning its id and deadline
+fn lobby_with<T: Config>(n: u32) -> (LobbyId, BlockNumberFor<T>) {
+    insert_map::<T>();
+    let host = funded::<T>("host", 0);
+    let deadline = frame_system::Pallet::<T>::block_number() + 100u32.into();
+    Pallet::<T>::create_lobby(
+        RawOrigin::Signed(host).into(),
+        0,
+        T::MaxLobbyParticipants::get(),
+        entry_fee::<T>(),
+        deadline,
+    )
+    .unwrap();
+    let lobby_id = NextLobbyId::<T>::get() - 1;
+    for index in 1..n {
+        let player = funded::<T>("player", index);
+        Pallet::<T>::join_lobby(RawOrigin::Signed(player).into(), lobby_id).unwrap();
+    }
+    (lobby_id, deadline)
+}
+
+fn server_key(seed: u8) -> ServerKey {
+    ServerKey::Ed25519(ed25519::Pair::from_seed(&[seed; 32]).public())
+}
+
+/// Register a game server and sign the SCALE encoding of `payload` with it
+fn signed<T: Config>(payload: impl Encode) -> (ServerKey, ServerSignature) {
+    let pair = ed25519::Pair::from_seed(&[1u8; 32]);
+    let key = ServerKey::Ed25519(pair.public());
+    GameServers::<T>::insert(&key, frame_system::Pallet::<T>::block_number());
+    (key, ServerSignature::Ed25519(pair.sign(&payload.encode())))
+}
+
+fn result() -> RaceResult {
+    RaceResult {
+        position: 1,
+        time_ms: 600_000,
+        distance: 1_000,
+        best_lap_ms: 200_000,
+        ghost_hash: Some(H256::repeat_byte(1)),
+    }
+}
+
+/// Create a lobby with `n` participants and post placements that are open to disputes
+fn settling<T: Config>(n: u32) -> LobbyId {
+    let (lobby_id, _) = lobby_with::<T>(n);
+    let placements = Lobbies::<T>::get(lobby_id).unwrap().participants;
+    let (server, signature) = signed::<T>((LOBBY_RESULT_SIGNING_CONTEXT, lobby_id, &placements));
+    let origin = T::ResultOrigin::try_successful_origin().unwrap();
+    Pallet::<T>::submit_lobby_result(origin, lobby_id, placements, server, signature).unwrap();
+    lobby_id
+}
+
+/// Start a race and post a result that is open to disputes
+fn completed<T: Config>() -> RaceId {
+    let owner: T::AccountId = whitelisted_caller();
+    let race_id = start::<T>(&owner);
+    let origin = T::ResultOrigin::try_successful_origin().unwrap();
+    let (server, signature) = signed::<T>((RESULT_SIGNING_CONTEXT, race_id, &result()));
+    Pallet::<T>::submit_race_result(origin, race_id, result(), server, signature).unwrap();
+    race_id
+}
+
+/// Result ridden at the speed limit of the bike of `race_id`, which is always flagged
+fn fastest_result<T: Config>(race_id: RaceId) -> RaceResult {
+    let speed_limit = Races::<T>::get(race_id).unwrap().speed_limit.max(1) as u64;
+    RaceResult {
+        position: 1,
+        time_ms: 1_000_000_000 / speed_limit + 1,
+        ..result()
+    }
+}
+
+benchmarks! {
+    start_race {
+        let caller: T::AccountId = whitelisted_caller();
+        let character_id = T::Characters::create_for_benchmark(&caller);
+        let bike_id = T::Bikes::create_for_benchmark(&caller);
+        insert_map::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), character_id, bike_id, 0)
+    verify {
+        assert_eq!(ActiveRace::<T>::get(&caller), Some(0));
+    }
+
+    submit_race_result {
+        let caller: T::AccountId = whitelisted_caller();
+        let race_id = start::<T>(&caller);
+        let origin = T::ResultOrigin::try_successful_origin().map_err(|_| "no result origin")?;
+        // Worst case: the result is flagged and bans its owner
+        let result = fastest_result::<T>(race_id);
+        SuspiciousResults::<T>::insert(&caller, T::SuspicionThreshold::get().saturating_sub(1));
+        let (server, signature) = signed::<T>((RESULT_SIGNING_CONTEXT, race_id, &result));
+    }: _<T::RuntimeOrigin>(origin, race_id, result.clone(), server, signature)
+    verify {
+        assert_eq!(RaceResults::<T>::get(race_id), Some(result));
+        assert!(RankedBans::<T>::contains_key(&caller));
+    }
+
+    claim_race_reward {
+        let caller: T::AccountId = whitelisted_caller();
+        let race_id = start::<T>(&caller);
+        let origin = T::ResultOrigin::try_successful_origin().map_err(|_| "no result origin")?;
+        let (server, signature) = signed::<T>((RESULT_SIGNING_CONTEXT, race_id, &result()));
+        Pallet::<T>::submit_race_result(origin, race_id, result(), server, signature)?;
+        let challenge_ends = frame_system::Pallet::<T>::block_number() + T::ChallengePeriod::get();
+        frame_system::Pallet::<T>::set_block_number(challenge_ends);
+        // Worst case: the run tops a full leaderboard
+        let mut board = BoundedVec::new();
+        for index in 0..T::MaxLeaderboardSize::get() {
+            let entry = LeaderboardEntry {
+                account: account("rival", index, SEED),
+                time_ms: result().time_ms + 1,
+                race_id: 0,
+                ghost_hash: None,
+            };
+            board.try_push(entry).map_err(|_| "leaderboard full")?;
+        }
+        Leaderboards::<T>::insert(0, board);
+    }: _(RawOrigin::Signed(caller.clone()), race_id)
+    verify {
+        assert!(matches!(Races::<T>::get(race_id).unwrap().status, RaceStatus::Claimed));
+        assert_eq!(Leaderboards::<T>::get(0)[0].account, caller);
+    }
+
+    cancel_race {
+        let caller: T::AccountId = whitelisted_caller();
+        let race_id = start::<T>(&caller);
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number().saturating_add(T::RaceTimeout::get()),
+        );
+    }: _(RawOrigin::Signed(caller.clone()), race_id)
+    verify {
+        assert_eq!(Races::<T>::get(race_id).unwrap().status, RaceStatus::Cancelled);
+        assert!(!ActiveRace::<T>::contains_key(&caller));
+    }
+
+    register_server_key {
+        let origin = T::ServerManagerOrigin::try_successful_origin()
+            .map_err(|_| "no server manager origin")?;
+    }: _<T::RuntimeOrigin>(origin, server_key(1))
+    verify {
+        assert!(GameServers::<T>::contains_key(server_key(1)));
+    }
+
+    rotate_server_key {
+        let origin = T::ServerManagerOrigin::try_successful_origin()
+            .map_err(|_| "no server manager origin")?;
+        GameServers::<T>::insert(server_key(1), frame_system::Pallet::<T>::block_number());
+    }: _<T::RuntimeOrigin>(origin, server_key(1), server_key(2))
+    verify {
+        assert!(!GameServers::<T>::contains_key(server_key(1)));
+        assert!(GameServers::<T>::contains_key(server_key(2)));
+    }
+
+    create_lobby {
+        insert_map::<T>();
+        let caller = funded::<T>("host", 0);
+        let deadline = frame_system::Pallet::<T>::block_number() + 100u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), 0, T::MaxLobbyParticipants::get(), entry_fee::<T>(), deadline)
+    verify {
+        assert_eq!(Lobbies::<T>::get(0).unwrap().host, caller);
+    }
+
+    join_lobby {
+        let n in 1 .. T::MaxLobbyParticipants::get() - 1;
+        let (lobby_id, _) = lobby_with::<T>(n);
+        let caller = funded::<T>("joiner", 0);
+    }: _(RawOrigin::Signed(caller.clone()), lobby_id)
+    verify {
+        assert!(Lobbies::<T>::get(lobby_id).unwrap().participants.contains(&caller));
+    }
+
+    cancel_lobby {
+        let n in 1 .. T::MaxLobbyParticipants::get();
+        let (lobby_id, _) = lobby_with::<T>(n);
+        let host = Lobbies::<T>::get(lobby_id).unwrap().host;
+    }: _(RawOrigin::Signed(host.clone()), lobby_id)
+    verify {
+        assert!(T::Currency::balance_on_hold(&HoldReason::LobbyEntryFee.into(), &host).is_zero());
+    }
+
+    refund_lobby {
+        let n in 1 .. T::MinLobbyParticipants::get().saturating_sub(1).max(1);
+        let (lobby_id, deadline) = lobby_with::<T>(n);
+        frame_system::Pallet::<T>::set_block_number(deadline + 1u32.into());
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), lobby_id)
+    verify {
+        assert_eq!(Lobbies::<T>::get(lobby_id).unwrap().status, LobbyStatus::Cancelled);
+    }
+
+    submit_lobby_result {
+        let n in T::MinLobbyParticipants::get() .. T::MaxLobbyParticipants::get();
+        let (lobby_id, _) = lobby_with::<T>(n);
+        let placements = Lobbies::<T>::get(lobby_id).unwrap().participants;
+        let (server, signature) = signed::<T>((LOBBY_RESULT_SIGNING_CONTEXT, lobby_id, &placements));
+        let origin = T::ResultOrigin::try_successful_origin().map_err(|_| "no result origin")?;
+    }: _<T::RuntimeOrigin>(origin, lobby_id, placements, server, signature)
+    verify {
+        assert_eq!(Lobbies::<T>::get(lobby_id).unwrap().status, LobbyStatus::Settling);
+    }
+
+    settle_lobby {
+        let n in T::MinLobbyParticipants::get() .. T::MaxLobbyParticipants::get();
+        let lobby_id = settling::<T>(n);
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number().saturating_add(T::ChallengePeriod::get()),
+        );
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), lobby_id)
+    verify {
+        assert_eq!(Lobbies::<T>::get(lobby_id).unwrap().status, LobbyStatus::Settled);
+    }
+
+    dispute_lobby_result {
+        let lobby_id = settling::<T>(T::MinLobbyParticipants::get());
+        let caller = Lobbies::<T>::get(lobby_id).unwrap().host;
+    }: _(RawOrigin::Signed(caller.clone()), lobby_id)
+    verify {
+        assert_eq!(LobbyDisputes::<T>::get(lobby_id).unwrap().disputer, caller);
+    }
+
+    resolve_lobby_dispute {
+        let n in T::MinLobbyParticipants::get() .. T::MaxLobbyParticipants::get();
+        let lobby_id = settling::<T>(n);
+        let host = Lobbies::<T>::get(lobby_id).unwrap().host;
+        Pallet::<T>::dispute_lobby_result(RawOrigin::Signed(host).into(), lobby_id)?;
+        let origin = T::ArbiterOrigin::try_successful_origin().map_err(|_| "no arbiter origin")?;
+        let mut corrected = Lobbies::<T>::get(lobby_id).unwrap().participants;
+        corrected.reverse();
+    }: _<T::RuntimeOrigin>(origin, lobby_id, Some(corrected.clone()))
+    verify {
+        assert_eq!(Lobbies::<T>::get(lobby_id).unwrap().status, LobbyStatus::Settled);
+        assert!(!LobbyDisputes::<T>::contains_key(lobby_id));
+    }
+
+    set_payout_curve {
+        let origin = T::GameAdminOrigin::try_successful_origin().map_err(|_| "no admin origin")?;
+        let mut curve = BoundedVec::new();
+        curve.try_push(Percent::from_percent(70)).map_err(|_| "curve too long")?;
+        curve.try_push(Percent::from_percent(30)).map_err(|_| "curve too long")?;
+    }: _<T::RuntimeOrigin>(origin, curve)
+    verify {
+        assert_eq!(PayoutCurve::<T>::get().len(), 2);
+    }
+
+    expire_lobby {
+        let n in T::MinLobbyParticipants::get() .. T::MaxLobbyParticipants::get();
+        let (lobby_id, deadline) = lobby_with::<T>(n);
+        frame_system::Pallet::<T>::set_block_number(
+            deadline
+                .saturating_add(T::RaceTimeout::get())
+                .saturating_add(T::ChallengePeriod::get())
+                + 1u32.into(),
+        );
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), lobby_id)
+    verify {
+        assert_eq!(Lobbies::<T>::get(lobby_id).unwrap().status, LobbyStatus::Cancelled);
+    }
+
+    create_map {
+        let origin = T::GameAdminOrigin::try_successful_origin().map_err(|_| "no admin origin")?;
+    }: _<T::RuntimeOrigin>(origin, map::<T>())
+    verify {
+        assert_eq!(Maps::<T>::get(0), Some(map::<T>()));
+    }
+
+    update_map {
+        let origin = T::GameAdminOrigin::try_successful_origin().map_err(|_| "no admin origin")?;
+        insert_map::<T>();
+        let mut info = map::<T>();
+        info.enabled = false;
+    }: _<T::RuntimeOrigin>(origin, 0, info.clone())
+    verify {
+        assert_eq!(Maps::<T>::get(0), Some(info));
+    }
+
+    review_flagged_result {
+        let caller: T::AccountId = whitelisted_caller();
+        let race_id = start::<T>(&caller);
+        let result = fastest_result::<T>(race_id);
+        let (server, signature) = signed::<T>((RESULT_SIGNING_CONTEXT, race_id, &result));
+        let relayer = T::ResultOrigin::try_successful_origin().map_err(|_| "no result origin")?;
+        Pallet::<T>::submit_race_result(relayer, race_id, result, server, signature)?;
+        let origin = T::GameAdminOrigin::try_successful_origin().map_err(|_| "no admin origin")?;
+    }: _<T::RuntimeOrigin>(origin, race_id, true)
+    verify {
+        assert!(matches!(Races::<T>::get(race_id).unwrap().status, RaceStatus::Completed { .. }));
+        assert!(!FlaggedResults::<T>::contains_key(race_id));
+    }
+
+    dispute_result {
+        let race_id = completed::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::set_balance(&caller, T::DisputeBond::get().saturating_mul(10u32.into()));
+    }: _(RawOrigin::Signed(caller.clone()), race_id)
+    verify {
+        assert_eq!(Disputes::<T>::get(race_id).unwrap().disputer, caller);
+    }
+
+    resolve_dispute {
+        let race_id = completed::<T>();
+        let disputer: T::AccountId = whitelisted_caller();
+        T::Currency::set_balance(&disputer, T::DisputeBond::get().saturating_mul(10u32.into()));
+        Pallet::<T>::dispute_result(RawOrigin::Signed(disputer).into(), race_id)?;
+        let origin = T::ArbiterOrigin::try_successful_origin().map_err(|_| "no arbiter origin")?;
+        let corrected = RaceResult { position: 2, ..result() };
+    }: _<T::RuntimeOrigin>(origin, race_id, Some(corrected.clone()))
+    verify {
+        assert_eq!(RaceResults::<T>::get(race_id), Some(corrected));
+        assert!(!Disputes::<T>::contains_key(race_id));
+    }
+
+    revoke_server_key {
+        let origin = T::ServerManagerOrigin::try_successful_origin()
+            .map_err(|_| "no server manager origin")?;
+        GameServers::<T>::insert(server_key(1), frame_system::Pallet::<T>::block_number());
+    }: _<T::RuntimeOrigin>(origin, server_key(1))
+    verify {
+        assert!(!GameServers::<T>::contains_key(server_key(1)));
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
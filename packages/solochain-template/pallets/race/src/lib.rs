@@ -0,0 +1,1754 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{AtLeast32BitUnsigned, Saturating, Verify, Zero},
+            Percent,
+        },
+        traits::{
+            fungible::{self, MutateHold},
+            tokens::{Fortitude, Precision, Restriction},
+        },
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_bike::{BikeId, BikeRacing, BikeStats};
+    use pallet_character::{CharacterId, CharacterInspect};
+    use pallet_ranked::RankedResultHandler;
+    use pallet_rewards::{RewardAccrual, RewardReason};
+    use pallet_user_profile::{Metric, QuestProgress, UserStatsHandler};
+    use sp_core::{ed25519, sr25519, H256};
+
+    /// Identifier of a race
+    pub type RaceId = u64;
+
+    /// Domain separator of race result signatures. Game servers sign the SCALE encoding of
+    /// `(RESULT_SIGNING_CONTEXT, race_id, result)`.
+    pub const RESULT_SIGNING_CONTEXT: &[u8] = b"bicycle/race-result";
+
+    /// Identifier of a race map
+    pub type MapId = u32;
+
+    /// Identifier of a multiplayer lobby
+    pub type LobbyId = u64;
+
+    /// Domain separator of lobby placement signatures. Game servers sign the SCALE encoding of
+    /// `(LOBBY_RESULT_SIGNING_CONTEXT, lobby_id, placements)`.
+    pub const LOBBY_RESULT_SIGNING_CONTEXT: &[u8] = b"bicycle/lobby-result";
+
+    pub type BalanceOf<T> = <T as Config>::Balance;
+
+    pub type NativeBalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    pub type RaceOf<T> =
+        Race<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, BalanceOf<T>>;
+
+    pub type MapInfoOf<T> = MapInfo<BalanceOf<T>, <T as Config>::MaxMapNameLength>;
+
+    pub type LobbyOf<T> = Lobby<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        NativeBalanceOf<T>,
+        <T as Config>::MaxLobbyParticipants,
+    >;
+
+    pub type LobbyResultOf<T> = LobbyResult<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as Config>::MaxLobbyParticipants,
+    >;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Characters that take part in races
+        type Characters: CharacterInspect<Self::AccountId>;
+        /// Bikes ridden in races
+        type Bikes: BikeRacing<Self::AccountId>;
+        /// Receives finished races for the player statistics
+        type UserStats: UserStatsHandler<Self::AccountId>;
+        /// Receives lobby placements for the ranked ladder
+        type Ranked: RankedResultHandler<Self::AccountId>;
+        /// Receives finished races for quest conditions
+        type QuestProgress: QuestProgress<Self::AccountId>;
+        /// Origin allowed to relay race results signed by a game server
+        type ResultOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to register, rotate and revoke game server keys
+        type ServerManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to manage game parameters such as the lobby payout curve
+        type GameAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to resolve disputed race results
+        type ArbiterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Overarching hold reason
+        type RuntimeHoldReason: From<HoldReason>;
+        /// Native currency holding lobby entry fees
+        type Currency: fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+        /// Balance of race rewards
+        type Balance: AtLeast32BitUnsigned + Parameter + Member + MaxEncodedLen + Copy;
+        /// Credits claimed race rewards as pending race rewards
+        type Rewards: RewardAccrual<Self::AccountId, Self::Balance>;
+        /// Maximum length of a map name
+        #[pallet::constant]
+        type MaxMapNameLength: Get<u32>;
+        /// Highest plausible average speed of a race, in meters per second
+        #[pallet::constant]
+        type MaxAverageSpeed: Get<u32>;
+        /// Average speed each point of bike speed allows, in millimeters per second
+        #[pallet::constant]
+        type SpeedPerBikeStat: Get<u32>;
+        /// Share of the speed limit above which results are flagged for review
+        #[pallet::constant]
+        type SuspiciousSpeedRatio: Get<Percent>;
+        /// Flagged results after which an account is banned from ranked lobbies
+        #[pallet::constant]
+        type SuspicionThreshold: Get<u32>;
+        /// Blocks a ranked ban lasts
+        #[pallet::constant]
+        type RankedBanDuration: Get<BlockNumberFor<Self>>;
+        /// Blocks after its start a race without a result can be cancelled by its owner
+        #[pallet::constant]
+        type RaceTimeout: Get<BlockNumberFor<Self>>;
+        /// Largest number of players in a lobby
+        #[pallet::constant]
+        type MaxLobbyParticipants: Get<u32>;
+        /// Fewest players a lobby needs to be raced; smaller lobbies are refunded
+        #[pallet::constant]
+        type MinLobbyParticipants: Get<u32>;
+        /// Number of fastest runs kept on the leaderboard of each map
+        #[pallet::constant]
+        type MaxLeaderboardSize: Get<u32>;
+        /// Blocks during which a posted result can be disputed before its reward is claimable
+        #[pallet::constant]
+        type ChallengePeriod: Get<BlockNumberFor<Self>>;
+        /// Deposit held from an account disputing a result, slashed if the dispute fails
+        #[pallet::constant]
+        type DisputeBond: Get<NativeBalanceOf<Self>>;
+    }
+
+    /// Reason for this pallet placing a hold on funds
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Entry fee paid to join a lobby
+        LobbyEntryFee,
+        /// Bond of an account disputing a race or lobby result
+        DisputeBond,
+    }
+
+    /// Race map managed by governance
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(NameLimit))]
+    pub struct MapInfo<Balance, NameLimit: Get<u32>> {
+        pub name: BoundedVec<u8, NameLimit>,
+        /// Track length in meters
+        pub distance: u64,
+        /// Scales the wear of bikes raced on the map
+        pub difficulty: u32,
+        /// Character energy spent to race the map
+        pub energy_cost: u32,
+        /// Character water spent to race the map
+        pub water_cost: u32,
+        /// Reward for finishing first; lower positions get a fraction of it
+        pub base_reward: Balance,
+        /// Whether new races and lobbies can use the map
+        pub enabled: bool,
+    }
+
+    /// Progress of a race
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RaceStatus<Balance, BlockNumber> {
+        /// Race is running, waiting for the game server result
+        Started,
+        /// Result posted, reward claimable once the challenge period ends
+        Completed {
+            reward: Balance,
+            challenge_ends: BlockNumber,
+        },
+        /// Reward paid out
+        Claimed,
+        /// Part of the reward paid out; the emission budget could not cover `unpaid`, which can
+        /// be claimed again on a later day
+        PartiallyClaimed { unpaid: Balance },
+        /// Result is suspiciously fast and waits for review before it can be claimed
+        Flagged { reward: Balance },
+        /// Result was rejected on review
+        Rejected,
+        /// Result is disputed and waits for the arbiter
+        Disputed,
+        /// No result arrived before the race timed out and its owner cancelled it
+        Cancelled,
+    }
+
+    /// Race started by a player
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Race<AccountId, BlockNumber, Balance> {
+        pub owner: AccountId,
+        pub character_id: CharacterId,
+        pub bike_id: BikeId,
+        pub map_id: MapId,
+        pub started_at: BlockNumber,
+        /// Highest average speed the bike allows, in millimeters per second
+        pub speed_limit: u32,
+        pub status: RaceStatus<Balance, BlockNumber>,
+    }
+
+    /// Open dispute of a race result
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Dispute<AccountId, Balance> {
+        pub disputer: AccountId,
+        pub bond: Balance,
+    }
+
+    /// Outcome of a race as computed by the game server
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        Default,
+    )]
+    pub struct RaceResult {
+        /// Finishing position, starting at 1
+        pub position: u32,
+        /// Total race time in milliseconds
+        pub time_ms: u64,
+        /// Distance ridden in meters
+        pub distance: u64,
+        /// Fastest lap in milliseconds, zero if the map has no laps
+        pub best_lap_ms: u64,
+        /// Blake2-256 hash of the ghost replay file stored off-chain
+        pub ghost_hash: Option<H256>,
+    }
+
+    /// Best run of an account on a map
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PersonalBest {
+        /// Fastest total time in milliseconds
+        pub time_ms: u64,
+        /// Fastest lap in milliseconds over all runs, zero if none was recorded
+        pub best_lap_ms: u64,
+        /// Race of the fastest total time
+        pub race_id: RaceId,
+        /// Ghost replay of the fastest total time
+        pub ghost_hash: Option<H256>,
+    }
+
+    /// Entry of a map leaderboard
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct LeaderboardEntry<AccountId> {
+        pub account: AccountId,
+        pub time_ms: u64,
+        pub race_id: RaceId,
+        pub ghost_hash: Option<H256>,
+    }
+
+    /// Public key of a game server
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum ServerKey {
+        Sr25519(sr25519::Public),
+        Ed25519(ed25519::Public),
+    }
+
+    /// Signature of a game server over a race result
+    #[derive(
+        Clone, Encode, Decode, DecodeWithMemTracking, PartialEq, Eq, RuntimeDebug, TypeInfo,
+    )]
+    pub enum ServerSignature {
+        Sr25519(sr25519::Signature),
+        Ed25519(ed25519::Signature),
+    }
+
+    impl ServerSignature {
+        /// Whether this is a valid signature of `message` by `key`
+        pub fn verify(&self, message: &[u8], key: &ServerKey) -> bool {
+            match (self, key) {
+                (ServerSignature::Sr25519(signature), ServerKey::Sr25519(public)) => {
+                    signature.verify(message, public)
+                }
+                (ServerSignature::Ed25519(signature), ServerKey::Ed25519(public)) => {
+                    signature.verify(message, public)
+                }
+                _ => false,
+            }
+        }
+    }
+
+    /// Progress of a lobby
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum LobbyStatus {
+        /// Players can join until the start deadline
+        Open,
+        /// Placements posted and the prize pool paid out
+        Settled,
+        /// Cancelled or under-filled, entry fees refunded
+        Cancelled,
+        /// Placements posted, the prize pool is paid out once the challenge period ends
+        Settling,
+        /// Placements are disputed and wait for the arbiter
+        Disputed,
+    }
+
+    /// Multiplayer race lobby with an entry fee
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(MaxParticipants))]
+    pub struct Lobby<AccountId, BlockNumber, Balance, MaxParticipants: Get<u32>> {
+        pub host: AccountId,
+        pub map_id: MapId,
+        pub max_participants: u32,
+        pub entry_fee: Balance,
+        pub start_deadline: BlockNumber,
+        pub participants: BoundedVec<AccountId, MaxParticipants>,
+        pub status: LobbyStatus,
+    }
+
+    /// Placements posted for a lobby, open to disputes until `challenge_ends`
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(MaxParticipants))]
+    pub struct LobbyResult<AccountId, BlockNumber, MaxParticipants: Get<u32>> {
+        pub placements: BoundedVec<AccountId, MaxParticipants>,
+        pub challenge_ends: BlockNumber,
+    }
+
+    /// Map from MapId to MapInfo
+    #[pallet::storage]
+    pub type Maps<T: Config> = StorageMap<_, Blake2_128Concat, MapId, MapInfoOf<T>>;
+
+    /// Next MapId to assign
+    #[pallet::storage]
+    pub type NextMapId<T> = StorageValue<_, MapId, ValueQuery>;
+
+    /// Map from RaceId to Race
+    #[pallet::storage]
+    pub type Races<T: Config> = StorageMap<_, Blake2_128Concat, RaceId, RaceOf<T>>;
+
+    /// Next RaceId to assign
+    #[pallet::storage]
+    pub type NextRaceId<T> = StorageValue<_, RaceId, ValueQuery>;
+
+    /// Map from RaceId to the submitted result
+    #[pallet::storage]
+    pub type RaceResults<T: Config> = StorageMap<_, Blake2_128Concat, RaceId, RaceResult>;
+
+    /// Race currently running for an account
+    #[pallet::storage]
+    pub type ActiveRace<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, RaceId>;
+
+    /// Map from LobbyId to Lobby
+    #[pallet::storage]
+    pub type Lobbies<T: Config> = StorageMap<_, Blake2_128Concat, LobbyId, LobbyOf<T>>;
+
+    /// Next LobbyId to assign
+    #[pallet::storage]
+    pub type NextLobbyId<T> = StorageValue<_, LobbyId, ValueQuery>;
+
+    /// Placements of lobbies that are not paid out yet
+    #[pallet::storage]
+    pub type LobbyResults<T: Config> = StorageMap<_, Blake2_128Concat, LobbyId, LobbyResultOf<T>>;
+
+    /// Open disputes of lobby placements
+    #[pallet::storage]
+    pub type LobbyDisputes<T: Config> =
+        StorageMap<_, Blake2_128Concat, LobbyId, Dispute<T::AccountId, NativeBalanceOf<T>>>;
+
+    /// Share of the lobby prize pool paid to each finishing position.
+    /// Empty means winner takes all.
+    #[pallet::storage]
+    pub type PayoutCurve<T: Config> =
+        StorageValue<_, BoundedVec<Percent, T::MaxLobbyParticipants>, ValueQuery>;
+
+    /// Best run of each account on each map
+    #[pallet::storage]
+    pub type PersonalBests<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, MapId, Blake2_128Concat, T::AccountId, PersonalBest>;
+
+    /// Fastest runs of each map, one per account, sorted by total time
+    #[pallet::storage]
+    pub type Leaderboards<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        MapId,
+        BoundedVec<LeaderboardEntry<T::AccountId>, T::MaxLeaderboardSize>,
+        ValueQuery,
+    >;
+
+    /// Registered game server keys and the block they were registered at
+    #[pallet::storage]
+    pub type GameServers<T: Config> = StorageMap<_, Blake2_128Concat, ServerKey, BlockNumberFor<T>>;
+
+    /// Open disputes of race results
+    #[pallet::storage]
+    pub type Disputes<T: Config> =
+        StorageMap<_, Blake2_128Concat, RaceId, Dispute<T::AccountId, NativeBalanceOf<T>>>;
+
+    /// Dispute queue of suspicious results waiting for review, with the block they were flagged
+    #[pallet::storage]
+    pub type FlaggedResults<T: Config> = StorageMap<_, Blake2_128Concat, RaceId, BlockNumberFor<T>>;
+
+    /// Flagged results of an account since its last ranked ban
+    #[pallet::storage]
+    pub type SuspiciousResults<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Accounts banned from ranked lobbies, with the block the ban ends
+    #[pallet::storage]
+    pub type RankedBans<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Race started
+        RaceStarted {
+            race_id: RaceId,
+            owner: T::AccountId,
+            character_id: CharacterId,
+            bike_id: BikeId,
+            map_id: MapId,
+        },
+        /// Race result submitted by the game server
+        RaceCompleted {
+            race_id: RaceId,
+            position: u32,
+            time_ms: u64,
+            distance: u64,
+            reward: BalanceOf<T>,
+            server: ServerKey,
+        },
+        /// Race without a result cancelled by its owner after timing out
+        RaceCancelled {
+            race_id: RaceId,
+            owner: T::AccountId,
+        },
+        /// Bike wear of a finished race could not be applied
+        WearNotApplied {
+            race_id: RaceId,
+            bike_id: BikeId,
+            error: DispatchError,
+        },
+        /// Race reward credited to the owner's pending rewards; `unpaid` is left to claim once
+        /// the emission budget allows
+        RaceRewardClaimed {
+            race_id: RaceId,
+            owner: T::AccountId,
+            reward: BalanceOf<T>,
+            unpaid: BalanceOf<T>,
+        },
+        /// Account improved its best time on a map
+        PersonalBestSet {
+            map_id: MapId,
+            who: T::AccountId,
+            time_ms: u64,
+            race_id: RaceId,
+        },
+        /// Run entered the leaderboard of a map at `rank`, starting at 1
+        LeaderboardUpdated {
+            map_id: MapId,
+            who: T::AccountId,
+            rank: u32,
+        },
+        /// Game server key registered
+        ServerKeyRegistered { key: ServerKey },
+        /// Game server key replaced by a new one
+        ServerKeyRotated { old: ServerKey, new: ServerKey },
+        /// Game server key revoked
+        ServerKeyRevoked { key: ServerKey },
+        /// Lobby created by its host
+        LobbyCreated {
+            lobby_id: LobbyId,
+            host: T::AccountId,
+            map_id: MapId,
+            max_participants: u32,
+            entry_fee: NativeBalanceOf<T>,
+            start_deadline: BlockNumberFor<T>,
+        },
+        /// Player joined a lobby
+        LobbyJoined {
+            lobby_id: LobbyId,
+            who: T::AccountId,
+        },
+        /// Lobby cancelled by its host, entry fees refunded
+        LobbyCancelled { lobby_id: LobbyId },
+        /// Lobby did not fill before its deadline, entry fees refunded
+        LobbyRefunded { lobby_id: LobbyId },
+        /// Filled lobby got no result in time, entry fees refunded
+        LobbyExpired { lobby_id: LobbyId },
+        /// Lobby placements posted by the game server, paid out once the challenge period ends
+        LobbyResultPosted {
+            lobby_id: LobbyId,
+            placements: BoundedVec<T::AccountId, T::MaxLobbyParticipants>,
+            challenge_ends: BlockNumberFor<T>,
+        },
+        /// Lobby placements final and prize pool paid out
+        LobbySettled {
+            lobby_id: LobbyId,
+            placements: BoundedVec<T::AccountId, T::MaxLobbyParticipants>,
+            payouts: BoundedVec<NativeBalanceOf<T>, T::MaxLobbyParticipants>,
+        },
+        /// Race map created
+        MapCreated { map_id: MapId, info: MapInfoOf<T> },
+        /// Race map definition changed
+        MapUpdated { map_id: MapId, info: MapInfoOf<T> },
+        /// Suspiciously fast result queued for review
+        ResultFlagged {
+            race_id: RaceId,
+            owner: T::AccountId,
+            suspicious_results: u32,
+        },
+        /// Flagged result reviewed by the game admin
+        FlaggedResultReviewed { race_id: RaceId, legitimate: bool },
+        /// Race result disputed during its challenge period
+        ResultDisputed {
+            race_id: RaceId,
+            disputer: T::AccountId,
+            bond: NativeBalanceOf<T>,
+        },
+        /// Dispute resolved by the arbiter; an upheld dispute corrected the result
+        DisputeResolved {
+            race_id: RaceId,
+            upheld: bool,
+            reward: BalanceOf<T>,
+        },
+        /// Lobby placements disputed by a participant during their challenge period
+        LobbyResultDisputed {
+            lobby_id: LobbyId,
+            disputer: T::AccountId,
+            bond: NativeBalanceOf<T>,
+        },
+        /// Lobby dispute resolved by the arbiter; an upheld dispute corrected the placements
+        LobbyDisputeResolved { lobby_id: LobbyId, upheld: bool },
+        /// Account banned from ranked lobbies after too many flagged results
+        RankedBanned {
+            who: T::AccountId,
+            until: BlockNumberFor<T>,
+        },
+        /// Lobby payout curve changed
+        PayoutCurveSet {
+            curve: BoundedVec<Percent, T::MaxLobbyParticipants>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Race does not exist
+        RaceNotFound,
+        /// Caller does not own the race
+        NotRaceOwner,
+        /// Caller does not own the character
+        NotCharacterOwner,
+        /// Map does not exist
+        MapNotFound,
+        /// Map is disabled
+        MapDisabled,
+        /// Map distance must be positive
+        InvalidMap,
+        /// Account already has a race running
+        AlreadyRacing,
+        /// Race is not waiting for a result
+        RaceNotRunning,
+        /// Race has no unclaimed reward
+        RaceNotCompleted,
+        /// Race can only be cancelled once it timed out
+        RaceNotTimedOut,
+        /// Result is not valid for the map
+        InvalidResult,
+        /// Result is faster than the bike or the map allow
+        ImplausibleSpeed,
+        /// Race result is not waiting for review
+        RaceNotFlagged,
+        /// Reward can only be claimed once the challenge period ends
+        ChallengePeriodActive,
+        /// Challenge period of the result is over
+        ChallengePeriodOver,
+        /// Race result is not disputed
+        RaceNotDisputed,
+        /// Account is banned from ranked lobbies
+        RankedBanned,
+        /// No more ids available
+        Overflow,
+        /// Game server key is already registered
+        ServerKeyExists,
+        /// Game server key is not registered
+        UnknownServerKey,
+        /// Result signature does not match the game server key
+        InvalidSignature,
+        /// Lobby does not exist
+        LobbyNotFound,
+        /// Caller is not the lobby host
+        NotLobbyHost,
+        /// Lobby is no longer open
+        LobbyNotOpen,
+        /// Lobby has no free seat left
+        LobbyFull,
+        /// Caller already joined the lobby
+        AlreadyJoined,
+        /// Participant limit outside the allowed range
+        InvalidParticipantLimit,
+        /// Start deadline already passed
+        DeadlinePassed,
+        /// Start deadline not reached yet
+        DeadlineNotPassed,
+        /// Lobby does not have enough participants
+        NotEnoughParticipants,
+        /// Lobby has enough participants to be raced
+        LobbyFilled,
+        /// Lobby result can still be posted
+        LobbyNotExpired,
+        /// Placements are not a ranking of the lobby participants
+        InvalidPlacements,
+        /// Payout shares do not add up to 100%
+        InvalidPayoutCurve,
+        /// Caller did not take part in the lobby
+        NotLobbyParticipant,
+        /// Lobby placements are not waiting for their challenge period to end
+        LobbyNotSettling,
+        /// Lobby placements are not disputed
+        LobbyNotDisputed,
+        /// Emission budget has nothing left for the reward today
+        RewardPoolExhausted,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Start a race with an owned character and bike on `map_id`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::start_race())]
+        pub fn start_race(
+            origin: OriginFor<T>,
+            character_id: CharacterId,
+            bike_id: BikeId,
+            map_id: MapId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let map = Self::enabled_map(map_id)?;
+            ensure!(
+                T::Characters::owner_of(character_id).as_ref() == Some(&who),
+                Error::<T>::NotCharacterOwner
+            );
+            let bike = T::Bikes::ensure_can_race(&who, bike_id)?;
+            ensure!(
+                !ActiveRace::<T>::contains_key(&who),
+                Error::<T>::AlreadyRacing
+            );
+
+            let race_id = NextRaceId::<T>::get();
+            let next = race_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            T::Characters::consume_vitals(character_id, map.energy_cost, map.water_cost)?;
+            NextRaceId::<T>::put(next);
+
+            Races::<T>::insert(
+                race_id,
+                Race {
+                    owner: who.clone(),
+                    character_id,
+                    bike_id,
+                    map_id,
+                    started_at: frame_system::Pallet::<T>::block_number(),
+                    speed_limit: Self::speed_limit(&bike),
+                    status: RaceStatus::Started,
+                },
+            );
+            ActiveRace::<T>::insert(&who, race_id);
+
+            Self::deposit_event(Event::RaceStarted {
+                race_id,
+                owner: who,
+                character_id,
+                bike_id,
+                map_id,
+            });
+
+            Ok(())
+        }
+
+        /// Submit the result of a running race, signed by a registered game server
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::submit_race_result())]
+        pub fn submit_race_result(
+            origin: OriginFor<T>,
+            race_id: RaceId,
+            result: RaceResult,
+            server: ServerKey,
+            signature: ServerSignature,
+        ) -> DispatchResult {
+            T::ResultOrigin::ensure_origin(origin)?;
+            Self::ensure_server_signed(
+                &server,
+                &signature,
+                (RESULT_SIGNING_CONTEXT, race_id, &result),
+            )?;
+
+            let mut race = Races::<T>::get(race_id).ok_or(Error::<T>::RaceNotFound)?;
+            ensure!(
+                matches!(race.status, RaceStatus::Started),
+                Error::<T>::RaceNotRunning
+            );
+            let map = Maps::<T>::get(race.map_id).ok_or(Error::<T>::MapNotFound)?;
+            let suspicious = Self::assess_result(&map, &race, &result)?;
+
+            // The bike may have been disassembled since the race started, which must not keep
+            // the result from being recorded
+            if let Err(error) = T::Bikes::apply_wear(race.bike_id, map.difficulty) {
+                Self::deposit_event(Event::WearNotApplied {
+                    race_id,
+                    bike_id: race.bike_id,
+                    error,
+                });
+            }
+
+            let reward = Self::reward_for(&map, result.position);
+            let owner = race.owner.clone();
+            let now = frame_system::Pallet::<T>::block_number();
+            race.status = if suspicious {
+                FlaggedResults::<T>::insert(race_id, now);
+                RaceStatus::Flagged { reward }
+            } else {
+                RaceStatus::Completed {
+                    reward,
+                    challenge_ends: now.saturating_add(T::ChallengePeriod::get()),
+                }
+            };
+            ActiveRace::<T>::remove(&owner);
+            Races::<T>::insert(race_id, race);
+            RaceResults::<T>::insert(race_id, result.clone());
+
+            Self::deposit_event(Event::RaceCompleted {
+                race_id,
+                position: result.position,
+                time_ms: result.time_ms,
+                distance: result.distance,
+                reward,
+                server,
+            });
+            if suspicious {
+                Self::record_suspicious(race_id, &owner);
+            }
+
+            Ok(())
+        }
+
+        /// Claim the reward of a completed race. When the emission budget only covers part of
+        /// it, the rest stays claimable on a later day.
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            T::WeightInfo::claim_race_reward()
+                .saturating_add(T::QuestProgress::record_weight().saturating_mul(3))
+                .saturating_add(T::UserStats::record_weight())
+        )]
+        pub fn claim_race_reward(origin: OriginFor<T>, race_id: RaceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let race = Races::<T>::get(race_id).ok_or(Error::<T>::RaceNotFound)?;
+            ensure!(race.owner == who, Error::<T>::NotRaceOwner);
+            let due = match race.status {
+                RaceStatus::Completed {
+                    reward,
+                    challenge_ends,
+                } => {
+                    ensure!(
+                        frame_system::Pallet::<T>::block_number() >= challenge_ends,
+                        Error::<T>::ChallengePeriodActive
+                    );
+                    reward
+                }
+                RaceStatus::PartiallyClaimed { unpaid } => {
+                    let reward = T::Rewards::accrue_up_to(&who, RewardReason::Race, unpaid)?;
+                    ensure!(!reward.is_zero(), Error::<T>::RewardPoolExhausted);
+                    return Self::credit_race_reward(race_id, race, reward, unpaid);
+                }
+                _ => return Err(Error::<T>::RaceNotCompleted.into()),
+            };
+
+            let reward = if due.is_zero() {
+                due
+            } else {
+                T::Rewards::accrue_up_to(&who, RewardReason::Race, due)?
+            };
+            ensure!(
+                due.is_zero() || !reward.is_zero(),
+                Error::<T>::RewardPoolExhausted
+            );
+            let result = RaceResults::<T>::get(race_id).unwrap_or_default();
+            Self::record_time(race_id, race.map_id, &who, &result);
+
+            T::UserStats::record_race(&who, result.position == 1, result.distance);
+            T::QuestProgress::record(&who, Metric::RacesCompleted, 1);
+            if result.position == 1 {
+                T::QuestProgress::record(&who, Metric::RacesWon, 1);
+            }
+            T::QuestProgress::record(&who, Metric::DistanceRidden, result.distance);
+
+            Self::credit_race_reward(race_id, race, reward, due)
+        }
+
+        /// Cancel a race that got no result within `RaceTimeout` blocks, so its owner can race
+        /// again. Energy and water spent on it are not refunded.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::cancel_race())]
+        pub fn cancel_race(origin: OriginFor<T>, race_id: RaceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut race = Races::<T>::get(race_id).ok_or(Error::<T>::RaceNotFound)?;
+            ensure!(race.owner == who, Error::<T>::NotRaceOwner);
+            ensure!(
+                matches!(race.status, RaceStatus::Started),
+                Error::<T>::RaceNotRunning
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number()
+                    >= race.started_at.saturating_add(T::RaceTimeout::get()),
+                Error::<T>::RaceNotTimedOut
+            );
+
+            race.status = RaceStatus::Cancelled;
+            Races::<T>::insert(race_id, race);
+            ActiveRace::<T>::remove(&who);
+
+            Self::deposit_event(Event::RaceCancelled {
+                race_id,
+                owner: who,
+            });
+
+            Ok(())
+        }
+
+        /// Register a game server key allowed to sign race results
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::register_server_key())]
+        pub fn register_server_key(origin: OriginFor<T>, key: ServerKey) -> DispatchResult {
+            T::ServerManagerOrigin::ensure_origin(origin)?;
+            ensure!(
+                !GameServers::<T>::contains_key(&key),
+                Error::<T>::ServerKeyExists
+            );
+
+            GameServers::<T>::insert(&key, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::ServerKeyRegistered { key });
+
+            Ok(())
+        }
+
+        /// Replace a registered game server key by `new`
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::rotate_server_key())]
+        pub fn rotate_server_key(
+            origin: OriginFor<T>,
+            old: ServerKey,
+            new: ServerKey,
+        ) -> DispatchResult {
+            T::ServerManagerOrigin::ensure_origin(origin)?;
+            ensure!(
+                GameServers::<T>::contains_key(&old),
+                Error::<T>::UnknownServerKey
+            );
+            ensure!(
+                !GameServers::<T>::contains_key(&new),
+                Error::<T>::ServerKeyExists
+            );
+
+            GameServers::<T>::remove(&old);
+            GameServers::<T>::insert(&new, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::ServerKeyRotated { old, new });
+
+            Ok(())
+        }
+
+        /// Revoke a game server key; results it signs are rejected from now on
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::revoke_server_key())]
+        pub fn revoke_server_key(origin: OriginFor<T>, key: ServerKey) -> DispatchResult {
+            T::ServerManagerOrigin::ensure_origin(origin)?;
+            ensure!(
+                GameServers::<T>::contains_key(&key),
+                Error::<T>::UnknownServerKey
+            );
+
+            GameServers::<T>::remove(&key);
+
+            Self::deposit_event(Event::ServerKeyRevoked { key });
+
+            Ok(())
+        }
+
+        /// Create a lobby and join it as its first player
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::create_lobby())]
+        pub fn create_lobby(
+            origin: OriginFor<T>,
+            map_id: MapId,
+            max_participants: u32,
+            entry_fee: NativeBalanceOf<T>,
+            start_deadline: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::enabled_map(map_id)?;
+            ensure!(!Self::is_ranked_banned(&who), Error::<T>::RankedBanned);
+            ensure!(
+                max_participants >= T::MinLobbyParticipants::get()
+                    && max_participants <= T::MaxLobbyParticipants::get(),
+                Error::<T>::InvalidParticipantLimit
+            );
+            ensure!(
+                start_deadline > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::DeadlinePassed
+            );
+
+            let lobby_id = NextLobbyId::<T>::get();
+            let next = lobby_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            let mut participants = BoundedVec::new();
+            participants
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::InvalidParticipantLimit)?;
+            T::Currency::hold(&HoldReason::LobbyEntryFee.into(), &who, entry_fee)?;
+            NextLobbyId::<T>::put(next);
+
+            Lobbies::<T>::insert(
+                lobby_id,
+                Lobby {
+                    host: who.clone(),
+                    map_id,
+                    max_participants,
+                    entry_fee,
+                    start_deadline,
+                    participants,
+                    status: LobbyStatus::Open,
+                },
+            );
+
+            Self::deposit_event(Event::LobbyCreated {
+                lobby_id,
+                host: who.clone(),
+                map_id,
+                max_participants,
+                entry_fee,
+                start_deadline,
+            });
+            Self::deposit_event(Event::LobbyJoined { lobby_id, who });
+
+            Ok(())
+        }
+
+        /// Join an open lobby, holding its entry fee
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::join_lobby(T::MaxLobbyParticipants::get()))]
+        pub fn join_lobby(origin: OriginFor<T>, lobby_id: LobbyId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_ranked_banned(&who), Error::<T>::RankedBanned);
+
+            let mut lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(lobby.status == LobbyStatus::Open, Error::<T>::LobbyNotOpen);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= lobby.start_deadline,
+                Error::<T>::DeadlinePassed
+            );
+            ensure!(
+                !lobby.participants.contains(&who),
+                Error::<T>::AlreadyJoined
+            );
+            ensure!(
+                (lobby.participants.len() as u32) < lobby.max_participants,
+                Error::<T>::LobbyFull
+            );
+            lobby
+                .participants
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::LobbyFull)?;
+
+            T::Currency::hold(&HoldReason::LobbyEntryFee.into(), &who, lobby.entry_fee)?;
+            Lobbies::<T>::insert(lobby_id, lobby);
+
+            Self::deposit_event(Event::LobbyJoined { lobby_id, who });
+
+            Ok(())
+        }
+
+        /// Cancel an open lobby before its deadline, refunding every entry fee
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::cancel_lobby(T::MaxLobbyParticipants::get()))]
+        pub fn cancel_lobby(origin: OriginFor<T>, lobby_id: LobbyId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(lobby.host == who, Error::<T>::NotLobbyHost);
+            ensure!(lobby.status == LobbyStatus::Open, Error::<T>::LobbyNotOpen);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= lobby.start_deadline,
+                Error::<T>::DeadlinePassed
+            );
+
+            Self::refund_entry_fees(&lobby)?;
+            lobby.status = LobbyStatus::Cancelled;
+            Lobbies::<T>::insert(lobby_id, lobby);
+
+            Self::deposit_event(Event::LobbyCancelled { lobby_id });
+
+            Ok(())
+        }
+
+        /// Refund a lobby that did not gather enough players before its deadline
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::refund_lobby(T::MaxLobbyParticipants::get()))]
+        pub fn refund_lobby(origin: OriginFor<T>, lobby_id: LobbyId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(lobby.status == LobbyStatus::Open, Error::<T>::LobbyNotOpen);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > lobby.start_deadline,
+                Error::<T>::DeadlineNotPassed
+            );
+            ensure!(
+                (lobby.participants.len() as u32) < T::MinLobbyParticipants::get(),
+                Error::<T>::LobbyFilled
+            );
+
+            Self::refund_entry_fees(&lobby)?;
+            lobby.status = LobbyStatus::Cancelled;
+            Lobbies::<T>::insert(lobby_id, lobby);
+
+            Self::deposit_event(Event::LobbyRefunded { lobby_id });
+
+            Ok(())
+        }
+
+        /// Post the final placements of a lobby, signed by a registered game server. The prize
+        /// pool is split along the payout curve once the challenge period ends.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::submit_lobby_result(T::MaxLobbyParticipants::get()))]
+        pub fn submit_lobby_result(
+            origin: OriginFor<T>,
+            lobby_id: LobbyId,
+            placements: BoundedVec<T::AccountId, T::MaxLobbyParticipants>,
+            server: ServerKey,
+            signature: ServerSignature,
+        ) -> DispatchResult {
+            T::ResultOrigin::ensure_origin(origin)?;
+            Self::ensure_server_signed(
+                &server,
+                &signature,
+                (LOBBY_RESULT_SIGNING_CONTEXT, lobby_id, &placements),
+            )?;
+
+            let mut lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(lobby.status == LobbyStatus::Open, Error::<T>::LobbyNotOpen);
+            ensure!(
+                lobby.participants.len() as u32 >= T::MinLobbyParticipants::get(),
+                Error::<T>::NotEnoughParticipants
+            );
+            Self::ensure_valid_placements(&lobby, &placements)?;
+
+            let challenge_ends =
+                frame_system::Pallet::<T>::block_number().saturating_add(T::ChallengePeriod::get());
+            lobby.status = LobbyStatus::Settling;
+            Lobbies::<T>::insert(lobby_id, lobby);
+            LobbyResults::<T>::insert(
+                lobby_id,
+                LobbyResult {
+                    placements: placements.clone(),
+                    challenge_ends,
+                },
+            );
+
+            Self::deposit_event(Event::LobbyResultPosted {
+                lobby_id,
+                placements,
+                challenge_ends,
+            });
+
+            Ok(())
+        }
+
+        /// Set the share of the lobby prize pool paid to each position
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_payout_curve())]
+        pub fn set_payout_curve(
+            origin: OriginFor<T>,
+            curve: BoundedVec<Percent, T::MaxLobbyParticipants>,
+        ) -> DispatchResult {
+            T::GameAdminOrigin::ensure_origin(origin)?;
+            let total: u32 = curve.iter().map(|share| share.deconstruct() as u32).sum();
+            ensure!(
+                curve.is_empty() || total == 100,
+                Error::<T>::InvalidPayoutCurve
+            );
+
+            PayoutCurve::<T>::put(&curve);
+
+            Self::deposit_event(Event::PayoutCurveSet { curve });
+
+            Ok(())
+        }
+
+        /// Refund a filled lobby whose result did not arrive within `RaceTimeout` blocks after
+        /// its deadline, once the challenge period such a result would have had is over too
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::expire_lobby(T::MaxLobbyParticipants::get()))]
+        pub fn expire_lobby(origin: OriginFor<T>, lobby_id: LobbyId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(lobby.status == LobbyStatus::Open, Error::<T>::LobbyNotOpen);
+            ensure!(
+                lobby.participants.len() as u32 >= T::MinLobbyParticipants::get(),
+                Error::<T>::NotEnoughParticipants
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number()
+                    > lobby
+                        .start_deadline
+                        .saturating_add(T::RaceTimeout::get())
+                        .saturating_add(T::ChallengePeriod::get()),
+                Error::<T>::LobbyNotExpired
+            );
+
+            Self::refund_entry_fees(&lobby)?;
+            lobby.status = LobbyStatus::Cancelled;
+            Lobbies::<T>::insert(lobby_id, lobby);
+
+            Self::deposit_event(Event::LobbyExpired { lobby_id });
+
+            Ok(())
+        }
+
+        /// Register a new race map
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::create_map())]
+        pub fn create_map(origin: OriginFor<T>, info: MapInfoOf<T>) -> DispatchResult {
+            T::GameAdminOrigin::ensure_origin(origin)?;
+            ensure!(info.distance > 0, Error::<T>::InvalidMap);
+
+            let map_id = NextMapId::<T>::get();
+            let next = map_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            NextMapId::<T>::put(next);
+            Maps::<T>::insert(map_id, info.clone());
+
+            Self::deposit_event(Event::MapCreated { map_id, info });
+
+            Ok(())
+        }
+
+        /// Replace the definition of a race map, e.g. to disable it
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::update_map())]
+        pub fn update_map(
+            origin: OriginFor<T>,
+            map_id: MapId,
+            info: MapInfoOf<T>,
+        ) -> DispatchResult {
+            T::GameAdminOrigin::ensure_origin(origin)?;
+            ensure!(info.distance > 0, Error::<T>::InvalidMap);
+            ensure!(Maps::<T>::contains_key(map_id), Error::<T>::MapNotFound);
+
+            Maps::<T>::insert(map_id, info.clone());
+
+            Self::deposit_event(Event::MapUpdated { map_id, info });
+
+            Ok(())
+        }
+
+        /// Release a flagged result for claiming, or reject it as cheated
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::review_flagged_result())]
+        pub fn review_flagged_result(
+            origin: OriginFor<T>,
+            race_id: RaceId,
+            legitimate: bool,
+        ) -> DispatchResult {
+            T::GameAdminOrigin::ensure_origin(origin)?;
+
+            let mut race = Races::<T>::get(race_id).ok_or(Error::<T>::RaceNotFound)?;
+            let RaceStatus::Flagged { reward } = race.status else {
+                return Err(Error::<T>::RaceNotFlagged.into());
+            };
+            if legitimate {
+                SuspiciousResults::<T>::mutate(&race.owner, |count| {
+                    *count = count.saturating_sub(1)
+                });
+                // Already reviewed, so not open to disputes
+                race.status = RaceStatus::Completed {
+                    reward,
+                    challenge_ends: frame_system::Pallet::<T>::block_number(),
+                };
+            } else {
+                race.status = RaceStatus::Rejected;
+            }
+            FlaggedResults::<T>::remove(race_id);
+            Races::<T>::insert(race_id, race);
+
+            Self::deposit_event(Event::FlaggedResultReviewed {
+                race_id,
+                legitimate,
+            });
+
+            Ok(())
+        }
+
+        /// Dispute the result of an owned race during its challenge period, holding the dispute
+        /// bond
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::dispute_result())]
+        pub fn dispute_result(origin: OriginFor<T>, race_id: RaceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut race = Races::<T>::get(race_id).ok_or(Error::<T>::RaceNotFound)?;
+            ensure!(race.owner == who, Error::<T>::NotRaceOwner);
+            let RaceStatus::Completed { challenge_ends, .. } = race.status else {
+                return Err(Error::<T>::RaceNotCompleted.into());
+            };
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < challenge_ends,
+                Error::<T>::ChallengePeriodOver
+            );
+
+            let bond = T::DisputeBond::get();
+            T::Currency::hold(&HoldReason::DisputeBond.into(), &who, bond)?;
+            race.status = RaceStatus::Disputed;
+            Races::<T>::insert(race_id, race);
+            Disputes::<T>::insert(
+                race_id,
+                Dispute {
+                    disputer: who.clone(),
+                    bond,
+                },
+            );
+
+            Self::deposit_event(Event::ResultDisputed {
+                race_id,
+                disputer: who,
+                bond,
+            });
+
+            Ok(())
+        }
+
+        /// Resolve a dispute. With a `corrected` result the dispute is upheld: the result is
+        /// replaced and the bond returned. Without one the result stands and the bond is
+        /// slashed. Either way the reward becomes claimable right away.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::resolve_dispute())]
+        pub fn resolve_dispute(
+            origin: OriginFor<T>,
+            race_id: RaceId,
+            corrected: Option<RaceResult>,
+        ) -> DispatchResult {
+            T::ArbiterOrigin::ensure_origin(origin)?;
+
+            let mut race = Races::<T>::get(race_id).ok_or(Error::<T>::RaceNotFound)?;
+            ensure!(
+                race.status == RaceStatus::Disputed,
+                Error::<T>::RaceNotDisputed
+            );
+            let dispute = Disputes::<T>::get(race_id).ok_or(Error::<T>::RaceNotDisputed)?;
+            let map = Maps::<T>::get(race.map_id).ok_or(Error::<T>::MapNotFound)?;
+            let result = match corrected {
+                Some(ref corrected) => {
+                    Self::assess_result(&map, &race, corrected)?;
+                    corrected.clone()
+                }
+                None => RaceResults::<T>::get(race_id).unwrap_or_default(),
+            };
+
+            Self::settle_bond(&dispute, corrected.is_some())?;
+
+            let reward = Self::reward_for(&map, result.position);
+            race.status = RaceStatus::Completed {
+                reward,
+                challenge_ends: frame_system::Pallet::<T>::block_number(),
+            };
+            Races::<T>::insert(race_id, race);
+            RaceResults::<T>::insert(race_id, result);
+            Disputes::<T>::remove(race_id);
+
+            Self::deposit_event(Event::DisputeResolved {
+                race_id,
+                upheld: corrected.is_some(),
+                reward,
+            });
+
+            Ok(())
+        }
+
+        /// Pay out the prize pool of a lobby whose placements were not disputed during their
+        /// challenge period
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::settle_lobby(T::MaxLobbyParticipants::get()))]
+        pub fn settle_lobby(origin: OriginFor<T>, lobby_id: LobbyId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(
+                lobby.status == LobbyStatus::Settling,
+                Error::<T>::LobbyNotSettling
+            );
+            let result = LobbyResults::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotSettling)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= result.challenge_ends,
+                Error::<T>::ChallengePeriodActive
+            );
+
+            Self::settle_lobby_result(lobby_id, lobby, result.placements)
+        }
+
+        /// Dispute the placements of a lobby the caller took part in during their challenge
+        /// period, holding the dispute bond
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::dispute_lobby_result())]
+        pub fn dispute_lobby_result(origin: OriginFor<T>, lobby_id: LobbyId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(
+                lobby.participants.contains(&who),
+                Error::<T>::NotLobbyParticipant
+            );
+            ensure!(
+                lobby.status == LobbyStatus::Settling,
+                Error::<T>::LobbyNotSettling
+            );
+            let result = LobbyResults::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotSettling)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < result.challenge_ends,
+                Error::<T>::ChallengePeriodOver
+            );
+
+            let bond = T::DisputeBond::get();
+            T::Currency::hold(&HoldReason::DisputeBond.into(), &who, bond)?;
+            lobby.status = LobbyStatus::Disputed;
+            Lobbies::<T>::insert(lobby_id, lobby);
+            LobbyDisputes::<T>::insert(
+                lobby_id,
+                Dispute {
+                    disputer: who.clone(),
+                    bond,
+                },
+            );
+
+            Self::deposit_event(Event::LobbyResultDisputed {
+                lobby_id,
+                disputer: who,
+                bond,
+            });
+
+            Ok(())
+        }
+
+        /// Resolve a lobby dispute. With `corrected` placements the dispute is upheld: the
+        /// placements are replaced and the bond returned. Without them the placements stand and
+        /// the bond is slashed. Either way the prize pool is paid out right away.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::resolve_lobby_dispute(T::MaxLobbyParticipants::get()))]
+        pub fn resolve_lobby_dispute(
+            origin: OriginFor<T>,
+            lobby_id: LobbyId,
+            corrected: Option<BoundedVec<T::AccountId, T::MaxLobbyParticipants>>,
+        ) -> DispatchResult {
+            T::ArbiterOrigin::ensure_origin(origin)?;
+
+            let lobby = Lobbies::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotFound)?;
+            ensure!(
+                lobby.status == LobbyStatus::Disputed,
+                Error::<T>::LobbyNotDisputed
+            );
+            let dispute = LobbyDisputes::<T>::get(lobby_id).ok_or(Error::<T>::LobbyNotDisputed)?;
+            let upheld = corrected.is_some();
+            let placements = match corrected {
+                Some(corrected) => {
+                    Self::ensure_valid_placements(&lobby, &corrected)?;
+                    corrected
+                }
+                None => {
+                    LobbyResults::<T>::get(lobby_id)
+                        .ok_or(Error::<T>::LobbyNotDisputed)?
+                        .placements
+                }
+            };
+
+            Self::settle_bond(&dispute, upheld)?;
+            LobbyDisputes::<T>::remove(lobby_id);
+            Self::deposit_event(Event::LobbyDisputeResolved { lobby_id, upheld });
+
+            Self::settle_lobby_result(lobby_id, lobby, placements)
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Mark `reward` of the `due` reward of `race` as credited, and keep the rest claimable
+        fn credit_race_reward(
+            race_id: RaceId,
+            mut race: RaceOf<T>,
+            reward: BalanceOf<T>,
+            due: BalanceOf<T>,
+        ) -> DispatchResult {
+            let owner = race.owner.clone();
+            let unpaid = due.saturating_sub(reward);
+            race.status = if unpaid.is_zero() {
+                RaceStatus::Claimed
+            } else {
+                RaceStatus::PartiallyClaimed { unpaid }
+            };
+            Races::<T>::insert(race_id, race);
+
+            Self::deposit_event(Event::RaceRewardClaimed {
+                race_id,
+                owner,
+                reward,
+                unpaid,
+            });
+
+            Ok(())
+        }
+
+        /// Reward for finishing `map` at `position`: the base reward divided by the position
+        pub fn reward_for(map: &MapInfoOf<T>, position: u32) -> BalanceOf<T> {
+            if position == 0 {
+                return Zero::zero();
+            }
+            map.base_reward / BalanceOf::<T>::from(position)
+        }
+
+        /// Definition of `map_id`, if it can be raced
+        fn enabled_map(map_id: MapId) -> Result<MapInfoOf<T>, DispatchError> {
+            let map = Maps::<T>::get(map_id).ok_or(Error::<T>::MapNotFound)?;
+            ensure!(map.enabled, Error::<T>::MapDisabled);
+            Ok(map)
+        }
+
+        /// Highest average speed a bike with `stats` allows, in millimeters per second
+        pub fn speed_limit(stats: &BikeStats) -> u32 {
+            stats
+                .speed
+                .saturating_mul(T::SpeedPerBikeStat::get())
+                .min(T::MaxAverageSpeed::get().saturating_mul(1000))
+        }
+
+        /// Whether `who` is currently banned from ranked lobbies
+        pub fn is_ranked_banned(who: &T::AccountId) -> bool {
+            RankedBans::<T>::get(who)
+                .is_some_and(|until| frame_system::Pallet::<T>::block_number() < until)
+        }
+
+        /// Ensure `result` could have been ridden in `race` on `map`: a finishing position, no
+        /// more than the track length and no faster than the speed limit of the bike.
+        /// Returns whether the result is close enough to the limit to be suspicious.
+        fn assess_result(
+            map: &MapInfoOf<T>,
+            race: &RaceOf<T>,
+            result: &RaceResult,
+        ) -> Result<bool, DispatchError> {
+            ensure!(
+                result.position > 0 && result.time_ms > 0 && result.distance <= map.distance,
+                Error::<T>::InvalidResult
+            );
+            // Both sides in micrometers to avoid rounding the average speed
+            let ridden = (result.distance as u128).saturating_mul(1_000_000);
+            let allowed = (race.speed_limit as u128).saturating_mul(result.time_ms as u128);
+            ensure!(ridden <= allowed, Error::<T>::ImplausibleSpeed);
+            Ok(ridden > T::SuspiciousSpeedRatio::get().mul_floor(allowed))
+        }
+
+        /// Update the personal best and leaderboard of `map_id` with a final result of `who`.
+        /// Only runs covering the whole track count as times.
+        fn record_time(race_id: RaceId, map_id: MapId, who: &T::AccountId, result: &RaceResult) {
+            let finished =
+                Maps::<T>::get(map_id).is_some_and(|map| result.distance == map.distance);
+            if !finished {
+                return;
+            }
+
+            let mut improved = false;
+            PersonalBests::<T>::mutate(map_id, who, |maybe_best| {
+                let best = maybe_best.get_or_insert_with(|| {
+                    improved = true;
+                    PersonalBest {
+                        time_ms: result.time_ms,
+                        best_lap_ms: result.best_lap_ms,
+                        race_id,
+                        ghost_hash: result.ghost_hash,
+                    }
+                });
+                if result.best_lap_ms > 0
+                    && (best.best_lap_ms == 0 || result.best_lap_ms < best.best_lap_ms)
+                {
+                    best.best_lap_ms = result.best_lap_ms;
+                }
+                if result.time_ms < best.time_ms {
+                    improved = true;
+                    best.time_ms = result.time_ms;
+                    best.race_id = race_id;
+                    best.ghost_hash = result.ghost_hash;
+                }
+            });
+            if !improved {
+                return;
+            }
+            Self::deposit_event(Event::PersonalBestSet {
+                map_id,
+                who: who.clone(),
+                time_ms: result.time_ms,
+                race_id,
+            });
+
+            let rank = Leaderboards::<T>::mutate(map_id, |board| {
+                // The previous entry of `who` is slower than the new personal best
+                board.retain(|entry| entry.account != *who);
+                let index = board
+                    .iter()
+                    .position(|entry| entry.time_ms > result.time_ms)
+                    .unwrap_or(board.len());
+                if index >= T::MaxLeaderboardSize::get() as usize {
+                    return None;
+                }
+                if board.is_full() {
+                    board.pop();
+                }
+                let entry = LeaderboardEntry {
+                    account: who.clone(),
+                    time_ms: result.time_ms,
+                    race_id,
+                    ghost_hash: result.ghost_hash,
+                };
+                board
+                    .try_insert(index, entry)
+                    .ok()
+                    .map(|_| index as u32 + 1)
+            });
+            if let Some(rank) = rank {
+                Self::deposit_event(Event::LeaderboardUpdated {
+                    map_id,
+                    who: who.clone(),
+                    rank,
+                });
+            }
+        }
+
+        /// Count a flagged result of `who`, banning them from ranked lobbies once the
+        /// suspicion threshold is reached
+        fn record_suspicious(race_id: RaceId, who: &T::AccountId) {
+            let suspicious_results = SuspiciousResults::<T>::mutate(who, |count| {
+                *count = count.saturating_add(1);
+                *count
+            });
+            Self::deposit_event(Event::ResultFlagged {
+                race_id,
+                owner: who.clone(),
+                suspicious_results,
+            });
+
+            if suspicious_results >= T::SuspicionThreshold::get() {
+                let until = frame_system::Pallet::<T>::block_number()
+                    .saturating_add(T::RankedBanDuration::get());
+                RankedBans::<T>::insert(who, until);
+                SuspiciousResults::<T>::remove(who);
+                Self::deposit_event(Event::RankedBanned {
+                    who: who.clone(),
+                    until,
+                });
+            }
+        }
+
+        /// Ensure `server` is registered and signed the SCALE encoding of `payload`
+        fn ensure_server_signed(
+            server: &ServerKey,
+            signature: &ServerSignature,
+            payload: impl Encode,
+        ) -> DispatchResult {
+            ensure!(
+                GameServers::<T>::contains_key(server),
+                Error::<T>::UnknownServerKey
+            );
+            ensure!(
+                payload.using_encoded(|message| signature.verify(message, server)),
+                Error::<T>::InvalidSignature
+            );
+            Ok(())
+        }
+
+        /// Amount won by each finishing position of `lobby`.
+        /// Shares of positions nobody finished at and rounding leftovers go to the winner.
+        pub fn lobby_payouts(
+            lobby: &LobbyOf<T>,
+        ) -> BoundedVec<NativeBalanceOf<T>, T::MaxLobbyParticipants> {
+            let count = lobby.participants.len();
+            let pot = lobby
+                .entry_fee
+                .saturating_mul(NativeBalanceOf::<T>::from(count as u32));
+            let curve = PayoutCurve::<T>::get();
+            let share_of = |position: usize| -> NativeBalanceOf<T> {
+                curve
+                    .get(position)
+                    .map_or_else(Zero::zero, |share| share.mul_floor(pot))
+            };
+            let others = (1..count).fold(Zero::zero(), |paid: NativeBalanceOf<T>, position| {
+                paid.saturating_add(share_of(position))
+            });
+
+            // Pushes cannot fail, there are never more positions than participants
+            let mut payouts = BoundedVec::new();
+            if count > 0 {
+                let _ = payouts.try_push(pot.saturating_sub(others));
+            }
+            for position in 1..count {
+                let _ = payouts.try_push(share_of(position));
+            }
+            payouts
+        }
+
+        /// Ensure `placements` ranks every participant of `lobby` exactly once
+        fn ensure_valid_placements(
+            lobby: &LobbyOf<T>,
+            placements: &[T::AccountId],
+        ) -> DispatchResult {
+            ensure!(
+                placements.len() == lobby.participants.len(),
+                Error::<T>::InvalidPlacements
+            );
+            for (index, who) in placements.iter().enumerate() {
+                ensure!(
+                    lobby.participants.contains(who) && !placements[..index].contains(who),
+                    Error::<T>::InvalidPlacements
+                );
+            }
+            Ok(())
+        }
+
+        /// Pay out the prize pool of `lobby` along its final `placements`
+        fn settle_lobby_result(
+            lobby_id: LobbyId,
+            mut lobby: LobbyOf<T>,
+            placements: BoundedVec<T::AccountId, T::MaxLobbyParticipants>,
+        ) -> DispatchResult {
+            let payouts = Self::lobby_payouts(&lobby);
+            Self::pay_out_lobby(&lobby, &placements, &payouts)?;
+            T::Ranked::record_placements(&placements);
+            lobby.status = LobbyStatus::Settled;
+            Lobbies::<T>::insert(lobby_id, lobby);
+            LobbyResults::<T>::remove(lobby_id);
+
+            Self::deposit_event(Event::LobbySettled {
+                lobby_id,
+                placements,
+                payouts,
+            });
+
+            Ok(())
+        }
+
+        /// Return the bond of an upheld dispute, or slash it when the dispute failed
+        fn settle_bond(
+            dispute: &Dispute<T::AccountId, NativeBalanceOf<T>>,
+            upheld: bool,
+        ) -> DispatchResult {
+            let reason: T::RuntimeHoldReason = HoldReason::DisputeBond.into();
+            if upheld {
+                T::Currency::release(
+                    &reason,
+                    &dispute.disputer,
+                    dispute.bond,
+                    Precision::BestEffort,
+                )?;
+            } else {
+                T::Currency::burn_held(
+                    &reason,
+                    &dispute.disputer,
+                    dispute.bond,
+                    Precision::BestEffort,
+                    Fortitude::Force,
+                )?;
+            }
+            Ok(())
+        }
+
+        /// Move the entry fees held for `lobby` to the players at `placements`
+        fn pay_out_lobby(
+            lobby: &LobbyOf<T>,
+            placements: &[T::AccountId],
+            payouts: &[NativeBalanceOf<T>],
+        ) -> DispatchResult {
+            let reason: T::RuntimeHoldReason = HoldReason::LobbyEntryFee.into();
+            let mut sources = lobby.participants.iter();
+            let mut source = sources.next();
+            let mut available = lobby.entry_fee;
+            for (winner, payout) in placements.iter().zip(payouts.iter()) {
+                let mut due = *payout;
+                while !due.is_zero() {
+                    let Some(from) = source else { break };
+                    let amount = due.min(available);
+                    if from == winner {
+                        T::Currency::release(&reason, from, amount, Precision::Exact)?;
+                    } else {
+                        T::Currency::transfer_on_hold(
+                            &reason,
+                            from,
+                            winner,
+                            amount,
+                            Precision::Exact,
+                            Restriction::Free,
+                            Fortitude::Polite,
+                        )?;
+                    }
+                    due -= amount;
+                    available -= amount;
+                    if available.is_zero() {
+                        source = sources.next();
+                        available = lobby.entry_fee;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Release the entry fees held for every participant of `lobby`
+        fn refund_entry_fees(lobby: &LobbyOf<T>) -> DispatchResult {
+            let reason: T::RuntimeHoldReason = HoldReason::LobbyEntryFee.into();
+            for who in lobby.participants.iter() {
+                T::Currency::release(&reason, who, lobby.entry_fee, Precision::BestEffort)?;
+            }
+            Ok(())
+        }
+    }
+}
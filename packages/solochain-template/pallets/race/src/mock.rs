@@ -0,0 +1,167 @@
+use crate as pallet_race;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64, WithdrawReasons},
+};
+use sp_runtime::{traits::ConvertInto, BuildStorage, Percent};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type Balances = pallet_balances::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type UserProfile = pallet_user_profile::Pallet<Test>;
+
+    #[runtime::pallet_index(3)]
+    pub type Character = pallet_character::Pallet<Test>;
+
+    #[runtime::pallet_index(4)]
+    pub type Bike = pallet_bike::Pallet<Test>;
+
+    #[runtime::pallet_index(5)]
+    pub type Race = pallet_race::Pallet<Test>;
+
+    #[runtime::pallet_index(6)]
+    pub type Rewards = pallet_rewards::Pallet<Test>;
+
+    #[runtime::pallet_index(7)]
+    pub type Vesting = pallet_vesting::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+impl pallet_user_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MaxUsernameLength = ConstU32<32>;
+    type MaxBioLength = ConstU32<256>;
+    type QuestProgress = ();
+    type OnStatsUpdated = ();
+}
+
+impl pallet_character::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MintOrigin = frame_system::EnsureRoot<u64>;
+    type MaxEnergy = ConstU32<100>;
+    type EnergyRegenPeriod = ConstU64<10>;
+    type MaxWater = ConstU32<100>;
+    type WaterRegenPeriod = ConstU64<10>;
+    type Attachments = ();
+}
+
+impl pallet_bike::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MintOrigin = frame_system::EnsureRoot<u64>;
+    type RewardToken = Balances;
+    type WearPerDifficulty = ConstU32<10>;
+    type RepairCostPerPoint = ConstU64<2>;
+}
+
+parameter_types! {
+    pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+        WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = ConstU64<1>;
+    type WeightInfo = ();
+    type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+    type BlockNumberProvider = System;
+    const MAX_VESTING_SCHEDULES: u32 = 3;
+}
+
+impl pallet_rewards::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type DistributionOrigin = frame_system::EnsureRoot<u64>;
+    type EmissionOrigin = frame_system::EnsureRoot<u64>;
+    type RewardToken = Balances;
+    type UserStats = UserProfile;
+    type Vesting = Vesting;
+    type VestingThreshold = ConstU64<300>;
+    type VestingDuration = ConstU64<50>;
+    type MaxHistory = ConstU32<4>;
+    type BlocksPerDay = ConstU64<10>;
+}
+
+parameter_types! {
+    pub const SuspiciousSpeedRatio: Percent = Percent::from_percent(80);
+}
+
+impl pallet_race::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Characters = Character;
+    type Bikes = Bike;
+    type UserStats = UserProfile;
+    type Ranked = ();
+    type QuestProgress = ();
+    type ResultOrigin = frame_system::EnsureSigned<u64>;
+    type ServerManagerOrigin = frame_system::EnsureRoot<u64>;
+    type GameAdminOrigin = frame_system::EnsureRoot<u64>;
+    type ArbiterOrigin = frame_system::EnsureRoot<u64>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type Balance = u64;
+    type Rewards = Rewards;
+    type MaxMapNameLength = ConstU32<16>;
+    type MaxAverageSpeed = ConstU32<30>;
+    type SpeedPerBikeStat = ConstU32<500>;
+    type SuspiciousSpeedRatio = SuspiciousSpeedRatio;
+    type SuspicionThreshold = ConstU32<2>;
+    type RankedBanDuration = ConstU64<100>;
+    type RaceTimeout = ConstU64<50>;
+    type MaxLobbyParticipants = ConstU32<4>;
+    type MinLobbyParticipants = ConstU32<2>;
+    type MaxLeaderboardSize = ConstU32<2>;
+    type ChallengePeriod = ConstU64<10>;
+    type DisputeBond = ConstU64<20>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 100), (2, 100), (3, 100), (4, 100)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
+}
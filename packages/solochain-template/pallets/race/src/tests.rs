@@ -0,0 +1,1357 @@
+use crate::{
+    mock::*, ActiveRace, Error, Event, FlaggedResults, GameServers, HoldReason, Leaderboards,
+    Lobbies, LobbyDisputes, LobbyId, LobbyResults, LobbyStatus, MapId, MapInfo, MapInfoOf, Maps,
+    NextRaceId, PayoutCurve, PersonalBest, PersonalBests, RaceId, RaceResult, RaceResults,
+    RaceStatus, Races, RankedBans, ServerKey, ServerSignature, SuspiciousResults,
+    LOBBY_RESULT_SIGNING_CONTEXT, RESULT_SIGNING_CONTEXT,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::InspectHold, ConstU32},
+    BoundedVec,
+};
+use pallet_bike::{BikeId, PartStats, PartType, Rarity};
+use pallet_character::{CharacterId, CharacterStats};
+use pallet_rewards::{EmissionSchedule, RewardReason};
+use pallet_user_profile::UserStatsMap;
+use sp_core::{ed25519, sr25519, Pair, H256};
+use sp_runtime::DispatchError;
+use sp_runtime::{Perbill, Percent};
+
+/// Account relaying results for the game server
+const RELAYER: u64 = 9;
+
+fn part_stats() -> PartStats {
+    PartStats {
+        speed: 10,
+        acceleration: 5,
+        durability: 30,
+        weight: 3,
+        rarity: Rarity::Common,
+    }
+}
+
+fn server() -> sr25519::Pair {
+    sr25519::Pair::from_seed(&[1u8; 32])
+}
+
+fn server_key() -> ServerKey {
+    ServerKey::Sr25519(server().public())
+}
+
+fn sign(pair: &sr25519::Pair, race_id: RaceId, result: &RaceResult) -> ServerSignature {
+    let message = (RESULT_SIGNING_CONTEXT, race_id, result).encode();
+    ServerSignature::Sr25519(pair.sign(&message))
+}
+
+/// Submit `result` for `race_id` signed by the registered game server
+fn submit(race_id: RaceId, result: RaceResult) -> sp_runtime::DispatchResult {
+    let signature = sign(&server(), race_id, &result);
+    Race::submit_race_result(
+        RuntimeOrigin::signed(RELAYER),
+        race_id,
+        result,
+        server_key(),
+        signature,
+    )
+}
+
+/// A 2 km map with the given difficulty, costing 40 energy and 10 water
+fn map(difficulty: u32) -> MapInfoOf<Test> {
+    MapInfo {
+        name: BoundedVec::try_from(b"downhill".to_vec()).unwrap(),
+        distance: 2_000,
+        difficulty,
+        energy_cost: 40,
+        water_cost: 10,
+        base_reward: 1_000,
+        enabled: true,
+    }
+}
+
+/// Register the game server and maps 0 to 2 (difficulty 1 to 3), mint a character (id 0)
+/// and assemble a bike (id 0) for account 1
+fn setup() {
+    System::set_block_number(1);
+    assert_ok!(Race::register_server_key(
+        RuntimeOrigin::root(),
+        server_key()
+    ));
+    for difficulty in 1..=3 {
+        assert_ok!(Race::create_map(RuntimeOrigin::root(), map(difficulty)));
+    }
+    equip(1);
+}
+
+/// Mint a character and assemble a bike for `who`, returning their ids
+fn equip(who: u64) -> (CharacterId, BikeId) {
+    let character_id = pallet_character::NextCharacterId::<Test>::get();
+    let first_part = pallet_bike::NextPartId::<Test>::get();
+    let bike_id = pallet_bike::NextBikeId::<Test>::get();
+    assert_ok!(Character::mint_character(
+        RuntimeOrigin::root(),
+        who,
+        CharacterStats::default()
+    ));
+    for part_type in [
+        PartType::Frame,
+        PartType::Wheel,
+        PartType::Pedal,
+        PartType::Handlebar,
+    ] {
+        assert_ok!(Bike::mint_part(
+            RuntimeOrigin::root(),
+            who,
+            part_type,
+            part_stats()
+        ));
+    }
+    assert_ok!(Bike::assemble_bike(
+        RuntimeOrigin::signed(who),
+        first_part,
+        first_part + 1,
+        first_part + 2,
+        first_part + 3
+    ));
+    (character_id, bike_id)
+}
+
+/// Race rewards `who` can claim from the rewards pallet
+fn pending_reward(who: u64) -> u64 {
+    pallet_rewards::Rewards::<Test>::get(who, RewardReason::Race)
+}
+
+fn result(position: u32) -> RaceResult {
+    RaceResult {
+        position,
+        time_ms: 95_000,
+        distance: 1_500,
+        best_lap_ms: 30_000,
+        ghost_hash: None,
+    }
+}
+
+#[test]
+fn start_race_works() {
+    new_test_ext().execute_with(|| {
+        setup();
+
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 2));
+
+        let race = Races::<Test>::get(0).unwrap();
+        assert_eq!(race.owner, 1);
+        assert_eq!(race.map_id, 2);
+        assert_eq!(race.started_at, 1);
+        assert_eq!(race.status, RaceStatus::Started);
+        assert_eq!(ActiveRace::<Test>::get(1), Some(0));
+        assert_eq!(Character::current_energy(0), 60);
+        assert_eq!(Character::current_water(0), 90);
+
+        System::assert_last_event(
+            Event::RaceStarted {
+                race_id: 0,
+                owner: 1,
+                character_id: 0,
+                bike_id: 0,
+                map_id: 2,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn start_race_validates_ownership_and_energy() {
+    new_test_ext().execute_with(|| {
+        setup();
+
+        assert_noop!(
+            Race::start_race(RuntimeOrigin::signed(1), 0, 0, 3),
+            Error::<Test>::MapNotFound
+        );
+        assert_noop!(
+            Race::start_race(RuntimeOrigin::signed(2), 0, 0, 0),
+            Error::<Test>::NotCharacterOwner
+        );
+        assert_ok!(Character::transfer_character(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert_noop!(
+            Race::start_race(RuntimeOrigin::signed(2), 0, 0, 0),
+            pallet_bike::Error::<Test>::NotBikeOwner
+        );
+        assert_ok!(Character::transfer_character(
+            RuntimeOrigin::signed(2),
+            0,
+            1
+        ));
+
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_noop!(
+            Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0),
+            Error::<Test>::AlreadyRacing
+        );
+        assert_ok!(submit(0, result(1)));
+
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(1, result(1)));
+        assert_noop!(
+            Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0),
+            pallet_character::Error::<Test>::NotEnoughEnergy
+        );
+    });
+}
+
+#[test]
+fn submit_race_result_works() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 1));
+
+        assert_noop!(
+            Race::submit_race_result(
+                RuntimeOrigin::none(),
+                0,
+                result(1),
+                server_key(),
+                sign(&server(), 0, &result(1))
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(submit(0, result(0)), Error::<Test>::InvalidResult);
+        assert_ok!(submit(0, result(2)));
+
+        assert_eq!(
+            Races::<Test>::get(0).unwrap().status,
+            RaceStatus::Completed {
+                reward: 500,
+                challenge_ends: 11
+            }
+        );
+        assert_eq!(RaceResults::<Test>::get(0), Some(result(2)));
+        assert_eq!(ActiveRace::<Test>::get(1), None);
+        // Map 1 has difficulty 2, wearing every part by 20
+        assert_eq!(pallet_bike::Parts::<Test>::get(0).unwrap().wear, 20);
+
+        System::assert_last_event(
+            Event::RaceCompleted {
+                race_id: 0,
+                position: 2,
+                time_ms: 95_000,
+                distance: 1_500,
+                reward: 500,
+                server: server_key(),
+            }
+            .into(),
+        );
+
+        assert_noop!(submit(0, result(1)), Error::<Test>::RaceNotRunning);
+    });
+}
+
+#[test]
+fn claim_race_reward_works() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotCompleted
+        );
+        assert_ok!(submit(0, result(1)));
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotRaceOwner
+        );
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::ChallengePeriodActive
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(pending_reward(1), 1_000);
+        assert_eq!(Races::<Test>::get(0).unwrap().status, RaceStatus::Claimed);
+
+        // Runs that do not cover the whole track set no record
+        assert_eq!(PersonalBests::<Test>::get(0, 1), None);
+
+        let stats = UserStatsMap::<Test>::get(1).unwrap();
+        assert_eq!(stats.total_races, 1);
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.total_distance, 1_500);
+
+        System::assert_last_event(
+            Event::RaceRewardClaimed {
+                race_id: 0,
+                owner: 1,
+                reward: 1_000,
+                unpaid: 0,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotCompleted
+        );
+    });
+}
+
+/// Limit race and quest rewards to `pool` per day
+fn set_daily_pool(pool: u64) {
+    assert_ok!(Rewards::set_emission_schedule(
+        RuntimeOrigin::root(),
+        Some(EmissionSchedule {
+            initial_daily_pool: pool,
+            era_days: 100,
+            era_decay: Perbill::zero(),
+            account_daily_cap: 10_000,
+        })
+    ));
+}
+
+#[test]
+fn short_emission_budget_leaves_the_rest_claimable() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(0, result(1)));
+        System::set_block_number(11);
+
+        set_daily_pool(400);
+        assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(pending_reward(1), 400);
+        assert_eq!(
+            Races::<Test>::get(0).unwrap().status,
+            RaceStatus::PartiallyClaimed { unpaid: 600 }
+        );
+        System::assert_last_event(
+            Event::RaceRewardClaimed {
+                race_id: 0,
+                owner: 1,
+                reward: 400,
+                unpaid: 600,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RewardPoolExhausted
+        );
+
+        set_daily_pool(1_000);
+        assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(pending_reward(1), 1_000);
+        assert_eq!(Races::<Test>::get(0).unwrap().status, RaceStatus::Claimed);
+        System::assert_last_event(
+            Event::RaceRewardClaimed {
+                race_id: 0,
+                owner: 1,
+                reward: 600,
+                unpaid: 0,
+            }
+            .into(),
+        );
+
+        // The race itself is only counted once
+        assert_eq!(UserStatsMap::<Test>::get(1).unwrap().total_races, 1);
+    });
+}
+
+#[test]
+fn exhausted_emission_budget_keeps_race_unclaimed() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(0, result(1)));
+        System::set_block_number(11);
+
+        set_daily_pool(0);
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RewardPoolExhausted
+        );
+        assert!(matches!(
+            Races::<Test>::get(0).unwrap().status,
+            RaceStatus::Completed { reward: 1_000, .. }
+        ));
+        assert_eq!(
+            UserStatsMap::<Test>::get(1).unwrap_or_default().total_races,
+            0
+        );
+
+        assert_ok!(Rewards::set_emission_schedule(RuntimeOrigin::root(), None));
+        assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(Races::<Test>::get(0).unwrap().status, RaceStatus::Claimed);
+    });
+}
+
+#[test]
+fn timed_out_races_can_be_cancelled() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+
+        System::set_block_number(50);
+        assert_noop!(
+            Race::cancel_race(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotTimedOut
+        );
+
+        System::set_block_number(51);
+        assert_noop!(
+            Race::cancel_race(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotRaceOwner
+        );
+        assert_ok!(Race::cancel_race(RuntimeOrigin::signed(1), 0));
+        assert_eq!(Races::<Test>::get(0).unwrap().status, RaceStatus::Cancelled);
+        assert_eq!(ActiveRace::<Test>::get(1), None);
+        System::assert_last_event(
+            Event::RaceCancelled {
+                race_id: 0,
+                owner: 1,
+            }
+            .into(),
+        );
+
+        // A late result no longer counts, and the owner can race again
+        assert_noop!(submit(0, result(1)), Error::<Test>::RaceNotRunning);
+        assert_noop!(
+            Race::cancel_race(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotRunning
+        );
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+    });
+}
+
+#[test]
+fn result_is_recorded_when_wear_cannot_be_applied() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(Bike::disassemble_bike(RuntimeOrigin::signed(1), 0));
+
+        assert_ok!(submit(0, result(1)));
+        assert!(matches!(
+            Races::<Test>::get(0).unwrap().status,
+            RaceStatus::Completed { .. }
+        ));
+        System::assert_has_event(
+            Event::WearNotApplied {
+                race_id: 0,
+                bike_id: 0,
+                error: pallet_bike::Error::<Test>::BikeNotFound.into(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn submit_race_result_checks_signature() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+
+        // Signed by an unregistered key
+        let rogue = sr25519::Pair::from_seed(&[2u8; 32]);
+        assert_noop!(
+            Race::submit_race_result(
+                RuntimeOrigin::signed(RELAYER),
+                0,
+                result(1),
+                ServerKey::Sr25519(rogue.public()),
+                sign(&rogue, 0, &result(1))
+            ),
+            Error::<Test>::UnknownServerKey
+        );
+        // Signature over another result
+        assert_noop!(
+            Race::submit_race_result(
+                RuntimeOrigin::signed(RELAYER),
+                0,
+                result(1),
+                server_key(),
+                sign(&server(), 0, &result(3))
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        // Signature for another race id
+        assert_noop!(
+            Race::submit_race_result(
+                RuntimeOrigin::signed(RELAYER),
+                0,
+                result(1),
+                server_key(),
+                sign(&server(), 1, &result(1))
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        // Ed25519 servers are supported as well
+        let ed_server = ed25519::Pair::from_seed(&[3u8; 32]);
+        let ed_key = ServerKey::Ed25519(ed_server.public());
+        assert_ok!(Race::register_server_key(
+            RuntimeOrigin::root(),
+            ed_key.clone()
+        ));
+        let message = (RESULT_SIGNING_CONTEXT, 0 as RaceId, &result(1)).encode();
+        assert_noop!(
+            Race::submit_race_result(
+                RuntimeOrigin::signed(RELAYER),
+                0,
+                result(1),
+                ed_key.clone(),
+                sign(&server(), 0, &result(1))
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        assert_ok!(Race::submit_race_result(
+            RuntimeOrigin::signed(RELAYER),
+            0,
+            result(1),
+            ed_key,
+            ServerSignature::Ed25519(ed_server.sign(&message))
+        ));
+    });
+}
+
+#[test]
+fn server_keys_can_be_rotated_and_revoked() {
+    new_test_ext().execute_with(|| {
+        setup();
+        let new_server = sr25519::Pair::from_seed(&[4u8; 32]);
+        let new_key = ServerKey::Sr25519(new_server.public());
+
+        assert_noop!(
+            Race::register_server_key(RuntimeOrigin::signed(1), new_key.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Race::register_server_key(RuntimeOrigin::root(), server_key()),
+            Error::<Test>::ServerKeyExists
+        );
+
+        assert_ok!(Race::rotate_server_key(
+            RuntimeOrigin::root(),
+            server_key(),
+            new_key.clone()
+        ));
+        assert!(!GameServers::<Test>::contains_key(server_key()));
+        assert_eq!(GameServers::<Test>::get(&new_key), Some(1));
+        System::assert_last_event(
+            Event::ServerKeyRotated {
+                old: server_key(),
+                new: new_key.clone(),
+            }
+            .into(),
+        );
+
+        // The old key no longer signs valid results
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_noop!(submit(0, result(1)), Error::<Test>::UnknownServerKey);
+
+        assert_ok!(Race::revoke_server_key(
+            RuntimeOrigin::root(),
+            new_key.clone()
+        ));
+        System::assert_last_event(
+            Event::ServerKeyRevoked {
+                key: new_key.clone(),
+            }
+            .into(),
+        );
+        assert_noop!(
+            Race::submit_race_result(
+                RuntimeOrigin::signed(RELAYER),
+                0,
+                result(1),
+                new_key.clone(),
+                sign(&new_server, 0, &result(1))
+            ),
+            Error::<Test>::UnknownServerKey
+        );
+        assert_noop!(
+            Race::revoke_server_key(RuntimeOrigin::root(), new_key),
+            Error::<Test>::UnknownServerKey
+        );
+    });
+}
+
+#[test]
+fn submit_race_result_rejects_implausible_results() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+
+        // Longer than the 2 km track
+        let too_far = RaceResult {
+            distance: 2_001,
+            ..result(1)
+        };
+        assert_noop!(submit(0, too_far), Error::<Test>::InvalidResult);
+        let no_time = RaceResult {
+            time_ms: 0,
+            ..result(1)
+        };
+        assert_noop!(submit(0, no_time), Error::<Test>::InvalidResult);
+        // The bike has 40 speed, allowing 20 m/s: 1.5 km take at least 75 s
+        let too_fast = RaceResult {
+            time_ms: 74_999,
+            ..result(1)
+        };
+        assert_noop!(submit(0, too_fast), Error::<Test>::ImplausibleSpeed);
+
+        let fastest = RaceResult {
+            time_ms: 75_000,
+            ..result(1)
+        };
+        assert_ok!(submit(0, fastest));
+    });
+}
+
+#[test]
+fn speed_limit_follows_bike_stats() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_eq!(Races::<Test>::get(0).unwrap().speed_limit, 20_000);
+
+        let fast_bike = pallet_bike::BikeStats {
+            speed: 100,
+            ..Default::default()
+        };
+        // Capped by the maximum average speed of 30 m/s
+        assert_eq!(Race::speed_limit(&fast_bike), 30_000);
+    });
+}
+
+#[test]
+fn borderline_results_are_flagged_for_review() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+
+        // 1.5 km in 90 s is 16.7 m/s, above 80% of the 20 m/s limit
+        let borderline = RaceResult {
+            time_ms: 90_000,
+            ..result(1)
+        };
+        assert_ok!(submit(0, borderline.clone()));
+        assert_eq!(
+            Races::<Test>::get(0).unwrap().status,
+            RaceStatus::Flagged { reward: 1_000 }
+        );
+        assert_eq!(FlaggedResults::<Test>::get(0), Some(1));
+        assert_eq!(SuspiciousResults::<Test>::get(1), 1);
+        System::assert_last_event(
+            Event::ResultFlagged {
+                race_id: 0,
+                owner: 1,
+                suspicious_results: 1,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotCompleted
+        );
+
+        assert_noop!(
+            Race::review_flagged_result(RuntimeOrigin::signed(1), 0, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Race::review_flagged_result(RuntimeOrigin::root(), 0, true));
+        System::assert_last_event(
+            Event::FlaggedResultReviewed {
+                race_id: 0,
+                legitimate: true,
+            }
+            .into(),
+        );
+        assert_eq!(FlaggedResults::<Test>::get(0), None);
+        assert_eq!(SuspiciousResults::<Test>::get(1), 0);
+        assert_noop!(
+            Race::review_flagged_result(RuntimeOrigin::root(), 0, true),
+            Error::<Test>::RaceNotFlagged
+        );
+        assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(1), 0));
+
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(1, borderline));
+        assert_ok!(Race::review_flagged_result(RuntimeOrigin::root(), 1, false));
+        assert_eq!(Races::<Test>::get(1).unwrap().status, RaceStatus::Rejected);
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::RaceNotCompleted
+        );
+    });
+}
+
+#[test]
+fn repeated_flagged_results_ban_from_ranked_lobbies() {
+    new_test_ext().execute_with(|| {
+        setup();
+        let borderline = RaceResult {
+            time_ms: 90_000,
+            ..result(1)
+        };
+        for race_id in 0..2 {
+            assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+            assert_ok!(submit(race_id, borderline.clone()));
+        }
+
+        assert_eq!(RankedBans::<Test>::get(1), Some(101));
+        assert_eq!(SuspiciousResults::<Test>::get(1), 0);
+        System::assert_last_event(Event::RankedBanned { who: 1, until: 101 }.into());
+        assert_noop!(
+            Race::create_lobby(RuntimeOrigin::signed(1), 0, 3, 10, 10),
+            Error::<Test>::RankedBanned
+        );
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(2), 0, 3, 10, 200));
+        assert_noop!(
+            Race::join_lobby(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RankedBanned
+        );
+
+        System::set_block_number(101);
+        assert!(!Race::is_ranked_banned(&1));
+        assert_ok!(Race::join_lobby(RuntimeOrigin::signed(1), 0));
+    });
+}
+
+#[test]
+fn maps_are_managed_by_game_admin() {
+    new_test_ext().execute_with(|| {
+        setup();
+
+        assert_noop!(
+            Race::create_map(RuntimeOrigin::signed(1), map(1)),
+            DispatchError::BadOrigin
+        );
+        let empty = MapInfo {
+            distance: 0,
+            ..map(1)
+        };
+        assert_noop!(
+            Race::create_map(RuntimeOrigin::root(), empty),
+            Error::<Test>::InvalidMap
+        );
+        assert_ok!(Race::create_map(RuntimeOrigin::root(), map(4)));
+        assert_eq!(Maps::<Test>::get(3), Some(map(4)));
+        System::assert_last_event(
+            Event::MapCreated {
+                map_id: 3,
+                info: map(4),
+            }
+            .into(),
+        );
+
+        let disabled = MapInfo {
+            enabled: false,
+            ..map(4)
+        };
+        assert_noop!(
+            Race::update_map(RuntimeOrigin::root(), 4, disabled.clone()),
+            Error::<Test>::MapNotFound
+        );
+        assert_ok!(Race::update_map(RuntimeOrigin::root(), 3, disabled.clone()));
+        System::assert_last_event(
+            Event::MapUpdated {
+                map_id: 3,
+                info: disabled,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Race::start_race(RuntimeOrigin::signed(1), 0, 0, 3),
+            Error::<Test>::MapDisabled
+        );
+        assert_noop!(
+            Race::create_lobby(RuntimeOrigin::signed(1), 3, 3, 10, 10),
+            Error::<Test>::MapDisabled
+        );
+    });
+}
+
+#[test]
+fn upheld_dispute_corrects_result() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(0, result(4)));
+
+        assert_noop!(
+            Race::resolve_dispute(RuntimeOrigin::root(), 0, None),
+            Error::<Test>::RaceNotDisputed
+        );
+        assert_noop!(
+            Race::dispute_result(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotRaceOwner
+        );
+        assert_ok!(Race::dispute_result(RuntimeOrigin::signed(1), 0));
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::DisputeBond.into(), &1),
+            20
+        );
+        assert_eq!(Races::<Test>::get(0).unwrap().status, RaceStatus::Disputed);
+        System::assert_last_event(
+            Event::ResultDisputed {
+                race_id: 0,
+                disputer: 1,
+                bond: 20,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Race::dispute_result(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotCompleted
+        );
+        assert_noop!(
+            Race::claim_race_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RaceNotCompleted
+        );
+
+        assert_noop!(
+            Race::resolve_dispute(RuntimeOrigin::signed(1), 0, Some(result(1))),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Race::resolve_dispute(
+            RuntimeOrigin::root(),
+            0,
+            Some(result(1))
+        ));
+        System::assert_last_event(
+            Event::DisputeResolved {
+                race_id: 0,
+                upheld: true,
+                reward: 1_000,
+            }
+            .into(),
+        );
+        assert_eq!(RaceResults::<Test>::get(0), Some(result(1)));
+        assert_eq!(pending_reward(1), 0);
+
+        // Claimable without waiting for another challenge period
+        assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(1), 0));
+        assert_eq!(pending_reward(1), 1_000);
+        assert_eq!(UserStatsMap::<Test>::get(1).unwrap().wins, 1);
+    });
+}
+
+#[test]
+fn rejected_dispute_slashes_bond() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(0, result(4)));
+
+        assert_ok!(Race::dispute_result(RuntimeOrigin::signed(1), 0));
+        assert_ok!(Race::resolve_dispute(RuntimeOrigin::root(), 0, None));
+        System::assert_last_event(
+            Event::DisputeResolved {
+                race_id: 0,
+                upheld: false,
+                reward: 250,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(1), 80);
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::DisputeBond.into(), &1),
+            0
+        );
+        assert_eq!(RaceResults::<Test>::get(0), Some(result(4)));
+
+        // Results can no longer be disputed once the challenge period is over
+        assert_ok!(Race::start_race(RuntimeOrigin::signed(1), 0, 0, 0));
+        assert_ok!(submit(1, result(1)));
+        System::set_block_number(11);
+        assert_noop!(
+            Race::dispute_result(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::ChallengePeriodOver
+        );
+    });
+}
+
+/// Race map 0 to the finish line with `equipment` of `who` and claim the reward
+fn finish(who: u64, equipment: (CharacterId, BikeId), time_ms: u64, best_lap_ms: u64) -> RaceId {
+    let (character_id, bike_id) = equipment;
+    assert_ok!(Race::start_race(
+        RuntimeOrigin::signed(who),
+        character_id,
+        bike_id,
+        0
+    ));
+    let race_id = NextRaceId::<Test>::get() - 1;
+    let run = RaceResult {
+        position: 1,
+        time_ms,
+        distance: 2_000,
+        best_lap_ms,
+        ghost_hash: Some(H256::repeat_byte(race_id as u8)),
+    };
+    assert_ok!(submit(race_id, run));
+    System::set_block_number(System::block_number() + 10);
+    assert_ok!(Race::claim_race_reward(RuntimeOrigin::signed(who), race_id));
+    race_id
+}
+
+fn leaders(map_id: MapId) -> Vec<(u64, u64)> {
+    Leaderboards::<Test>::get(map_id)
+        .iter()
+        .map(|entry| (entry.account, entry.time_ms))
+        .collect()
+}
+
+#[test]
+fn claimed_runs_set_personal_bests_and_leaderboard() {
+    new_test_ext().execute_with(|| {
+        setup();
+        let first = (0, 0);
+        let second = equip(2);
+        let third = equip(3);
+
+        let race_id = finish(1, first, 150_000, 50_000);
+        assert_eq!(
+            PersonalBests::<Test>::get(0, 1),
+            Some(PersonalBest {
+                time_ms: 150_000,
+                best_lap_ms: 50_000,
+                race_id,
+                ghost_hash: Some(H256::repeat_byte(race_id as u8)),
+            })
+        );
+        System::assert_has_event(
+            Event::LeaderboardUpdated {
+                map_id: 0,
+                who: 1,
+                rank: 1,
+            }
+            .into(),
+        );
+
+        finish(2, second, 140_000, 60_000);
+        assert_eq!(leaders(0), vec![(2, 140_000), (1, 150_000)]);
+
+        // A slower run only improves the best lap
+        System::reset_events();
+        finish(1, first, 160_000, 45_000);
+        let best = PersonalBests::<Test>::get(0, 1).unwrap();
+        assert_eq!(
+            (best.time_ms, best.best_lap_ms, best.race_id),
+            (150_000, 45_000, race_id)
+        );
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Race(Event::PersonalBestSet { .. })
+        )));
+
+        // Too slow for the full leaderboard
+        finish(3, third, 170_000, 0);
+        assert!(PersonalBests::<Test>::contains_key(0, 3));
+        assert_eq!(leaders(0), vec![(2, 140_000), (1, 150_000)]);
+
+        let race_id = finish(3, third, 130_000, 0);
+        assert_eq!(leaders(0), vec![(3, 130_000), (2, 140_000)]);
+        System::assert_has_event(
+            Event::PersonalBestSet {
+                map_id: 0,
+                who: 3,
+                time_ms: 130_000,
+                race_id,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Leaderboards::<Test>::get(0)[0].ghost_hash,
+            Some(H256::repeat_byte(race_id as u8))
+        );
+    });
+}
+
+fn held(who: u64) -> u64 {
+    Balances::balance_on_hold(&HoldReason::LobbyEntryFee.into(), &who)
+}
+
+fn placements(accounts: Vec<u64>) -> BoundedVec<u64, ConstU32<4>> {
+    BoundedVec::try_from(accounts).unwrap()
+}
+
+/// Submit `ranking` for `lobby_id` signed by the registered game server
+fn submit_lobby(lobby_id: LobbyId, ranking: Vec<u64>) -> sp_runtime::DispatchResult {
+    let ranking = placements(ranking);
+    let message = (LOBBY_RESULT_SIGNING_CONTEXT, lobby_id, &ranking).encode();
+    Race::submit_lobby_result(
+        RuntimeOrigin::signed(RELAYER),
+        lobby_id,
+        ranking,
+        server_key(),
+        ServerSignature::Sr25519(server().sign(&message)),
+    )
+}
+
+/// Open lobby 0 hosted by account 1 with a fee of 10, joined by accounts 2 and 3
+fn setup_lobby() {
+    setup();
+    assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 1, 3, 10, 10));
+    assert_ok!(Race::join_lobby(RuntimeOrigin::signed(2), 0));
+    assert_ok!(Race::join_lobby(RuntimeOrigin::signed(3), 0));
+}
+
+#[test]
+fn create_and_join_lobby_works() {
+    new_test_ext().execute_with(|| {
+        setup();
+
+        assert_noop!(
+            Race::create_lobby(RuntimeOrigin::signed(1), 1, 5, 10, 10),
+            Error::<Test>::InvalidParticipantLimit
+        );
+        assert_noop!(
+            Race::create_lobby(RuntimeOrigin::signed(1), 1, 3, 10, 1),
+            Error::<Test>::DeadlinePassed
+        );
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 1, 3, 10, 10));
+        assert_eq!(held(1), 10);
+        System::assert_has_event(
+            Event::LobbyCreated {
+                lobby_id: 0,
+                host: 1,
+                map_id: 1,
+                max_participants: 3,
+                entry_fee: 10,
+                start_deadline: 10,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Race::join_lobby(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::AlreadyJoined
+        );
+        assert_ok!(Race::join_lobby(RuntimeOrigin::signed(2), 0));
+        assert_ok!(Race::join_lobby(RuntimeOrigin::signed(3), 0));
+        System::assert_last_event(
+            Event::LobbyJoined {
+                lobby_id: 0,
+                who: 3,
+            }
+            .into(),
+        );
+        assert_eq!(held(3), 10);
+        assert_eq!(Balances::free_balance(3), 90);
+        assert_noop!(
+            Race::join_lobby(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::LobbyFull
+        );
+
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 1, 3, 10, 10));
+        System::set_block_number(11);
+        assert_noop!(
+            Race::join_lobby(RuntimeOrigin::signed(4), 1),
+            Error::<Test>::DeadlinePassed
+        );
+    });
+}
+
+#[test]
+fn submit_lobby_result_splits_prize_pool() {
+    new_test_ext().execute_with(|| {
+        setup_lobby();
+        let curve: BoundedVec<Percent, ConstU32<4>> = BoundedVec::try_from(vec![
+            Percent::from_percent(60),
+            Percent::from_percent(30),
+            Percent::from_percent(10),
+        ])
+        .unwrap();
+        assert_ok!(Race::set_payout_curve(RuntimeOrigin::root(), curve));
+
+        assert_noop!(
+            submit_lobby(0, vec![3, 1]),
+            Error::<Test>::InvalidPlacements
+        );
+        assert_noop!(
+            submit_lobby(0, vec![3, 1, 1]),
+            Error::<Test>::InvalidPlacements
+        );
+        assert_noop!(
+            submit_lobby(0, vec![3, 1, 4]),
+            Error::<Test>::InvalidPlacements
+        );
+        assert_ok!(submit_lobby(0, vec![3, 1, 2]));
+        assert_eq!(
+            Lobbies::<Test>::get(0).unwrap().status,
+            LobbyStatus::Settling
+        );
+        System::assert_last_event(
+            Event::LobbyResultPosted {
+                lobby_id: 0,
+                placements: placements(vec![3, 1, 2]),
+                challenge_ends: 11,
+            }
+            .into(),
+        );
+        assert_noop!(submit_lobby(0, vec![3, 1, 2]), Error::<Test>::LobbyNotOpen);
+
+        // Nothing is paid out during the challenge period
+        assert_noop!(
+            Race::settle_lobby(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::ChallengePeriodActive
+        );
+        assert_eq!(held(3), 10);
+
+        System::set_block_number(11);
+        assert_ok!(Race::settle_lobby(RuntimeOrigin::signed(4), 0));
+
+        // Pot of 30 split 18 / 9 / 3
+        assert_eq!(Balances::free_balance(3), 108);
+        assert_eq!(Balances::free_balance(1), 99);
+        assert_eq!(Balances::free_balance(2), 93);
+        for who in [1, 2, 3] {
+            assert_eq!(held(who), 0);
+        }
+        assert_eq!(
+            Lobbies::<Test>::get(0).unwrap().status,
+            LobbyStatus::Settled
+        );
+
+        System::assert_last_event(
+            Event::LobbySettled {
+                lobby_id: 0,
+                placements: placements(vec![3, 1, 2]),
+                payouts: BoundedVec::try_from(vec![18, 9, 3]).unwrap(),
+            }
+            .into(),
+        );
+        assert_eq!(LobbyResults::<Test>::get(0), None);
+        assert_noop!(
+            Race::settle_lobby(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::LobbyNotSettling
+        );
+    });
+}
+
+#[test]
+fn winner_takes_all_without_payout_curve() {
+    new_test_ext().execute_with(|| {
+        setup_lobby();
+        assert!(PayoutCurve::<Test>::get().is_empty());
+
+        assert_ok!(submit_lobby(0, vec![2, 3, 1]));
+        System::set_block_number(11);
+        assert_ok!(Race::settle_lobby(RuntimeOrigin::signed(4), 0));
+
+        assert_eq!(Balances::free_balance(2), 120);
+        assert_eq!(Balances::free_balance(3), 90);
+        assert_eq!(Balances::free_balance(1), 90);
+    });
+}
+
+#[test]
+fn upheld_lobby_dispute_corrects_placements() {
+    new_test_ext().execute_with(|| {
+        setup_lobby();
+        assert_ok!(submit_lobby(0, vec![2, 3, 1]));
+
+        assert_noop!(
+            Race::dispute_lobby_result(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::NotLobbyParticipant
+        );
+        assert_ok!(Race::dispute_lobby_result(RuntimeOrigin::signed(1), 0));
+        assert_eq!(
+            Lobbies::<Test>::get(0).unwrap().status,
+            LobbyStatus::Disputed
+        );
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::DisputeBond.into(), &1),
+            20
+        );
+        System::assert_last_event(
+            Event::LobbyResultDisputed {
+                lobby_id: 0,
+                disputer: 1,
+                bond: 20,
+            }
+            .into(),
+        );
+        System::set_block_number(11);
+        assert_noop!(
+            Race::settle_lobby(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::LobbyNotSettling
+        );
+
+        assert_noop!(
+            Race::resolve_lobby_dispute(RuntimeOrigin::root(), 0, Some(placements(vec![1, 1, 2]))),
+            Error::<Test>::InvalidPlacements
+        );
+        assert_ok!(Race::resolve_lobby_dispute(
+            RuntimeOrigin::root(),
+            0,
+            Some(placements(vec![1, 2, 3]))
+        ));
+        System::assert_has_event(
+            Event::LobbyDisputeResolved {
+                lobby_id: 0,
+                upheld: true,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Lobbies::<Test>::get(0).unwrap().status,
+            LobbyStatus::Settled
+        );
+        assert_eq!(Balances::free_balance(1), 120);
+        assert_eq!(Balances::free_balance(2), 90);
+        assert_eq!(Balances::free_balance(3), 90);
+        assert_eq!(LobbyDisputes::<Test>::get(0), None);
+    });
+}
+
+#[test]
+fn rejected_lobby_dispute_slashes_bond() {
+    new_test_ext().execute_with(|| {
+        setup_lobby();
+        assert_ok!(submit_lobby(0, vec![2, 3, 1]));
+
+        assert_ok!(Race::dispute_lobby_result(RuntimeOrigin::signed(1), 0));
+        assert_noop!(
+            Race::resolve_lobby_dispute(RuntimeOrigin::signed(2), 0, None),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Race::resolve_lobby_dispute(RuntimeOrigin::root(), 0, None));
+        assert_eq!(Balances::free_balance(1), 70);
+        assert_eq!(Balances::free_balance(2), 120);
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::DisputeBond.into(), &1),
+            0
+        );
+        System::assert_last_event(
+            Event::LobbySettled {
+                lobby_id: 0,
+                placements: placements(vec![2, 3, 1]),
+                payouts: BoundedVec::try_from(vec![30, 0, 0]).unwrap(),
+            }
+            .into(),
+        );
+
+        // Placements can no longer be disputed once the challenge period is over
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 1, 3, 10, 10));
+        assert_ok!(Race::join_lobby(RuntimeOrigin::signed(2), 1));
+        assert_ok!(submit_lobby(1, vec![2, 1]));
+        System::set_block_number(11);
+        assert_noop!(
+            Race::dispute_lobby_result(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::ChallengePeriodOver
+        );
+    });
+}
+
+#[test]
+fn cancelled_and_under_filled_lobbies_are_refunded() {
+    new_test_ext().execute_with(|| {
+        setup_lobby();
+
+        assert_noop!(
+            Race::cancel_lobby(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotLobbyHost
+        );
+        assert_ok!(Race::cancel_lobby(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(Event::LobbyCancelled { lobby_id: 0 }.into());
+        for who in [1, 2, 3] {
+            assert_eq!(held(who), 0);
+            assert_eq!(Balances::free_balance(who), 100);
+        }
+        assert_noop!(
+            Race::join_lobby(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::LobbyNotOpen
+        );
+
+        // Lobby 1 only gathers its host before the deadline
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 0, 2, 10, 10));
+        assert_noop!(
+            Race::refund_lobby(RuntimeOrigin::signed(4), 1),
+            Error::<Test>::DeadlineNotPassed
+        );
+        assert_noop!(
+            submit_lobby(1, vec![1]),
+            Error::<Test>::NotEnoughParticipants
+        );
+        System::set_block_number(11);
+        assert_ok!(Race::refund_lobby(RuntimeOrigin::signed(4), 1));
+        System::assert_last_event(Event::LobbyRefunded { lobby_id: 1 }.into());
+        assert_eq!(held(1), 0);
+        assert_eq!(
+            Lobbies::<Test>::get(1).unwrap().status,
+            LobbyStatus::Cancelled
+        );
+
+        // Lobby 2 filled up and must be raced
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 0, 2, 10, 20));
+        assert_ok!(Race::join_lobby(RuntimeOrigin::signed(2), 2));
+        System::set_block_number(21);
+        assert_noop!(
+            Race::refund_lobby(RuntimeOrigin::signed(4), 2),
+            Error::<Test>::LobbyFilled
+        );
+    });
+}
+
+#[test]
+fn lobbies_without_result_expire() {
+    new_test_ext().execute_with(|| {
+        setup_lobby();
+
+        // Deadline 10 plus a race timeout of 50 and a challenge period of 10
+        System::set_block_number(70);
+        assert_noop!(
+            Race::expire_lobby(RuntimeOrigin::signed(4), 0),
+            Error::<Test>::LobbyNotExpired
+        );
+        System::set_block_number(71);
+        assert_ok!(Race::expire_lobby(RuntimeOrigin::signed(4), 0));
+        System::assert_last_event(Event::LobbyExpired { lobby_id: 0 }.into());
+        for who in [1, 2, 3] {
+            assert_eq!(held(who), 0);
+            assert_eq!(Balances::free_balance(who), 100);
+        }
+        assert_eq!(
+            Lobbies::<Test>::get(0).unwrap().status,
+            LobbyStatus::Cancelled
+        );
+        assert_noop!(submit_lobby(0, vec![3, 1, 2]), Error::<Test>::LobbyNotOpen);
+
+        // Under-filled lobbies are refunded instead
+        assert_ok!(Race::create_lobby(RuntimeOrigin::signed(1), 0, 2, 10, 70));
+        System::set_block_number(200);
+        assert_noop!(
+            Race::expire_lobby(RuntimeOrigin::signed(4), 1),
+            Error::<Test>::NotEnoughParticipants
+        );
+    });
+}
+
+#[test]
+fn set_payout_curve_validates_shares() {
+    new_test_ext().execute_with(|| {
+        let curve: BoundedVec<Percent, ConstU32<4>> =
+            BoundedVec::try_from(vec![Percent::from_percent(60), Percent::from_percent(30)])
+                .unwrap();
+
+        assert_noop!(
+            Race::set_payout_curve(RuntimeOrigin::signed(1), curve.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Race::set_payout_curve(RuntimeOrigin::root(), curve),
+            Error::<Test>::InvalidPayoutCurve
+        );
+    });
+}
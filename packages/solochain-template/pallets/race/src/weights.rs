@@ -0,0 +1,490 @@
+//! Autogenerated weights for pallet_race
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-01-01, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `localhost`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_race.
+pub trait WeightInfo {
+	fn start_race() -> Weight;
+	fn submit_race_result() -> Weight;
+	fn claim_race_reward() -> Weight;
+	fn cancel_race() -> Weight;
+	fn register_server_key() -> Weight;
+	fn rotate_server_key() -> Weight;
+	fn revoke_server_key() -> Weight;
+	fn create_lobby() -> Weight;
+	fn join_lobby(n: u32, ) -> Weight;
+	fn cancel_lobby(n: u32, ) -> Weight;
+	fn refund_lobby(n: u32, ) -> Weight;
+	fn submit_lobby_result(n: u32, ) -> Weight;
+	fn set_payout_curve() -> Weight;
+	fn expire_lobby(n: u32, ) -> Weight;
+	fn create_map() -> Weight;
+	fn update_map() -> Weight;
+	fn review_flagged_result() -> Weight;
+	fn dispute_result() -> Weight;
+	fn resolve_dispute() -> Weight;
+	fn settle_lobby(n: u32, ) -> Weight;
+	fn dispute_lobby_result() -> Weight;
+	fn resolve_lobby_dispute(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_race using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Race Maps (r:1 w:0)
+	/// Storage: Character Characters (r:1 w:0)
+	/// Storage: Character Energy (r:1 w:1)
+	/// Storage: Character Water (r:1 w:1)
+	/// Storage: Bike Bikes (r:1 w:0)
+	/// Storage: Bike Parts (r:4 w:0)
+	/// Storage: Race ActiveRace (r:1 w:1)
+	/// Storage: Race NextRaceId (r:1 w:1)
+	/// Storage: Race Races (r:0 w:1)
+	fn start_race() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 48_000_000 picoseconds.
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Race GameServers (r:1 w:0)
+	/// Storage: Race Races (r:1 w:1)
+	/// Storage: Race Maps (r:1 w:0)
+	/// Storage: Bike Bikes (r:1 w:0)
+	/// Storage: Bike Parts (r:4 w:4)
+	/// Storage: Race SuspiciousResults (r:1 w:1)
+	/// Storage: Race ActiveRace (r:0 w:1)
+	/// Storage: Race RaceResults (r:0 w:1)
+	/// Storage: Race FlaggedResults (r:0 w:1)
+	/// Storage: Race RankedBans (r:0 w:1)
+	fn submit_race_result() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 96_000_000 picoseconds.
+		Weight::from_parts(96_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: Race Races (r:1 w:1)
+	/// Storage: Race RaceResults (r:1 w:0)
+	/// Storage: Rewards Rewards (r:1 w:1)
+	/// Storage: Rewards RewardHistory (r:1 w:1)
+	/// Storage: UserProfile UserStatsMap (r:1 w:1)
+	/// Storage: Race Maps (r:1 w:0)
+	/// Storage: Race PersonalBests (r:1 w:1)
+	/// Storage: Race Leaderboards (r:1 w:1)
+	/// Storage: Rewards Emission (r:1 w:0)
+	/// Storage: Rewards DailyPool (r:1 w:1)
+	/// Storage: Rewards DailyPayouts (r:1 w:1)
+	fn claim_race_reward() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 66_000_000 picoseconds.
+		Weight::from_parts(66_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Race Races (r:1 w:1)
+	/// Storage: Race ActiveRace (r:0 w:1)
+	fn cancel_race() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Race GameServers (r:1 w:1)
+	fn register_server_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Race GameServers (r:2 w:2)
+	fn rotate_server_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Race GameServers (r:1 w:1)
+	fn revoke_server_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Race Maps (r:1 w:0)
+	/// Storage: Race RankedBans (r:1 w:0)
+	/// Storage: Race NextLobbyId (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: Race Lobbies (r:0 w:1)
+	fn create_lobby() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(41_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Race RankedBans (r:1 w:0)
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// The range of component `n` is `[1, 16]`.
+	fn join_lobby(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(38_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(120_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Balances Holds (r:n w:n)
+	/// The range of component `n` is `[1, 16]`.
+	fn cancel_lobby(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Balances Holds (r:n w:n)
+	/// The range of component `n` is `[1, 16]`.
+	fn refund_lobby(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Race GameServers (r:1 w:0)
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Race LobbyResults (r:0 w:1)
+	/// The range of component `n` is `[2, 16]`.
+	fn submit_lobby_result(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 62_000_000 picoseconds.
+		Weight::from_parts(62_000_000, 0)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(400_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Race PayoutCurve (r:0 w:1)
+	fn set_payout_curve() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Balances Holds (r:n w:n)
+	/// The range of component `n` is `[2, 16]`.
+	fn expire_lobby(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Race NextMapId (r:1 w:1)
+	/// Storage: Race Maps (r:0 w:1)
+	fn create_map() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Race Maps (r:1 w:1)
+	fn update_map() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Race Races (r:1 w:1)
+	/// Storage: Race SuspiciousResults (r:1 w:1)
+	/// Storage: Race FlaggedResults (r:0 w:1)
+	fn review_flagged_result() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Race Races (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: Race Disputes (r:0 w:1)
+	fn dispute_result() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Race Races (r:1 w:1)
+	/// Storage: Race Disputes (r:1 w:1)
+	/// Storage: Race Maps (r:1 w:0)
+	/// Storage: Race RaceResults (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn resolve_dispute() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 45_000_000 picoseconds.
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Race LobbyResults (r:1 w:1)
+	/// Storage: Race PayoutCurve (r:1 w:0)
+	/// Storage: Balances Holds (r:n w:n)
+	/// Storage: System Account (r:n w:n)
+	/// Storage: Ranked Ratings (r:n w:n)
+	/// Storage: Ranked Tiers (r:n w:0)
+	/// The range of component `n` is `[2, 16]`.
+	fn settle_lobby(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 40_000_000 picoseconds.
+		Weight::from_parts(40_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Race LobbyResults (r:1 w:0)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: Race LobbyDisputes (r:0 w:1)
+	fn dispute_lobby_result() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Race Lobbies (r:1 w:1)
+	/// Storage: Race LobbyDisputes (r:1 w:1)
+	/// Storage: Race LobbyResults (r:1 w:1)
+	/// Storage: Race PayoutCurve (r:1 w:0)
+	/// Storage: Balances Holds (r:n w:n)
+	/// Storage: System Account (r:n w:n)
+	/// Storage: Ranked Ratings (r:n w:n)
+	/// Storage: Ranked Tiers (r:n w:0)
+	/// The range of component `n` is `[2, 16]`.
+	fn resolve_lobby_dispute(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 70_000_000 picoseconds.
+		Weight::from_parts(70_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn start_race() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn submit_race_result() -> Weight {
+		Weight::from_parts(96_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn claim_race_reward() -> Weight {
+		Weight::from_parts(66_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	fn cancel_race() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn register_server_key() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn rotate_server_key() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn revoke_server_key() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn create_lobby() -> Weight {
+		Weight::from_parts(41_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn join_lobby(n: u32, ) -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(Weight::from_parts(120_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn cancel_lobby(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn refund_lobby(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn submit_lobby_result(n: u32, ) -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(Weight::from_parts(400_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_payout_curve() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn expire_lobby(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn create_map() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn update_map() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn review_flagged_result() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn dispute_result() -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn settle_lobby(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	fn dispute_lobby_result() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn resolve_lobby_dispute(n: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use sp_std::vec::Vec;
+
+/// `n` tiers 100 rating points apart, each paying a reward
+fn tiers<T: Config>(n: u32) -> BoundedVec<TierOf<T>, T::MaxTiers> {
+    BoundedVec::truncate_from(
+        (0..n)
+            .map(|index| Tier {
+                min_rating: index * 100,
+                reward: ((index + 1) * 1_000).into(),
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+benchmarks! {
+    set_tiers {
+        let n in 0 .. T::MaxTiers::get();
+        let origin = T::SeasonOrigin::try_successful_origin().map_err(|_| "no season origin")?;
+        let tiers = tiers::<T>(n);
+    }: _<T::RuntimeOrigin>(origin, tiers)
+    verify {
+        assert_eq!(Tiers::<T>::get().len() as u32, n);
+    }
+
+    claim_season_reward {
+        // Rating of the previous season still open, closed by the claim itself
+        Tiers::<T>::put(tiers::<T>(T::MaxTiers::get()));
+        let caller: T::AccountId = whitelisted_caller();
+        Ratings::<T>::insert(&caller, Rating { rating: u32::MAX, games: 1, season: 0 });
+        frame_system::Pallet::<T>::set_block_number(T::SeasonLength::get());
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(SeasonRewards::<T>::get(&caller, 0).is_none());
+        assert_eq!(Ratings::<T>::get(&caller).unwrap().season, 1);
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,375 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{AtLeast32BitUnsigned, SaturatedConversion, Zero},
+            Percent,
+        },
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_rewards::{RewardAccrual, RewardReason};
+    use sp_std::vec::Vec;
+
+    /// Index of a ranked season, counted from genesis
+    pub type SeasonId = u32;
+
+    pub type BalanceOf<T> = <T as Config>::Balance;
+
+    pub type TierOf<T> = Tier<BalanceOf<T>>;
+
+    /// Expected score, in per mille, of a player rated 0, 50, 100, ... 800 points above their
+    /// opponent
+    const EXPECTED_SCORES: [u32; 17] = [
+        500, 571, 640, 703, 760, 808, 849, 882, 909, 930, 947, 960, 969, 977, 983, 987, 990,
+    ];
+
+    /// Rating difference between two entries of `EXPECTED_SCORES`
+    const EXPECTED_SCORE_STEP: u32 = 50;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to set the rating tiers
+        type SeasonOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Balance of end-of-season rewards
+        type Balance: AtLeast32BitUnsigned + Parameter + Member + MaxEncodedLen + Copy;
+        /// Credits claimed season rewards as pending season rewards
+        type Rewards: RewardAccrual<Self::AccountId, Self::Balance>;
+        /// Blocks a season lasts; season `n` starts at block `n * SeasonLength`
+        #[pallet::constant]
+        type SeasonLength: Get<BlockNumberFor<Self>>;
+        /// Rating of an account's first ranked game
+        #[pallet::constant]
+        type InitialRating: Get<u32>;
+        /// Largest rating change of a single game
+        #[pallet::constant]
+        type KFactor: Get<u32>;
+        /// Share of the distance to the initial rating kept when a new season starts
+        #[pallet::constant]
+        type SoftResetRetention: Get<Percent>;
+        /// Largest number of rating tiers
+        #[pallet::constant]
+        type MaxTiers: Get<u32>;
+    }
+
+    /// Ranked standing of an account
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Rating {
+        pub rating: u32,
+        /// Ranked games played this season
+        pub games: u32,
+        /// Season the rating belongs to
+        pub season: SeasonId,
+    }
+
+    /// Rating tier and the reward for finishing a season in it
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub struct Tier<Balance> {
+        /// Lowest rating of the tier
+        pub min_rating: u32,
+        pub reward: Balance,
+    }
+
+    /// Map from AccountId to Rating
+    #[pallet::storage]
+    pub type Ratings<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Rating>;
+
+    /// Rating tiers sorted by ascending minimum rating
+    #[pallet::storage]
+    pub type Tiers<T: Config> = StorageValue<_, BoundedVec<TierOf<T>, T::MaxTiers>, ValueQuery>;
+
+    /// End-of-season rewards not claimed yet, with the tier they were earned in
+    #[pallet::storage]
+    pub type SeasonRewards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        SeasonId,
+        (u32, BalanceOf<T>),
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Rating changed by a ranked game
+        RatingUpdated {
+            who: T::AccountId,
+            season: SeasonId,
+            rating: u32,
+        },
+        /// Rating of a past season closed and soft reset into the current season
+        SeasonClosed {
+            who: T::AccountId,
+            season: SeasonId,
+            final_rating: u32,
+            new_rating: u32,
+        },
+        /// End-of-season reward credited to the account's pending rewards
+        SeasonRewardPaid {
+            who: T::AccountId,
+            season: SeasonId,
+            tier: u32,
+            amount: BalanceOf<T>,
+        },
+        /// Rating tiers replaced
+        TiersSet {
+            tiers: BoundedVec<TierOf<T>, T::MaxTiers>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Tier minimum ratings are not strictly ascending
+        InvalidTiers,
+        /// No unclaimed reward for the season
+        NothingToClaim,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                !T::SeasonLength::get().is_zero(),
+                "SeasonLength must not be zero"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Replace the rating tiers used for end-of-season rewards
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_tiers(tiers.len() as u32))]
+        pub fn set_tiers(
+            origin: OriginFor<T>,
+            tiers: BoundedVec<TierOf<T>, T::MaxTiers>,
+        ) -> DispatchResult {
+            T::SeasonOrigin::ensure_origin(origin)?;
+            ensure!(
+                tiers
+                    .windows(2)
+                    .all(|pair| pair[0].min_rating < pair[1].min_rating),
+                Error::<T>::InvalidTiers
+            );
+
+            Tiers::<T>::put(&tiers);
+
+            Self::deposit_event(Event::TiersSet { tiers });
+
+            Ok(())
+        }
+
+        /// Claim the reward for the tier reached at the end of `season`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::claim_season_reward())]
+        pub fn claim_season_reward(origin: OriginFor<T>, season: SeasonId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            if let Some(rating) = Ratings::<T>::get(&who) {
+                Ratings::<T>::insert(&who, Self::roll_over(&who, rating));
+            }
+
+            let (tier, amount) =
+                SeasonRewards::<T>::take(&who, season).ok_or(Error::<T>::NothingToClaim)?;
+            T::Rewards::accrue(&who, RewardReason::Season, amount)?;
+
+            Self::deposit_event(Event::SeasonRewardPaid {
+                who,
+                season,
+                tier,
+                amount,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Season of the current block
+        pub fn current_season() -> SeasonId {
+            let now = frame_system::Pallet::<T>::block_number();
+            (now / T::SeasonLength::get()).saturated_into()
+        }
+
+        /// Current rating of `who`, with the soft reset applied if they have not played this
+        /// season
+        pub fn rating_of(who: &T::AccountId) -> u32 {
+            match Ratings::<T>::get(who) {
+                Some(rating) if rating.season < Self::current_season() => {
+                    Self::soft_reset(rating.rating)
+                }
+                Some(rating) => rating.rating,
+                None => T::InitialRating::get(),
+            }
+        }
+
+        /// Index and tier of `rating`, if it reaches the lowest tier
+        pub fn tier_of(rating: u32) -> Option<(u32, TierOf<T>)> {
+            Tiers::<T>::get()
+                .into_iter()
+                .enumerate()
+                .rev()
+                .find(|(_, tier)| tier.min_rating <= rating)
+                .map(|(index, tier)| (index as u32, tier))
+        }
+
+        /// Expected score, in per mille, of a player rated `rating` against `opponent`
+        pub fn expected_score(rating: u32, opponent: u32) -> u32 {
+            let difference = rating.abs_diff(opponent);
+            let step = (difference / EXPECTED_SCORE_STEP) as usize;
+            let score = match (EXPECTED_SCORES.get(step), EXPECTED_SCORES.get(step + 1)) {
+                (Some(low), Some(high)) => {
+                    low + (high - low) * (difference % EXPECTED_SCORE_STEP) / EXPECTED_SCORE_STEP
+                }
+                _ => EXPECTED_SCORES[EXPECTED_SCORES.len() - 1],
+            };
+            if rating >= opponent {
+                score
+            } else {
+                1_000 - score
+            }
+        }
+
+        /// Move `rating` part of the way back to the initial rating
+        fn soft_reset(rating: u32) -> u32 {
+            let initial = T::InitialRating::get();
+            let kept = T::SoftResetRetention::get();
+            if rating >= initial {
+                initial.saturating_add(kept * (rating - initial))
+            } else {
+                initial.saturating_sub(kept * (initial - rating))
+            }
+        }
+
+        /// Close a rating from a past season, recording its tier reward, and soft reset it
+        /// into the current season
+        fn roll_over(who: &T::AccountId, rating: Rating) -> Rating {
+            let season = Self::current_season();
+            if rating.season >= season {
+                return rating;
+            }
+
+            if rating.games > 0 {
+                if let Some((index, tier)) = Self::tier_of(rating.rating) {
+                    if !tier.reward.is_zero() {
+                        SeasonRewards::<T>::insert(who, rating.season, (index, tier.reward));
+                    }
+                }
+            }
+            let new_rating = Self::soft_reset(rating.rating);
+
+            Self::deposit_event(Event::SeasonClosed {
+                who: who.clone(),
+                season: rating.season,
+                final_rating: rating.rating,
+                new_rating,
+            });
+
+            Rating {
+                rating: new_rating,
+                games: 0,
+                season,
+            }
+        }
+
+        /// Update the ratings of a finished ranked game, `placements` ordered from first to
+        /// last. Each pair of players counts as one game won by the better placed one.
+        pub(crate) fn update_ratings(placements: &[T::AccountId]) {
+            let players = placements.len();
+            if players < 2 {
+                return;
+            }
+
+            let initial = T::InitialRating::get();
+            let season = Self::current_season();
+            let ratings = placements
+                .iter()
+                .map(|who| match Ratings::<T>::get(who) {
+                    Some(rating) => Self::roll_over(who, rating),
+                    None => Rating {
+                        rating: initial,
+                        games: 0,
+                        season,
+                    },
+                })
+                .collect::<Vec<_>>();
+
+            let k_factor = T::KFactor::get() as i64;
+            let opponents = (players - 1) as i64;
+            for (index, who) in placements.iter().enumerate() {
+                let own = ratings[index].rating;
+                let surplus = ratings
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != index)
+                    .map(|(other, opponent)| {
+                        let score = if index < other { 1_000 } else { 0 };
+                        score - Self::expected_score(own, opponent.rating) as i64
+                    })
+                    .sum::<i64>();
+                let change = k_factor * surplus / (1_000 * opponents);
+                let rating = (own as i64 + change).clamp(0, u32::MAX as i64) as u32;
+
+                Ratings::<T>::insert(
+                    who,
+                    Rating {
+                        rating,
+                        games: ratings[index].games.saturating_add(1),
+                        season,
+                    },
+                );
+
+                Self::deposit_event(Event::RatingUpdated {
+                    who: who.clone(),
+                    season,
+                    rating,
+                });
+            }
+        }
+    }
+}
+
+/// Hook for game pallets to report the placements of ranked games
+pub trait RankedResultHandler<AccountId> {
+    /// Record a finished ranked game, `placements` ordered from first to last
+    fn record_placements(placements: &[AccountId]);
+}
+
+impl<AccountId> RankedResultHandler<AccountId> for () {
+    fn record_placements(_placements: &[AccountId]) {}
+}
+
+impl<T: Config> RankedResultHandler<T::AccountId> for Pallet<T> {
+    fn record_placements(placements: &[T::AccountId]) {
+        Self::update_ratings(placements);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{mock::*, Error, Event, RankedResultHandler, Rating, Ratings, SeasonRewards, Tier};
+use frame_support::{assert_noop, assert_ok, traits::ConstU32, BoundedVec};
+use pallet_rewards::RewardReason;
+use sp_runtime::DispatchError;
+
+/// Season rewards `who` can claim from the rewards pallet
+fn pending_reward(who: u64) -> u64 {
+    pallet_rewards::Rewards::<Test>::get(who, RewardReason::Season)
+}
+
+fn tiers(tiers: Vec<(u32, u64)>) -> BoundedVec<Tier<u64>, ConstU32<4>> {
+    BoundedVec::try_from(
+        tiers
+            .into_iter()
+            .map(|(min_rating, reward)| Tier { min_rating, reward })
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+fn rating(who: u64) -> Rating {
+    Ratings::<Test>::get(who).unwrap()
+}
+
+#[test]
+fn expected_score_follows_elo_curve() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Ranked::expected_score(1500, 1500), 500);
+        assert_eq!(Ranked::expected_score(1700, 1500), 760);
+        assert_eq!(Ranked::expected_score(1500, 1700), 240);
+        assert_eq!(Ranked::expected_score(1525, 1500), 535);
+        assert_eq!(Ranked::expected_score(3000, 1000), 990);
+    });
+}
+
+#[test]
+fn placements_update_ratings() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        Ranked::record_placements(&[1, 2, 3]);
+
+        assert_eq!(
+            rating(1),
+            Rating {
+                rating: 1516,
+                games: 1,
+                season: 0
+            }
+        );
+        assert_eq!(rating(2).rating, 1500);
+        assert_eq!(rating(3).rating, 1484);
+        System::assert_last_event(
+            Event::RatingUpdated {
+                who: 3,
+                season: 0,
+                rating: 1484,
+            }
+            .into(),
+        );
+
+        // Beating a higher rated player gains more than losing to them costs
+        Ranked::record_placements(&[3, 1]);
+        assert_eq!(rating(3).rating, 1501);
+        assert_eq!(rating(1).rating, 1499);
+        assert_eq!(rating(1).games, 2);
+    });
+}
+
+#[test]
+fn single_player_is_not_rated() {
+    new_test_ext().execute_with(|| {
+        Ranked::record_placements(&[1]);
+        assert!(Ratings::<Test>::get(1).is_none());
+    });
+}
+
+#[test]
+fn set_tiers_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Ranked::set_tiers(RuntimeOrigin::signed(1), tiers(vec![(0, 10)])),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Ranked::set_tiers(RuntimeOrigin::root(), tiers(vec![(1500, 10), (1500, 20)])),
+            Error::<Test>::InvalidTiers
+        );
+
+        let tiers = tiers(vec![(0, 10), (1510, 50)]);
+        assert_ok!(Ranked::set_tiers(RuntimeOrigin::root(), tiers.clone()));
+        System::assert_last_event(Event::TiersSet { tiers }.into());
+        assert_eq!(Ranked::tier_of(1509).unwrap().0, 0);
+        assert_eq!(Ranked::tier_of(1510).unwrap().0, 1);
+    });
+}
+
+#[test]
+fn new_season_soft_resets_ratings() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Ranked::record_placements(&[1, 2, 3]);
+
+        System::set_block_number(100);
+        assert_eq!(Ranked::current_season(), 1);
+        assert_eq!(Ranked::rating_of(&1), 1508);
+        assert_eq!(Ranked::rating_of(&3), 1492);
+
+        Ranked::record_placements(&[3, 1]);
+        System::assert_has_event(
+            Event::SeasonClosed {
+                who: 1,
+                season: 0,
+                final_rating: 1516,
+                new_rating: 1508,
+            }
+            .into(),
+        );
+        assert_eq!(
+            rating(1),
+            Rating {
+                rating: 1492,
+                games: 1,
+                season: 1
+            }
+        );
+        assert_eq!(rating(3).rating, 1508);
+    });
+}
+
+#[test]
+fn claim_season_reward_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Ranked::set_tiers(
+            RuntimeOrigin::root(),
+            tiers(vec![(1490, 10), (1510, 50)])
+        ));
+        Ranked::record_placements(&[1, 2, 3]);
+
+        assert_noop!(
+            Ranked::claim_season_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::NothingToClaim
+        );
+
+        System::set_block_number(100);
+        // Player 1 closes the season when playing again, player 2 when claiming
+        Ranked::record_placements(&[1, 3]);
+        assert_eq!(SeasonRewards::<Test>::get(1, 0), Some((1, 50)));
+        assert_ok!(Ranked::claim_season_reward(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(
+            Event::SeasonRewardPaid {
+                who: 1,
+                season: 0,
+                tier: 1,
+                amount: 50,
+            }
+            .into(),
+        );
+        assert_eq!(pending_reward(1), 50);
+
+        assert_ok!(Ranked::claim_season_reward(RuntimeOrigin::signed(2), 0));
+        assert_eq!(pending_reward(2), 10);
+        assert_eq!(rating(2).season, 1);
+
+        // Below the lowest tier and already claimed seasons pay nothing
+        assert_noop!(
+            Ranked::claim_season_reward(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NothingToClaim
+        );
+        assert_noop!(
+            Ranked::claim_season_reward(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    sp_runtime::{
+        traits::{Bounded, Saturating, Zero},
+        Perbill,
+    },
+    traits::fungible::Inspect,
+};
+use frame_system::RawOrigin;
+
+fn amount<T: Config>() -> BalanceOf<T> {
+    T::RewardToken::minimum_balance().saturating_mul(10u32.into())
+}
+
+/// Schedule that never limits a payout
+fn schedule<T: Config>() -> EmissionScheduleOf<T> {
+    EmissionSchedule {
+        initial_daily_pool: BalanceOf::<T>::max_value(),
+        era_days: 30,
+        era_decay: Perbill::from_percent(10),
+        account_daily_cap: BalanceOf::<T>::max_value(),
+    }
+}
+
+benchmarks! {
+    claim_reward {
+        // Claim above the threshold, so it is vested
+        let caller: T::AccountId = whitelisted_caller();
+        let claim = T::VestingThreshold::get().saturating_add(amount::<T>());
+        Rewards::<T>::insert(&caller, RewardReason::Race, claim);
+    }: _(RawOrigin::Signed(caller.clone()), RewardReason::Race)
+    verify {
+        assert!(Rewards::<T>::get(&caller, RewardReason::Race).is_zero());
+    }
+
+    distribute_reward {
+        // Capped reward and full history, so the oldest record is dropped
+        Emission::<T>::put((schedule::<T>(), frame_system::Pallet::<T>::block_number()));
+        let who: T::AccountId = account("player", 0, 0);
+        for _ in 0..T::MaxHistory::get() {
+            Pallet::<T>::accrue(&who, RewardReason::Quest, amount::<T>())?;
+        }
+        let origin = T::DistributionOrigin::try_successful_origin()
+            .map_err(|_| "no distribution origin")?;
+    }: _<T::RuntimeOrigin>(origin, who.clone(), amount::<T>(), RewardReason::Race)
+    verify {
+        assert_eq!(Rewards::<T>::get(&who, RewardReason::Race), amount::<T>());
+    }
+
+    set_emission_schedule {
+        let origin = T::EmissionOrigin::try_successful_origin().map_err(|_| "no emission origin")?;
+    }: _<T::RuntimeOrigin>(origin, Some(schedule::<T>()))
+    verify {
+        assert_eq!(Emission::<T>::get().map(|(schedule, _)| schedule), Some(schedule::<T>()));
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,487 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{CheckedAdd, SaturatedConversion, Saturating, Zero},
+            Perbill,
+        },
+        traits::{fungible, Currency, VestingSchedule},
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_user_profile::UserStatsHandler;
+
+    pub type BalanceOf<T> = <<T as Config>::RewardToken as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    pub type VestingBalanceOf<T> = <<<T as Config>::Vesting as VestingSchedule<
+        <T as frame_system::Config>::AccountId,
+    >>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    pub type RewardRecordOf<T> = RewardRecord<BalanceOf<T>, BlockNumberFor<T>>;
+
+    pub type EmissionScheduleOf<T> = EmissionSchedule<BalanceOf<T>>;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to distribute rewards
+        type DistributionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to set the emission schedule
+        type EmissionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Token minted when rewards are claimed
+        type RewardToken: fungible::Mutate<Self::AccountId>;
+        /// Receives claimed rewards for the player statistics
+        type UserStats: UserStatsHandler<Self::AccountId>;
+        /// Locks claimed rewards above the vesting threshold; must lock `RewardToken`
+        type Vesting: VestingSchedule<Self::AccountId, Moment = BlockNumberFor<Self>>;
+        /// Claims above this amount are vested instead of paid out liquid
+        #[pallet::constant]
+        type VestingThreshold: Get<BalanceOf<Self>>;
+        /// Blocks over which a vested claim is released
+        #[pallet::constant]
+        type VestingDuration: Get<BlockNumberFor<Self>>;
+        /// Number of distributed rewards kept in each account's history
+        #[pallet::constant]
+        type MaxHistory: Get<u32>;
+        /// Blocks in a day of the emission schedule
+        #[pallet::constant]
+        type BlocksPerDay: Get<BlockNumberFor<Self>>;
+    }
+
+    /// Activity a reward was earned for
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum RewardReason {
+        Race,
+        Quest,
+        Tournament,
+        Season,
+        CheckIn,
+    }
+
+    impl RewardReason {
+        /// Whether rewards for this reason are limited by the emission schedule
+        pub fn draws_from_pool(&self) -> bool {
+            matches!(
+                self,
+                RewardReason::Race | RewardReason::Quest | RewardReason::CheckIn
+            )
+        }
+    }
+
+    /// Reasons for freezing reward tokens
+    #[pallet::composite_enum]
+    pub enum FreezeReason {
+        /// Claimed rewards released by a vesting schedule
+        Vesting,
+    }
+
+    /// Reward distributed to an account
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RewardRecord<Balance, BlockNumber> {
+        pub reason: RewardReason,
+        pub amount: Balance,
+        /// Block the reward was distributed at
+        pub block: BlockNumber,
+    }
+
+    /// Emission curve limiting the race, quest and check-in rewards paid out each day
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub struct EmissionSchedule<Balance> {
+        /// Daily pool of the first era
+        pub initial_daily_pool: Balance,
+        /// Days in an era
+        pub era_days: u32,
+        /// Reduction of the daily pool from one era to the next
+        pub era_decay: Perbill,
+        /// Largest amount a single account can receive per day
+        pub account_daily_cap: Balance,
+    }
+
+    /// Unclaimed rewards of each account by reason
+    #[pallet::storage]
+    pub type Rewards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        RewardReason,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Latest rewards distributed to each account, oldest first
+    #[pallet::storage]
+    pub type RewardHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<RewardRecordOf<T>, T::MaxHistory>,
+        ValueQuery,
+    >;
+
+    /// Emission schedule and the block it was set at, which starts its first era; rewards are
+    /// uncapped while unset
+    #[pallet::storage]
+    pub type Emission<T: Config> = StorageValue<_, (EmissionScheduleOf<T>, BlockNumberFor<T>)>;
+
+    /// Day of the current daily pool and the amount left in it
+    #[pallet::storage]
+    pub type DailyPool<T: Config> = StorageValue<_, (u32, BalanceOf<T>)>;
+
+    /// Day of each account's latest payout and the amount paid to it that day
+    #[pallet::storage]
+    pub type DailyPayouts<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (u32, BalanceOf<T>)>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Reward added to an account's pending rewards
+        RewardDistributed {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            reason: RewardReason,
+        },
+        /// Pending rewards claimed
+        RewardClaimed {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            reason: RewardReason,
+        },
+        /// Claimed rewards locked in a vesting schedule releasing `per_block`
+        RewardVested {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            per_block: BalanceOf<T>,
+        },
+        /// Emission schedule replaced or removed
+        EmissionScheduleSet {
+            schedule: Option<EmissionScheduleOf<T>>,
+        },
+        /// Daily pool ran out; further payouts wait for the next day
+        DailyPoolExhausted { day: u32 },
+        /// Account reached its daily payout cap
+        DailyCapReached { account: T::AccountId, day: u32 },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Reward amount is zero
+        ZeroReward,
+        /// No pending reward for the reason
+        NothingToClaim,
+        /// Pending rewards would overflow
+        Overflow,
+        /// Emission eras must last at least one day
+        InvalidSchedule,
+        /// Today's pool or the account's daily cap cannot cover the reward
+        EmissionExhausted,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                !T::BlocksPerDay::get().is_zero(),
+                "BlocksPerDay must not be zero"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Claim all pending rewards earned for `reason`
+        #[pallet::call_index(0)]
+        #[pallet::weight(
+            T::WeightInfo::claim_reward().saturating_add(T::UserStats::record_weight())
+        )]
+        pub fn claim_reward(origin: OriginFor<T>, reason: RewardReason) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let amount = Rewards::<T>::take(&who, reason);
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+            T::RewardToken::mint_into(&who, amount)?;
+            if amount > T::VestingThreshold::get() {
+                Self::vest(&who, amount)?;
+            }
+            T::UserStats::record_reward(&who, amount.saturated_into::<u128>());
+
+            Self::deposit_event(Event::RewardClaimed {
+                account: who,
+                amount,
+                reason,
+            });
+
+            Ok(())
+        }
+
+        /// Add `amount` to the pending rewards of `account`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::distribute_reward())]
+        pub fn distribute_reward(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            reason: RewardReason,
+        ) -> DispatchResult {
+            T::DistributionOrigin::ensure_origin(origin)?;
+            Self::accrue(&account, reason, amount)
+        }
+
+        /// Replace the emission schedule, or lift all caps with `None`
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::set_emission_schedule())]
+        pub fn set_emission_schedule(
+            origin: OriginFor<T>,
+            schedule: Option<EmissionScheduleOf<T>>,
+        ) -> DispatchResult {
+            T::EmissionOrigin::ensure_origin(origin)?;
+            if let Some(schedule) = &schedule {
+                ensure!(schedule.era_days > 0, Error::<T>::InvalidSchedule);
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Emission::<T>::set(schedule.clone().map(|schedule| (schedule, now)));
+            // Today's pool is recomputed from the new schedule on the next payout
+            DailyPool::<T>::kill();
+
+            Self::deposit_event(Event::EmissionScheduleSet { schedule });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Add `amount` to the pending rewards of `who` and to their history. Rewards that draw
+        /// from the daily pool fail with `EmissionExhausted` unless it covers all of `amount`.
+        pub fn accrue(
+            who: &T::AccountId,
+            reason: RewardReason,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            ensure!(!amount.is_zero(), Error::<T>::ZeroReward);
+            if reason.draws_from_pool() {
+                Self::draw(who, amount, false)?;
+            }
+            Self::credit(who, reason, amount)
+        }
+
+        /// Add as much of `amount` as the daily pool allows to the pending rewards of `who`,
+        /// and return the amount credited
+        pub fn accrue_up_to(
+            who: &T::AccountId,
+            reason: RewardReason,
+            amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            ensure!(!amount.is_zero(), Error::<T>::ZeroReward);
+            let granted = if reason.draws_from_pool() {
+                Self::draw(who, amount, true)?
+            } else {
+                amount
+            };
+            if !granted.is_zero() {
+                Self::credit(who, reason, granted)?;
+            }
+            Ok(granted)
+        }
+
+        /// Add `amount` to the pending rewards of `who` and to their history
+        fn credit(
+            who: &T::AccountId,
+            reason: RewardReason,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Rewards::<T>::try_mutate(who, reason, |pending| -> DispatchResult {
+                *pending = pending.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+                Ok(())
+            })?;
+
+            RewardHistory::<T>::mutate(who, |history| {
+                if history.is_full() && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(RewardRecord {
+                    reason,
+                    amount,
+                    block: frame_system::Pallet::<T>::block_number(),
+                });
+            });
+
+            Self::deposit_event(Event::RewardDistributed {
+                account: who.clone(),
+                amount,
+                reason,
+            });
+
+            Ok(())
+        }
+
+        /// Lock the just-minted `amount` of `who` in a schedule releasing it linearly over
+        /// `VestingDuration` blocks
+        fn vest(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let locked = amount.saturated_into::<u128>();
+            let duration = T::VestingDuration::get().saturated_into::<u128>().max(1);
+            let per_block = (locked / duration).max(1);
+            T::Vesting::add_vesting_schedule(
+                who,
+                locked.saturated_into::<VestingBalanceOf<T>>(),
+                per_block.saturated_into::<VestingBalanceOf<T>>(),
+                frame_system::Pallet::<T>::block_number(),
+            )?;
+
+            Self::deposit_event(Event::RewardVested {
+                account: who.clone(),
+                amount,
+                per_block: per_block.saturated_into(),
+            });
+
+            Ok(())
+        }
+
+        /// Day of the emission schedule `block` falls in
+        pub fn day_of(block: BlockNumberFor<T>) -> u32 {
+            (block / T::BlocksPerDay::get()).saturated_into()
+        }
+
+        /// Day of the emission schedule at the current block
+        pub fn current_day() -> u32 {
+            Self::day_of(frame_system::Pallet::<T>::block_number())
+        }
+
+        /// Daily pool of `day` under `schedule` set at block `start`
+        pub fn daily_pool(
+            schedule: &EmissionScheduleOf<T>,
+            start: BlockNumberFor<T>,
+            day: u32,
+        ) -> BalanceOf<T> {
+            let elapsed = day.saturating_sub(Self::day_of(start));
+            let era = elapsed / schedule.era_days.max(1);
+            let remaining = Perbill::one()
+                .saturating_sub(schedule.era_decay)
+                .saturating_pow(era as usize);
+            remaining * schedule.initial_daily_pool
+        }
+
+        /// Take `amount` for `who` from today's pool, within their daily cap, and return the
+        /// amount granted. Unless `partial`, anything short of `amount` is an error.
+        fn draw(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+            partial: bool,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let Some((schedule, start)) = Emission::<T>::get() else {
+                return Ok(amount);
+            };
+            let day = Self::current_day();
+
+            let pool = match DailyPool::<T>::get() {
+                Some((pool_day, remaining)) if pool_day == day => remaining,
+                _ => Self::daily_pool(&schedule, start, day),
+            };
+            let paid = match DailyPayouts::<T>::get(who) {
+                Some((paid_day, paid)) if paid_day == day => paid,
+                _ => Zero::zero(),
+            };
+            let room = schedule.account_daily_cap.saturating_sub(paid);
+            let granted = amount.min(pool).min(room);
+            ensure!(partial || granted == amount, Error::<T>::EmissionExhausted);
+            if granted.is_zero() {
+                return Ok(granted);
+            }
+
+            let remaining = pool.saturating_sub(granted);
+            DailyPool::<T>::put((day, remaining));
+            DailyPayouts::<T>::insert(who, (day, paid.saturating_add(granted)));
+
+            if remaining.is_zero() {
+                Self::deposit_event(Event::DailyPoolExhausted { day });
+            }
+            if granted == room {
+                Self::deposit_event(Event::DailyCapReached {
+                    account: who.clone(),
+                    day,
+                });
+            }
+
+            Ok(granted)
+        }
+    }
+}
+
+/// Hook for other game pallets to credit claimable rewards
+pub trait RewardAccrual<AccountId, Balance> {
+    /// Add `amount` to the pending rewards `who` earned for `reason`, failing if the emission
+    /// schedule cannot cover all of it
+    fn accrue(
+        who: &AccountId,
+        reason: RewardReason,
+        amount: Balance,
+    ) -> frame_support::dispatch::DispatchResult;
+
+    /// Add as much of `amount` as the emission schedule allows today, and return the amount
+    /// credited
+    fn accrue_up_to(
+        who: &AccountId,
+        reason: RewardReason,
+        amount: Balance,
+    ) -> Result<Balance, frame_support::sp_runtime::DispatchError>;
+}
+
+impl<T: Config> RewardAccrual<T::AccountId, BalanceOf<T>> for Pallet<T> {
+    fn accrue(
+        who: &T::AccountId,
+        reason: RewardReason,
+        amount: BalanceOf<T>,
+    ) -> frame_support::dispatch::DispatchResult {
+        Pallet::<T>::accrue(who, reason, amount)
+    }
+
+    fn accrue_up_to(
+        who: &T::AccountId,
+        reason: RewardReason,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, frame_support::sp_runtime::DispatchError> {
+        Pallet::<T>::accrue_up_to(who, reason, amount)
+    }
+}
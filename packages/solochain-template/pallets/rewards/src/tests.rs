@@ -0,0 +1,324 @@
+use crate::{
+    mock::*, DailyPool, Emission, EmissionSchedule, Error, Event, RewardHistory, RewardReason,
+    RewardRecord,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::Inspect, tokens::Preservation, VestingSchedule},
+};
+use pallet_user_profile::UserStatsMap;
+use sp_runtime::{DispatchError, Perbill};
+
+fn pending(who: u64, reason: RewardReason) -> u64 {
+    crate::Rewards::<Test>::get(who, reason)
+}
+
+/// Daily pool of 100 halving every two days, at most 60 per account and day
+fn schedule() -> EmissionSchedule<u64> {
+    EmissionSchedule {
+        initial_daily_pool: 100,
+        era_days: 2,
+        era_decay: Perbill::from_percent(50),
+        account_daily_cap: 60,
+    }
+}
+
+fn distribute(who: u64, amount: u64, reason: RewardReason) {
+    assert_ok!(Rewards::distribute_reward(
+        RuntimeOrigin::root(),
+        who,
+        amount,
+        reason
+    ));
+}
+
+#[test]
+fn distribute_reward_accrues_pending_rewards() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Rewards::distribute_reward(RuntimeOrigin::signed(1), 1, 10, RewardReason::Race),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Rewards::distribute_reward(RuntimeOrigin::root(), 1, 0, RewardReason::Race),
+            Error::<Test>::ZeroReward
+        );
+
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            1,
+            10,
+            RewardReason::Race
+        ));
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            1,
+            5,
+            RewardReason::Race
+        ));
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            1,
+            7,
+            RewardReason::Quest
+        ));
+        System::assert_last_event(
+            Event::RewardDistributed {
+                account: 1,
+                amount: 7,
+                reason: RewardReason::Quest,
+            }
+            .into(),
+        );
+
+        assert_eq!(pending(1, RewardReason::Race), 15);
+        assert_eq!(pending(1, RewardReason::Quest), 7);
+        assert_eq!(pending(1, RewardReason::Tournament), 0);
+        assert_eq!(RewardHistory::<Test>::get(1).len(), 3);
+    });
+}
+
+#[test]
+fn history_drops_oldest_records() {
+    new_test_ext().execute_with(|| {
+        for block in 1..=4u64 {
+            System::set_block_number(block);
+            assert_ok!(Rewards::distribute_reward(
+                RuntimeOrigin::root(),
+                1,
+                block,
+                RewardReason::Tournament
+            ));
+        }
+
+        let amounts = RewardHistory::<Test>::get(1)
+            .into_iter()
+            .map(|record| record.amount)
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, vec![2, 3, 4]);
+        assert_eq!(
+            RewardHistory::<Test>::get(1)[2],
+            RewardRecord {
+                reason: RewardReason::Tournament,
+                amount: 4,
+                block: 4
+            }
+        );
+    });
+}
+
+#[test]
+fn claim_reward_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            1,
+            30,
+            RewardReason::Race
+        ));
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            1,
+            5,
+            RewardReason::Quest
+        ));
+
+        assert_noop!(
+            Rewards::claim_reward(RuntimeOrigin::signed(1), RewardReason::Tournament),
+            Error::<Test>::NothingToClaim
+        );
+
+        assert_ok!(Rewards::claim_reward(
+            RuntimeOrigin::signed(1),
+            RewardReason::Race
+        ));
+        System::assert_last_event(
+            Event::RewardClaimed {
+                account: 1,
+                amount: 30,
+                reason: RewardReason::Race,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(1), 130);
+        assert_eq!(pending(1, RewardReason::Race), 0);
+        assert_eq!(pending(1, RewardReason::Quest), 5);
+        assert_eq!(UserStatsMap::<Test>::get(1).unwrap().total_rewards, 30);
+
+        assert_noop!(
+            Rewards::claim_reward(RuntimeOrigin::signed(1), RewardReason::Race),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn large_claims_are_vested() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            1,
+            500,
+            RewardReason::Tournament
+        ));
+        assert_ok!(Rewards::distribute_reward(
+            RuntimeOrigin::root(),
+            2,
+            300,
+            RewardReason::Tournament
+        ));
+
+        assert_ok!(Rewards::claim_reward(
+            RuntimeOrigin::signed(1),
+            RewardReason::Tournament
+        ));
+        System::assert_has_event(
+            Event::RewardVested {
+                account: 1,
+                amount: 500,
+                per_block: 10,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(1), 600);
+        assert_eq!(Vesting::vesting_balance(&1), Some(500));
+        assert_eq!(
+            Balances::reducible_balance(&1, Preservation::Expendable, Default::default()),
+            100
+        );
+
+        // Claims up to the threshold are paid out liquid
+        assert_ok!(Rewards::claim_reward(
+            RuntimeOrigin::signed(2),
+            RewardReason::Tournament
+        ));
+        assert_eq!(Vesting::vesting_balance(&2), None);
+
+        // Released after the vesting duration
+        System::set_block_number(51);
+        assert_eq!(Vesting::vesting_balance(&1), Some(0));
+    });
+}
+
+#[test]
+fn set_emission_schedule_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Rewards::set_emission_schedule(RuntimeOrigin::signed(1), Some(schedule())),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Rewards::set_emission_schedule(
+                RuntimeOrigin::root(),
+                Some(EmissionSchedule {
+                    era_days: 0,
+                    ..schedule()
+                })
+            ),
+            Error::<Test>::InvalidSchedule
+        );
+
+        assert_ok!(Rewards::set_emission_schedule(
+            RuntimeOrigin::root(),
+            Some(schedule())
+        ));
+        System::assert_last_event(
+            Event::EmissionScheduleSet {
+                schedule: Some(schedule()),
+            }
+            .into(),
+        );
+        assert_eq!(Emission::<Test>::get(), Some((schedule(), 1)));
+
+        assert_eq!(Rewards::daily_pool(&schedule(), 1, 1), 100);
+        assert_eq!(Rewards::daily_pool(&schedule(), 1, 2), 50);
+        assert_eq!(Rewards::daily_pool(&schedule(), 1, 5), 25);
+        // Eras count from the day the schedule was set
+        assert_eq!(Rewards::daily_pool(&schedule(), 30, 4), 100);
+        assert_eq!(Rewards::daily_pool(&schedule(), 30, 5), 50);
+    });
+}
+
+#[test]
+fn payouts_are_capped_per_account_and_day() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Rewards::set_emission_schedule(
+            RuntimeOrigin::root(),
+            Some(schedule())
+        ));
+
+        distribute(1, 50, RewardReason::Race);
+        assert_noop!(
+            Rewards::distribute_reward(RuntimeOrigin::root(), 1, 50, RewardReason::Quest),
+            Error::<Test>::EmissionExhausted
+        );
+        distribute(1, 10, RewardReason::Quest);
+        System::assert_has_event(Event::DailyCapReached { account: 1, day: 0 }.into());
+        assert_eq!(pending(1, RewardReason::Quest), 10);
+
+        // Capped account gets nothing more today; tournament prizes are not capped
+        assert_noop!(
+            Rewards::distribute_reward(RuntimeOrigin::root(), 1, 5, RewardReason::Race),
+            Error::<Test>::EmissionExhausted
+        );
+        assert_eq!(Rewards::accrue_up_to(&1, RewardReason::Race, 5), Ok(0));
+        distribute(1, 500, RewardReason::Tournament);
+        assert_eq!(pending(1, RewardReason::Tournament), 500);
+        assert_eq!(DailyPool::<Test>::get(), Some((0, 40)));
+    });
+}
+
+#[test]
+fn exhausted_pool_refills_next_day_with_decay() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Rewards::set_emission_schedule(
+            RuntimeOrigin::root(),
+            Some(schedule())
+        ));
+
+        distribute(1, 60, RewardReason::Race);
+        assert_eq!(Rewards::accrue_up_to(&2, RewardReason::Race, 60), Ok(40));
+        System::assert_has_event(Event::DailyPoolExhausted { day: 0 }.into());
+        System::assert_has_event(
+            Event::RewardDistributed {
+                account: 2,
+                amount: 40,
+                reason: RewardReason::Race,
+            }
+            .into(),
+        );
+        assert_eq!(pending(2, RewardReason::Race), 40);
+
+        // Day 2 starts the second era with half the pool
+        System::set_block_number(20);
+        assert_noop!(
+            Rewards::distribute_reward(RuntimeOrigin::root(), 2, 60, RewardReason::Race),
+            Error::<Test>::EmissionExhausted
+        );
+        distribute(2, 50, RewardReason::Race);
+        assert_eq!(pending(2, RewardReason::Race), 90);
+        assert_eq!(DailyPool::<Test>::get(), Some((2, 0)));
+
+        // A new schedule starts over with the full pool
+        System::set_block_number(30);
+        assert_ok!(Rewards::set_emission_schedule(
+            RuntimeOrigin::root(),
+            Some(schedule())
+        ));
+        distribute(2, 60, RewardReason::Race);
+        assert_eq!(DailyPool::<Test>::get(), Some((3, 40)));
+
+        // Without a schedule rewards are uncapped again
+        assert_ok!(Rewards::set_emission_schedule(RuntimeOrigin::root(), None));
+        distribute(2, 500, RewardReason::Race);
+        assert_eq!(pending(2, RewardReason::Race), 650);
+    });
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    sp_runtime::traits::Saturating,
+    traits::fungible::{Inspect, Mutate},
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_character::{CharacterInspect, CharacterStats};
+
+const SEED: u32 = 0;
+
+fn effect<T: Config>() -> SkillEffect<BlockNumberFor<T>> {
+    SkillEffect {
+        boost: CharacterStats {
+            speed: 1,
+            acceleration: 1,
+            stamina: 1,
+        },
+        cooldown: 10u32.into(),
+        map_condition: None,
+    }
+}
+
+fn insert_skill<T: Config>(owner: &T::AccountId) -> SkillId {
+    let skill_id = NextSkillId::<T>::get();
+    NextSkillId::<T>::put(skill_id + 1);
+    Skills::<T>::insert(
+        skill_id,
+        SkillBook {
+            owner: owner.clone(),
+            kind: 0,
+            level: 1,
+            effect: effect::<T>(),
+            attached_to: None,
+        },
+    );
+    skill_id
+}
+
+benchmarks! {
+    mint_skill {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let origin = T::MintOrigin::try_successful_origin().map_err(|_| "no mint origin")?;
+    }: _<T::RuntimeOrigin>(origin, owner.clone(), 0, effect::<T>())
+    verify {
+        assert_eq!(Skills::<T>::get(0).unwrap().owner, owner);
+    }
+
+    transfer_skill {
+        let caller: T::AccountId = whitelisted_caller();
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let skill_id = insert_skill::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), skill_id, dest.clone())
+    verify {
+        assert_eq!(Skills::<T>::get(skill_id).unwrap().owner, dest);
+    }
+
+    attach_skill {
+        let caller: T::AccountId = whitelisted_caller();
+        let character_id = T::Characters::create_for_benchmark(&caller);
+        let skill_id = insert_skill::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), skill_id, character_id)
+    verify {
+        assert_eq!(Skills::<T>::get(skill_id).unwrap().attached_to, Some(character_id));
+    }
+
+    detach_skill {
+        let caller: T::AccountId = whitelisted_caller();
+        let character_id = 0;
+        let skill_id = insert_skill::<T>(&caller);
+        Skills::<T>::mutate(skill_id, |skill| {
+            skill.as_mut().unwrap().attached_to = Some(character_id);
+        });
+        CharacterSkills::<T>::mutate(character_id, |skills| {
+            skills.try_push(skill_id).unwrap();
+        });
+    }: _(RawOrigin::Signed(caller), skill_id)
+    verify {
+        assert!(CharacterSkills::<T>::get(character_id).is_empty());
+    }
+
+    fuse_skills {
+        let caller: T::AccountId = whitelisted_caller();
+        T::RewardToken::set_balance(
+            &caller,
+            T::FusionCost::get().saturating_add(T::RewardToken::minimum_balance()),
+        );
+        let mut inputs = BoundedVec::<SkillId, T::SkillsPerFusion>::new();
+        for _ in 0..T::SkillsPerFusion::get() {
+            inputs.try_push(insert_skill::<T>(&caller)).map_err(|_| "too many inputs")?;
+        }
+        let output = NextSkillId::<T>::get();
+    }: _(RawOrigin::Signed(caller), inputs)
+    verify {
+        assert_eq!(Skills::<T>::get(output).unwrap().level, 2);
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
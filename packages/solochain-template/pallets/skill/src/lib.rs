@@ -0,0 +1,473 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+use pallet_character::{CharacterAttachments, CharacterId};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+pub mod runtime_api;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use core::cmp::Reverse;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::Saturating,
+        traits::{
+            fungible,
+            tokens::{Fortitude, Precision, Preservation},
+        },
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_character::{CharacterId, CharacterInspect, CharacterStats};
+
+    /// Identifier of a skill book NFT
+    pub type SkillId = u64;
+
+    pub type BalanceOf<T> = <<T as Config>::RewardToken as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to mint new skill books
+        type MintOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Characters that skills attach to
+        type Characters: CharacterInspect<Self::AccountId>;
+        /// Maximum number of skills attached to a character at once
+        #[pallet::constant]
+        type MaxSkillSlots: Get<u32>;
+        /// Blocks a character must wait between two skill attach/detach operations
+        #[pallet::constant]
+        type SwapCooldown: Get<BlockNumberFor<Self>>;
+        /// Reward token burned to pay for skill fusion
+        type RewardToken: fungible::Mutate<Self::AccountId>;
+        /// Number of identical skill books consumed by one fusion
+        #[pallet::constant]
+        type SkillsPerFusion: Get<u32>;
+        /// Highest level a skill can be fused up to
+        #[pallet::constant]
+        type MaxSkillLevel: Get<u8>;
+        /// Percentage applied to the stat boost of a skill on each fusion
+        #[pallet::constant]
+        type FusionBoostPercent: Get<u32>;
+        /// Reward tokens burned per fusion
+        #[pallet::constant]
+        type FusionCost: Get<BalanceOf<Self>>;
+    }
+
+    /// Effect granted by a skill while it is attached
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub struct SkillEffect<BlockNumber> {
+        /// Stats added on top of the character's base stats
+        pub boost: CharacterStats,
+        /// Blocks before the skill can be triggered again in a race
+        pub cooldown: BlockNumber,
+        /// Map the boost applies on; applies on every map when `None`
+        pub map_condition: Option<u32>,
+    }
+
+    /// Skill book NFT
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct SkillBook<AccountId, BlockNumber> {
+        pub owner: AccountId,
+        /// Kind of skill; books of the same kind share the same base effect
+        pub kind: u32,
+        pub level: u8,
+        pub effect: SkillEffect<BlockNumber>,
+        /// Character this skill is currently attached to, if any
+        pub attached_to: Option<CharacterId>,
+    }
+
+    /// Map from SkillId to SkillBook
+    #[pallet::storage]
+    pub type Skills<T: Config> =
+        StorageMap<_, Blake2_128Concat, SkillId, SkillBook<T::AccountId, BlockNumberFor<T>>>;
+
+    /// Next SkillId to assign
+    #[pallet::storage]
+    pub type NextSkillId<T> = StorageValue<_, SkillId, ValueQuery>;
+
+    /// Skills attached to each character
+    #[pallet::storage]
+    pub type CharacterSkills<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        CharacterId,
+        BoundedVec<SkillId, T::MaxSkillSlots>,
+        ValueQuery,
+    >;
+
+    /// Block of the last skill attach/detach on each character
+    #[pallet::storage]
+    pub type LastSkillChange<T: Config> =
+        StorageMap<_, Blake2_128Concat, CharacterId, BlockNumberFor<T>>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// New skill book minted
+        SkillMinted {
+            skill_id: SkillId,
+            owner: T::AccountId,
+            kind: u32,
+        },
+        /// Skill book transferred to another account
+        SkillTransferred {
+            skill_id: SkillId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// Skill attached to a character
+        SkillAttached {
+            skill_id: SkillId,
+            character_id: CharacterId,
+        },
+        /// Skill detached from a character
+        SkillDetached {
+            skill_id: SkillId,
+            character_id: CharacterId,
+        },
+        /// Identical skill books burned into a higher level one
+        SkillsFused {
+            owner: T::AccountId,
+            inputs: BoundedVec<SkillId, T::SkillsPerFusion>,
+            output: SkillId,
+            kind: u32,
+            level: u8,
+            cost: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Skill does not exist
+        SkillNotFound,
+        /// Caller does not own the skill
+        NotSkillOwner,
+        /// Skill is attached to a character and cannot be used
+        SkillAttached,
+        /// Skill is not attached to any character
+        SkillNotAttached,
+        /// Character does not exist
+        CharacterNotFound,
+        /// Caller does not own the character
+        NotCharacterOwner,
+        /// All skill slots of the character are taken
+        NoFreeSlot,
+        /// Character changed skills too recently
+        SwapCooldown,
+        /// No more ids available
+        Overflow,
+        /// Fusion needs exactly `SkillsPerFusion` skill books
+        WrongFusionInputCount,
+        /// Fusion inputs are not copies of the same skill at the same level
+        SkillMismatch,
+        /// The same skill book was given more than once
+        DuplicateSkill,
+        /// Skill is already at the maximum level
+        MaxLevelReached,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Mint a new level 1 skill book to `owner`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::mint_skill())]
+        pub fn mint_skill(
+            origin: OriginFor<T>,
+            owner: T::AccountId,
+            kind: u32,
+            effect: SkillEffect<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::MintOrigin::ensure_origin(origin)?;
+
+            let skill_id = NextSkillId::<T>::get();
+            let next = skill_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            NextSkillId::<T>::put(next);
+
+            Skills::<T>::insert(
+                skill_id,
+                SkillBook {
+                    owner: owner.clone(),
+                    kind,
+                    level: 1,
+                    effect,
+                    attached_to: None,
+                },
+            );
+
+            Self::deposit_event(Event::SkillMinted {
+                skill_id,
+                owner,
+                kind,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer an owned, unattached skill book to `dest`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::transfer_skill())]
+        pub fn transfer_skill(
+            origin: OriginFor<T>,
+            skill_id: SkillId,
+            dest: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Skills::<T>::try_mutate(skill_id, |maybe_skill| -> DispatchResult {
+                let skill = maybe_skill.as_mut().ok_or(Error::<T>::SkillNotFound)?;
+                ensure!(skill.owner == who, Error::<T>::NotSkillOwner);
+                ensure!(skill.attached_to.is_none(), Error::<T>::SkillAttached);
+                skill.owner = dest.clone();
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::SkillTransferred {
+                skill_id,
+                from: who,
+                to: dest,
+            });
+
+            Ok(())
+        }
+
+        /// Attach an owned skill to an owned character with a free skill slot
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::attach_skill())]
+        pub fn attach_skill(
+            origin: OriginFor<T>,
+            skill_id: SkillId,
+            character_id: CharacterId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut skill = Skills::<T>::get(skill_id).ok_or(Error::<T>::SkillNotFound)?;
+            ensure!(skill.owner == who, Error::<T>::NotSkillOwner);
+            ensure!(skill.attached_to.is_none(), Error::<T>::SkillAttached);
+            let character_owner =
+                T::Characters::owner_of(character_id).ok_or(Error::<T>::CharacterNotFound)?;
+            ensure!(character_owner == who, Error::<T>::NotCharacterOwner);
+            Self::ensure_cooldown_passed(character_id)?;
+
+            CharacterSkills::<T>::try_mutate(character_id, |skills| {
+                skills
+                    .try_push(skill_id)
+                    .map_err(|_| Error::<T>::NoFreeSlot)
+            })?;
+            skill.attached_to = Some(character_id);
+            Skills::<T>::insert(skill_id, skill);
+            LastSkillChange::<T>::insert(character_id, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::SkillAttached {
+                skill_id,
+                character_id,
+            });
+
+            Ok(())
+        }
+
+        /// Detach an owned skill from the character it is attached to
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::detach_skill())]
+        pub fn detach_skill(origin: OriginFor<T>, skill_id: SkillId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut skill = Skills::<T>::get(skill_id).ok_or(Error::<T>::SkillNotFound)?;
+            ensure!(skill.owner == who, Error::<T>::NotSkillOwner);
+            let character_id = skill.attached_to.ok_or(Error::<T>::SkillNotAttached)?;
+            Self::ensure_cooldown_passed(character_id)?;
+
+            CharacterSkills::<T>::mutate(character_id, |skills| {
+                skills.retain(|id| *id != skill_id);
+            });
+            skill.attached_to = None;
+            Skills::<T>::insert(skill_id, skill);
+            LastSkillChange::<T>::insert(character_id, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::SkillDetached {
+                skill_id,
+                character_id,
+            });
+
+            Ok(())
+        }
+
+        /// Burn `SkillsPerFusion` owned, unattached copies of the same skill at the same level
+        /// and mint one book of the next level with a scaled stat boost. The new book scales the
+        /// effect of the strongest input: the one with the largest total boost, then the
+        /// shortest cooldown, then the first one given.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::fuse_skills())]
+        pub fn fuse_skills(
+            origin: OriginFor<T>,
+            inputs: BoundedVec<SkillId, T::SkillsPerFusion>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                inputs.len() as u32 == T::SkillsPerFusion::get(),
+                Error::<T>::WrongFusionInputCount
+            );
+
+            let mut template: Option<SkillBook<T::AccountId, BlockNumberFor<T>>> = None;
+            for (index, skill_id) in inputs.iter().enumerate() {
+                ensure!(
+                    !inputs[..index].contains(skill_id),
+                    Error::<T>::DuplicateSkill
+                );
+                let skill = Skills::<T>::get(skill_id).ok_or(Error::<T>::SkillNotFound)?;
+                ensure!(skill.owner == who, Error::<T>::NotSkillOwner);
+                ensure!(skill.attached_to.is_none(), Error::<T>::SkillAttached);
+                match &mut template {
+                    Some(strongest) => {
+                        ensure!(
+                            strongest.kind == skill.kind && strongest.level == skill.level,
+                            Error::<T>::SkillMismatch
+                        );
+                        if Self::strength(&skill.effect) > Self::strength(&strongest.effect) {
+                            *strongest = skill;
+                        }
+                    }
+                    None => template = Some(skill),
+                }
+            }
+            let template = template.ok_or(Error::<T>::WrongFusionInputCount)?;
+            ensure!(
+                template.level < T::MaxSkillLevel::get(),
+                Error::<T>::MaxLevelReached
+            );
+
+            let output = NextSkillId::<T>::get();
+            let next = output.checked_add(1).ok_or(Error::<T>::Overflow)?;
+
+            let cost = T::FusionCost::get();
+            T::RewardToken::burn_from(
+                &who,
+                cost,
+                Preservation::Expendable,
+                Precision::Exact,
+                Fortitude::Polite,
+            )?;
+
+            for skill_id in inputs.iter() {
+                Skills::<T>::remove(skill_id);
+            }
+            let level = template.level.saturating_add(1);
+            let effect = Self::fused_effect(&template.effect);
+            NextSkillId::<T>::put(next);
+            Skills::<T>::insert(
+                output,
+                SkillBook {
+                    owner: who.clone(),
+                    kind: template.kind,
+                    level,
+                    effect,
+                    attached_to: None,
+                },
+            );
+
+            Self::deposit_event(Event::SkillsFused {
+                owner: who,
+                inputs,
+                output,
+                kind: template.kind,
+                level,
+                cost,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Ensure `character_id` did not change skills within the swap cooldown
+        fn ensure_cooldown_passed(character_id: CharacterId) -> DispatchResult {
+            if let Some(last) = LastSkillChange::<T>::get(character_id) {
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(
+                    now >= last.saturating_add(T::SwapCooldown::get()),
+                    Error::<T>::SwapCooldown
+                );
+            }
+            Ok(())
+        }
+
+        /// Ranks skill effects for fusion: larger total boost first, then shorter cooldown
+        fn strength(effect: &SkillEffect<BlockNumberFor<T>>) -> (u64, Reverse<BlockNumberFor<T>>) {
+            let boost = &effect.boost;
+            let total = boost.speed as u64 + boost.acceleration as u64 + boost.stamina as u64;
+            (total, Reverse(effect.cooldown))
+        }
+
+        /// Effect of the skill produced by fusing copies of a skill with `effect`
+        pub fn fused_effect(
+            effect: &SkillEffect<BlockNumberFor<T>>,
+        ) -> SkillEffect<BlockNumberFor<T>> {
+            let scale = |value: u32| {
+                let scaled =
+                    (value as u64).saturating_mul(T::FusionBoostPercent::get() as u64) / 100;
+                scaled.min(u32::MAX as u64) as u32
+            };
+            SkillEffect {
+                boost: CharacterStats {
+                    speed: scale(effect.boost.speed),
+                    acceleration: scale(effect.boost.acceleration),
+                    stamina: scale(effect.boost.stamina),
+                },
+                cooldown: effect.cooldown,
+                map_condition: effect.map_condition,
+            }
+        }
+
+        /// Stats of `character_id` with the boosts of its attached skills applied.
+        /// Skills limited to another map than `map` are ignored.
+        pub fn effective_stats(
+            character_id: CharacterId,
+            map: Option<u32>,
+        ) -> Option<CharacterStats> {
+            let mut stats = T::Characters::base_stats(character_id)?;
+            for skill_id in CharacterSkills::<T>::get(character_id) {
+                if let Some(skill) = Skills::<T>::get(skill_id) {
+                    let applies = match skill.effect.map_condition {
+                        Some(condition) => map == Some(condition),
+                        None => true,
+                    };
+                    if applies {
+                        stats = stats.saturating_add(&skill.effect.boost);
+                    }
+                }
+            }
+            Some(stats)
+        }
+    }
+}
+
+impl<T: Config> CharacterAttachments for Pallet<T> {
+    fn has_attachments(character_id: CharacterId) -> bool {
+        !CharacterSkills::<T>::get(character_id).is_empty()
+    }
+}
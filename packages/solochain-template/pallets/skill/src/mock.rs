@@ -0,0 +1,87 @@
+use crate as pallet_skill;
+use frame_support::{
+    derive_impl,
+    traits::{ConstU32, ConstU64, ConstU8},
+};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type Balances = pallet_balances::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type Character = pallet_character::Pallet<Test>;
+
+    #[runtime::pallet_index(3)]
+    pub type Skill = pallet_skill::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+impl pallet_character::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MintOrigin = frame_system::EnsureRoot<u64>;
+    type MaxEnergy = ConstU32<100>;
+    type EnergyRegenPeriod = ConstU64<10>;
+    type MaxWater = ConstU32<100>;
+    type WaterRegenPeriod = ConstU64<10>;
+    type Attachments = Skill;
+}
+
+impl pallet_skill::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MintOrigin = frame_system::EnsureRoot<u64>;
+    type Characters = Character;
+    type MaxSkillSlots = ConstU32<2>;
+    type SwapCooldown = ConstU64<10>;
+    type RewardToken = Balances;
+    type SkillsPerFusion = ConstU32<3>;
+    type MaxSkillLevel = ConstU8<2>;
+    type FusionBoostPercent = ConstU32<150>;
+    type FusionCost = ConstU64<100>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 50)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
+}
@@ -0,0 +1,10 @@
+//! Runtime API for querying skill-adjusted character stats.
+
+use pallet_character::{CharacterId, CharacterStats};
+
+sp_api::decl_runtime_apis! {
+    pub trait SkillApi {
+        /// Stats of `character_id` with its attached skills applied on map `map`
+        fn effective_stats(character_id: CharacterId, map: Option<u32>) -> Option<CharacterStats>;
+    }
+}
@@ -0,0 +1,393 @@
+use crate::{mock::*, CharacterSkills, Error, Event, NextSkillId, SkillEffect, Skills};
+use frame_support::{assert_noop, assert_ok, traits::ConstU32, BoundedVec};
+use pallet_character::CharacterStats;
+
+fn base_stats() -> CharacterStats {
+    CharacterStats {
+        speed: 10,
+        acceleration: 10,
+        stamina: 10,
+    }
+}
+
+fn effect(speed: u32, map_condition: Option<u32>) -> SkillEffect<u64> {
+    SkillEffect {
+        boost: CharacterStats {
+            speed,
+            acceleration: 1,
+            stamina: 0,
+        },
+        cooldown: 5,
+        map_condition,
+    }
+}
+
+fn setup() {
+    System::set_block_number(1);
+    assert_ok!(Character::mint_character(
+        RuntimeOrigin::root(),
+        1,
+        base_stats()
+    ));
+    assert_ok!(Skill::mint_skill(
+        RuntimeOrigin::root(),
+        1,
+        7,
+        effect(5, None)
+    ));
+    assert_ok!(Skill::mint_skill(
+        RuntimeOrigin::root(),
+        1,
+        8,
+        effect(3, Some(2))
+    ));
+    assert_ok!(Skill::mint_skill(
+        RuntimeOrigin::root(),
+        1,
+        9,
+        effect(1, None)
+    ));
+}
+
+#[test]
+fn mint_skill_works() {
+    new_test_ext().execute_with(|| {
+        setup();
+
+        let skill = Skills::<Test>::get(0).unwrap();
+        assert_eq!(skill.owner, 1);
+        assert_eq!(skill.kind, 7);
+        assert_eq!(skill.level, 1);
+        assert_eq!(skill.attached_to, None);
+
+        System::assert_last_event(
+            Event::SkillMinted {
+                skill_id: 2,
+                owner: 1,
+                kind: 9,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn attach_skill_checks_ownership() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Character::mint_character(
+            RuntimeOrigin::root(),
+            2,
+            base_stats()
+        ));
+
+        assert_noop!(
+            Skill::attach_skill(RuntimeOrigin::signed(2), 0, 1),
+            Error::<Test>::NotSkillOwner
+        );
+        assert_noop!(
+            Skill::attach_skill(RuntimeOrigin::signed(1), 0, 1),
+            Error::<Test>::NotCharacterOwner
+        );
+        assert_noop!(
+            Skill::attach_skill(RuntimeOrigin::signed(1), 0, 5),
+            Error::<Test>::CharacterNotFound
+        );
+    });
+}
+
+#[test]
+fn attach_and_detach_respect_slots_and_cooldown() {
+    new_test_ext().execute_with(|| {
+        setup();
+
+        assert_ok!(Skill::attach_skill(RuntimeOrigin::signed(1), 0, 0));
+        System::assert_last_event(
+            Event::SkillAttached {
+                skill_id: 0,
+                character_id: 0,
+            }
+            .into(),
+        );
+        assert_eq!(Skills::<Test>::get(0).unwrap().attached_to, Some(0));
+        assert_noop!(
+            Skill::transfer_skill(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::SkillAttached
+        );
+
+        // A second change right away is still on cooldown
+        assert_noop!(
+            Skill::attach_skill(RuntimeOrigin::signed(1), 1, 0),
+            Error::<Test>::SwapCooldown
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Skill::attach_skill(RuntimeOrigin::signed(1), 1, 0));
+        assert_eq!(CharacterSkills::<Test>::get(0).into_inner(), vec![0, 1]);
+
+        System::set_block_number(21);
+        assert_noop!(
+            Skill::attach_skill(RuntimeOrigin::signed(1), 2, 0),
+            Error::<Test>::NoFreeSlot
+        );
+
+        assert_ok!(Skill::detach_skill(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(
+            Event::SkillDetached {
+                skill_id: 0,
+                character_id: 0,
+            }
+            .into(),
+        );
+        assert_eq!(CharacterSkills::<Test>::get(0).into_inner(), vec![1]);
+        assert_eq!(Skills::<Test>::get(0).unwrap().attached_to, None);
+        assert_noop!(
+            Skill::detach_skill(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::SkillNotAttached
+        );
+    });
+}
+
+#[test]
+fn characters_with_skills_cannot_be_transferred() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_ok!(Skill::attach_skill(RuntimeOrigin::signed(1), 0, 0));
+
+        assert_noop!(
+            Character::transfer_character(RuntimeOrigin::signed(1), 0, 2),
+            pallet_character::Error::<Test>::ItemsAttached
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Skill::detach_skill(RuntimeOrigin::signed(1), 0));
+        assert_ok!(Character::transfer_character(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert_eq!(
+            pallet_character::Characters::<Test>::get(0).unwrap().owner,
+            2
+        );
+    });
+}
+
+#[test]
+fn effective_stats_apply_skills_per_map() {
+    new_test_ext().execute_with(|| {
+        setup();
+        assert_eq!(Skill::effective_stats(0, None), Some(base_stats()));
+        assert_eq!(Skill::effective_stats(1, None), None);
+
+        assert_ok!(Skill::attach_skill(RuntimeOrigin::signed(1), 0, 0));
+        System::set_block_number(11);
+        assert_ok!(Skill::attach_skill(RuntimeOrigin::signed(1), 1, 0));
+
+        // Skill 1 only boosts on map 2
+        assert_eq!(
+            Skill::effective_stats(0, Some(1)),
+            Some(CharacterStats {
+                speed: 15,
+                acceleration: 11,
+                stamina: 10,
+            })
+        );
+        assert_eq!(
+            Skill::effective_stats(0, Some(2)),
+            Some(CharacterStats {
+                speed: 18,
+                acceleration: 12,
+                stamina: 10,
+            })
+        );
+    });
+}
+
+fn mint_copies(owner: u64, kind: u32, count: u32) -> Vec<u64> {
+    (0..count)
+        .map(|_| {
+            let skill_id = NextSkillId::<Test>::get();
+            assert_ok!(Skill::mint_skill(
+                RuntimeOrigin::root(),
+                owner,
+                kind,
+                effect(10, Some(3))
+            ));
+            skill_id
+        })
+        .collect()
+}
+
+fn inputs(ids: Vec<u64>) -> BoundedVec<u64, ConstU32<3>> {
+    BoundedVec::try_from(ids).unwrap()
+}
+
+#[test]
+fn fuse_skills_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let copies = mint_copies(1, 4, 3);
+
+        assert_ok!(Skill::fuse_skills(
+            RuntimeOrigin::signed(1),
+            inputs(copies.clone())
+        ));
+
+        for skill_id in &copies {
+            assert!(!Skills::<Test>::contains_key(skill_id));
+        }
+        let fused = Skills::<Test>::get(3).unwrap();
+        assert_eq!(fused.owner, 1);
+        assert_eq!(fused.kind, 4);
+        assert_eq!(fused.level, 2);
+        assert_eq!(
+            fused.effect,
+            SkillEffect {
+                boost: CharacterStats {
+                    speed: 15,
+                    acceleration: 1,
+                    stamina: 0,
+                },
+                cooldown: 5,
+                map_condition: Some(3),
+            }
+        );
+        assert_eq!(Balances::free_balance(1), 900);
+
+        System::assert_last_event(
+            Event::SkillsFused {
+                owner: 1,
+                inputs: inputs(copies),
+                output: 3,
+                kind: 4,
+                level: 2,
+                cost: 100,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn fuse_skills_builds_on_the_strongest_input() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let weak = mint_copies(1, 4, 1)[0];
+        let strong = NextSkillId::<Test>::get();
+        assert_ok!(Skill::mint_skill(
+            RuntimeOrigin::root(),
+            1,
+            4,
+            effect(20, None)
+        ));
+        // Same total boost as the strong book, but a shorter cooldown
+        let quick = NextSkillId::<Test>::get();
+        assert_ok!(Skill::mint_skill(
+            RuntimeOrigin::root(),
+            1,
+            4,
+            SkillEffect {
+                cooldown: 2,
+                ..effect(20, Some(1))
+            }
+        ));
+
+        assert_ok!(Skill::fuse_skills(
+            RuntimeOrigin::signed(1),
+            inputs(vec![weak, strong, quick])
+        ));
+        assert_eq!(
+            Skills::<Test>::get(3).unwrap().effect,
+            SkillEffect {
+                boost: CharacterStats {
+                    speed: 30,
+                    acceleration: 1,
+                    stamina: 0,
+                },
+                cooldown: 2,
+                map_condition: Some(1),
+            }
+        );
+
+        // Equally strong inputs: the first one given is kept
+        let first = NextSkillId::<Test>::get();
+        assert_ok!(Skill::mint_skill(
+            RuntimeOrigin::root(),
+            1,
+            5,
+            effect(10, Some(1))
+        ));
+        let copies = mint_copies(1, 5, 2);
+        assert_ok!(Skill::fuse_skills(
+            RuntimeOrigin::signed(1),
+            inputs(vec![first, copies[0], copies[1]])
+        ));
+        assert_eq!(
+            Skills::<Test>::get(7).unwrap().effect.map_condition,
+            Some(1)
+        );
+    });
+}
+
+#[test]
+fn fuse_skills_validates_inputs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let copies = mint_copies(1, 4, 3);
+        let other = mint_copies(1, 5, 1)[0];
+        let foreign = mint_copies(2, 4, 1)[0];
+
+        assert_noop!(
+            Skill::fuse_skills(RuntimeOrigin::signed(1), inputs(copies[..2].to_vec())),
+            Error::<Test>::WrongFusionInputCount
+        );
+        assert_noop!(
+            Skill::fuse_skills(
+                RuntimeOrigin::signed(1),
+                inputs(vec![copies[0], copies[1], other])
+            ),
+            Error::<Test>::SkillMismatch
+        );
+        assert_noop!(
+            Skill::fuse_skills(
+                RuntimeOrigin::signed(1),
+                inputs(vec![copies[0], copies[1], copies[1]])
+            ),
+            Error::<Test>::DuplicateSkill
+        );
+        assert_noop!(
+            Skill::fuse_skills(
+                RuntimeOrigin::signed(1),
+                inputs(vec![copies[0], copies[1], foreign])
+            ),
+            Error::<Test>::NotSkillOwner
+        );
+
+        assert_ok!(Skill::attach_skill(RuntimeOrigin::signed(1), copies[0], 0));
+        assert_noop!(
+            Skill::fuse_skills(RuntimeOrigin::signed(1), inputs(copies)),
+            Error::<Test>::SkillAttached
+        );
+    });
+}
+
+#[test]
+fn fuse_skills_respects_max_level_and_cost() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let copies = mint_copies(1, 4, 3);
+        for skill_id in &copies {
+            Skills::<Test>::mutate(skill_id, |skill| skill.as_mut().unwrap().level = 2);
+        }
+        assert_noop!(
+            Skill::fuse_skills(RuntimeOrigin::signed(1), inputs(copies)),
+            Error::<Test>::MaxLevelReached
+        );
+
+        // Account 2 cannot afford the fusion cost
+        let copies = mint_copies(2, 4, 3);
+        assert!(Skill::fuse_skills(RuntimeOrigin::signed(2), inputs(copies.clone())).is_err());
+        assert!(Skills::<Test>::contains_key(copies[0]));
+    });
+}
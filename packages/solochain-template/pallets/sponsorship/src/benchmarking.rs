@@ -0,0 +1,58 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::BoundedVec;
+use sp_std::vec;
+
+fn allowed_calls<T: Config>() -> BoundedVec<CallVariant, T::MaxAllowedCalls> {
+    let variant = CallVariant {
+        pallet_index: 0,
+        call_index: 0,
+    };
+    BoundedVec::truncate_from(vec![variant; T::MaxAllowedCalls::get() as usize])
+}
+
+benchmarks! {
+    set_sponsorship {
+        let origin = T::SponsorOrigin::try_successful_origin().map_err(|_| "no sponsor origin")?;
+        let sponsor = T::SponsorOrigin::ensure_origin(origin.clone()).map_err(|_| "no sponsor")?;
+    }: _<T::RuntimeOrigin>(origin, 1_000u32.into(), 100u32.into(), allowed_calls::<T>())
+    verify {
+        assert!(Sponsorships::<T>::contains_key(&sponsor));
+    }
+
+    remove_sponsorship {
+        let origin = T::SponsorOrigin::try_successful_origin().map_err(|_| "no sponsor origin")?;
+        let sponsor = T::SponsorOrigin::ensure_origin(origin.clone()).map_err(|_| "no sponsor")?;
+        Sponsorships::<T>::insert(&sponsor, Sponsorship {
+            budget: 1_000u32.into(),
+            per_user_limit: 100u32.into(),
+            allowed_calls: allowed_calls::<T>(),
+        });
+    }: _<T::RuntimeOrigin>(origin)
+    verify {
+        assert!(!Sponsorships::<T>::contains_key(&sponsor));
+    }
+
+    sponsor_player {
+        let origin = T::SponsorOrigin::try_successful_origin().map_err(|_| "no sponsor origin")?;
+        let sponsor = T::SponsorOrigin::ensure_origin(origin.clone()).map_err(|_| "no sponsor")?;
+        let who: T::AccountId = account("player", 0, 0);
+    }: _<T::RuntimeOrigin>(origin, who.clone())
+    verify {
+        assert!(SponsoredPlayers::<T>::contains_key(&sponsor, &who));
+    }
+
+    unsponsor_player {
+        let origin = T::SponsorOrigin::try_successful_origin().map_err(|_| "no sponsor origin")?;
+        let sponsor = T::SponsorOrigin::ensure_origin(origin.clone()).map_err(|_| "no sponsor")?;
+        let who: T::AccountId = account("player", 0, 0);
+        SponsoredPlayers::<T>::insert(&sponsor, &who, ());
+    }: _<T::RuntimeOrigin>(origin, who.clone())
+    verify {
+        assert!(!SponsoredPlayers::<T>::contains_key(&sponsor, &who));
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
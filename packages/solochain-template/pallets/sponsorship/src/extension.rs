@@ -0,0 +1,223 @@
+//! Transaction extension charging the fees of players' calls to a sponsor.
+
+use crate::{Config, Pallet};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::fmt;
+use frame_support::{
+    dispatch::{DispatchInfo, PostDispatchInfo},
+    sp_runtime::{
+        traits::{
+            AsSystemOriginSigner, DispatchInfoOf, DispatchOriginOf, Dispatchable, Implication,
+            PostDispatchInfoOf, TransactionExtension, TransactionExtensionMetadata, ValidateResult,
+            Zero,
+        },
+        transaction_validity::{
+            InvalidTransaction, TransactionSource, TransactionValidityError, ValidTransaction,
+        },
+        DispatchResult,
+    },
+    traits::Get,
+    weights::Weight,
+};
+use pallet_transaction_payment::{BalanceOf, ChargeTransactionPayment, OnChargeTransaction};
+use scale_info::TypeInfo;
+use sp_std::{vec, vec::Vec};
+
+type LiquidityInfoOf<T> =
+    <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<
+        T,
+    >>::LiquidityInfo;
+
+/// Wraps the fee payment extension `S`. When the signer names a sponsor that added them as a
+/// player and whose sponsorship covers the call, the fee is withdrawn from the sponsor instead
+/// and `S` is skipped.
+///
+/// The estimated fee is taken from the sponsorship budget together with the fee withdrawal, so
+/// the following transactions of the block see what is left of it. Whatever the call did not
+/// use is returned after dispatch.
+///
+/// The sponsor is encoded ahead of `S`, and reported in the metadata as the `SponsoredBy`
+/// extension followed by those of `S`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeSponsored<T: Config, S> {
+    /// Account paying the fee, or `None` to pay it through `S`
+    pub sponsor: Option<T::AccountId>,
+    pub inner: S,
+}
+
+impl<T: Config, S> ChargeSponsored<T, S> {
+    pub fn new(sponsor: Option<T::AccountId>, inner: S) -> Self {
+        Self { sponsor, inner }
+    }
+}
+
+impl<T: Config, S> From<S> for ChargeSponsored<T, S> {
+    fn from(inner: S) -> Self {
+        Self::new(None, inner)
+    }
+}
+
+impl<T: Config, S: fmt::Debug> fmt::Debug for ChargeSponsored<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChargeSponsored({:?}, {:?})", self.sponsor, self.inner)
+    }
+}
+
+/// Fee a sponsor pays for a transaction of `who`
+pub struct SponsoredFee<T: Config> {
+    pub sponsor: T::AccountId,
+    pub who: T::AccountId,
+    pub fee: BalanceOf<T>,
+}
+
+/// Whether the wrapped extension runs or the fee is paid by a sponsor
+pub enum Intermediate<Inner, Sponsored> {
+    Apply(Inner),
+    Sponsor(Sponsored),
+}
+
+impl<T, S> TransactionExtension<T::RuntimeCall> for ChargeSponsored<T, S>
+where
+    T: Config + Send + Sync,
+    T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+    S: TransactionExtension<T::RuntimeCall>,
+    DispatchOriginOf<T::RuntimeCall>: AsSystemOriginSigner<T::AccountId> + Clone,
+{
+    const IDENTIFIER: &'static str = "SponsoredBy";
+    type Implicit = S::Implicit;
+    type Val = Intermediate<S::Val, SponsoredFee<T>>;
+    type Pre = Intermediate<S::Pre, (SponsoredFee<T>, LiquidityInfoOf<T>)>;
+
+    fn implicit(&self) -> Result<Self::Implicit, TransactionValidityError> {
+        self.inner.implicit()
+    }
+
+    fn metadata() -> Vec<TransactionExtensionMetadata> {
+        let mut metadata = vec![TransactionExtensionMetadata {
+            identifier: Self::IDENTIFIER,
+            ty: scale_info::meta_type::<Option<T::AccountId>>(),
+            implicit: scale_info::meta_type::<()>(),
+        }];
+        metadata.extend(S::metadata());
+        metadata
+    }
+
+    fn weight(&self, call: &T::RuntimeCall) -> Weight {
+        // Sponsored players, sponsorship and the fees it paid for the signer
+        self.inner
+            .weight(call)
+            .saturating_add(T::DbWeight::get().reads_writes(3, 2))
+    }
+
+    fn validate(
+        &self,
+        origin: DispatchOriginOf<T::RuntimeCall>,
+        call: &T::RuntimeCall,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+        self_implicit: Self::Implicit,
+        inherited_implication: &impl Implication,
+        source: TransactionSource,
+    ) -> ValidateResult<Self::Val, T::RuntimeCall> {
+        let Some(sponsor) = self.sponsor.clone() else {
+            let (valid, val, origin) = self.inner.validate(
+                origin,
+                call,
+                info,
+                len,
+                self_implicit,
+                inherited_implication,
+                source,
+            )?;
+            return Ok((valid, Intermediate::Apply(val), origin));
+        };
+
+        let who = origin
+            .as_system_origin_signer()
+            .ok_or(InvalidTransaction::BadSigner)?
+            .clone();
+        let fee =
+            pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, Zero::zero());
+        if !Pallet::<T>::can_sponsor(&sponsor, &who, call, T::Balance::from(fee)) {
+            return Err(InvalidTransaction::Payment.into());
+        }
+        T::OnChargeTransaction::can_withdraw_fee(&sponsor, call, info, fee, Zero::zero())?;
+
+        let valid = ValidTransaction {
+            priority: ChargeTransactionPayment::<T>::get_priority(info, len, Zero::zero(), fee),
+            ..Default::default()
+        };
+        Ok((
+            valid,
+            Intermediate::Sponsor(SponsoredFee { sponsor, who, fee }),
+            origin,
+        ))
+    }
+
+    fn prepare(
+        self,
+        val: Self::Val,
+        origin: &DispatchOriginOf<T::RuntimeCall>,
+        call: &T::RuntimeCall,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        match val {
+            Intermediate::Apply(val) => self
+                .inner
+                .prepare(val, origin, call, info, len)
+                .map(Intermediate::Apply),
+            Intermediate::Sponsor(sponsored) => {
+                let withdrawn = T::OnChargeTransaction::withdraw_fee(
+                    &sponsored.sponsor,
+                    call,
+                    info,
+                    sponsored.fee,
+                    Zero::zero(),
+                )?;
+                Pallet::<T>::reserve_sponsored(
+                    &sponsored.sponsor,
+                    &sponsored.who,
+                    sponsored.fee.into(),
+                );
+                Ok(Intermediate::Sponsor((sponsored, withdrawn)))
+            }
+        }
+    }
+
+    fn post_dispatch_details(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<Weight, TransactionValidityError> {
+        match pre {
+            Intermediate::Apply(pre) => S::post_dispatch_details(pre, info, post_info, len, result),
+            Intermediate::Sponsor((sponsored, withdrawn)) => {
+                let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+                    len as u32,
+                    info,
+                    post_info,
+                    Zero::zero(),
+                );
+                T::OnChargeTransaction::correct_and_deposit_fee(
+                    &sponsored.sponsor,
+                    info,
+                    post_info,
+                    actual_fee,
+                    Zero::zero(),
+                    withdrawn,
+                )?;
+                Pallet::<T>::note_sponsored(
+                    &sponsored.sponsor,
+                    &sponsored.who,
+                    sponsored.fee.into(),
+                    actual_fee.into(),
+                );
+                Ok(Weight::zero())
+            }
+        }
+    }
+}
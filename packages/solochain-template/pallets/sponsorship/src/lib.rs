@@ -0,0 +1,304 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod extension;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+pub use extension::ChargeSponsored;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, Zero},
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_transaction_payment::BalanceOf;
+
+    pub type SponsorshipOf<T> = Sponsorship<<T as Config>::Balance, <T as Config>::MaxAllowedCalls>;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_transaction_payment::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to sponsor fees, resolving to the account paying them
+        type SponsorOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+        /// Balance of transaction fees
+        type Balance: AtLeast32BitUnsigned
+            + Parameter
+            + Member
+            + MaxEncodedLen
+            + Copy
+            + IsType<BalanceOf<Self>>;
+        /// Largest number of call variants a sponsorship covers
+        #[pallet::constant]
+        type MaxAllowedCalls: Get<u32>;
+    }
+
+    /// Call of a pallet, identified by the indices it is encoded with
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub struct CallVariant {
+        pub pallet_index: u8,
+        pub call_index: u8,
+    }
+
+    /// Fees a sponsor pays for players' calls
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(MaxCalls))]
+    pub struct Sponsorship<Balance, MaxCalls: Get<u32>> {
+        /// Fees the sponsor is still willing to pay
+        pub budget: Balance,
+        /// Largest total of fees paid for a single player
+        pub per_user_limit: Balance,
+        /// Calls whose fees are paid
+        pub allowed_calls: BoundedVec<CallVariant, MaxCalls>,
+    }
+
+    /// Sponsorship of each sponsor account
+    #[pallet::storage]
+    pub type Sponsorships<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, SponsorshipOf<T>>;
+
+    /// Players each sponsor pays fees for
+    #[pallet::storage]
+    pub type SponsoredPlayers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Fees each sponsor paid for each player
+    #[pallet::storage]
+    pub type SponsoredFees<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        T::Balance,
+        ValueQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Sponsorship created or replaced
+        SponsorshipSet {
+            sponsor: T::AccountId,
+            budget: T::Balance,
+            per_user_limit: T::Balance,
+        },
+        /// Sponsorship removed with the budget it had left
+        SponsorshipRemoved {
+            sponsor: T::AccountId,
+            unspent: T::Balance,
+        },
+        /// Sponsor started paying the fees of a player
+        PlayerSponsored {
+            sponsor: T::AccountId,
+            who: T::AccountId,
+        },
+        /// Sponsor stopped paying the fees of a player
+        PlayerUnsponsored {
+            sponsor: T::AccountId,
+            who: T::AccountId,
+        },
+        /// Sponsor paid the fee of a player's transaction
+        FeeSponsored {
+            sponsor: T::AccountId,
+            who: T::AccountId,
+            fee: T::Balance,
+            budget_left: T::Balance,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Sponsorship budget and per-player limit must be positive
+        InvalidSponsorship,
+        /// Account does not sponsor fees
+        NoSponsorship,
+        /// Player is already sponsored by the account
+        AlreadySponsored,
+        /// Player is not sponsored by the account
+        NotSponsored,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Pay up to `budget` of the fees of `allowed_calls`, and at most `per_user_limit` for
+        /// a single player. Replaces the caller's previous sponsorship; only the players added
+        /// with `sponsor_player` are sponsored.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_sponsorship())]
+        pub fn set_sponsorship(
+            origin: OriginFor<T>,
+            budget: T::Balance,
+            per_user_limit: T::Balance,
+            allowed_calls: BoundedVec<CallVariant, T::MaxAllowedCalls>,
+        ) -> DispatchResult {
+            let sponsor = T::SponsorOrigin::ensure_origin(origin)?;
+            ensure!(
+                !budget.is_zero() && !per_user_limit.is_zero(),
+                Error::<T>::InvalidSponsorship
+            );
+
+            Sponsorships::<T>::insert(
+                &sponsor,
+                Sponsorship {
+                    budget,
+                    per_user_limit,
+                    allowed_calls,
+                },
+            );
+
+            Self::deposit_event(Event::SponsorshipSet {
+                sponsor,
+                budget,
+                per_user_limit,
+            });
+
+            Ok(())
+        }
+
+        /// Stop sponsoring fees
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::remove_sponsorship())]
+        pub fn remove_sponsorship(origin: OriginFor<T>) -> DispatchResult {
+            let sponsor = T::SponsorOrigin::ensure_origin(origin)?;
+            let sponsorship = Sponsorships::<T>::take(&sponsor).ok_or(Error::<T>::NoSponsorship)?;
+
+            Self::deposit_event(Event::SponsorshipRemoved {
+                sponsor,
+                unspent: sponsorship.budget,
+            });
+
+            Ok(())
+        }
+
+        /// Pay the fees of `who` under the caller's sponsorship
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::sponsor_player())]
+        pub fn sponsor_player(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            let sponsor = T::SponsorOrigin::ensure_origin(origin)?;
+            ensure!(
+                !SponsoredPlayers::<T>::contains_key(&sponsor, &who),
+                Error::<T>::AlreadySponsored
+            );
+
+            SponsoredPlayers::<T>::insert(&sponsor, &who, ());
+
+            Self::deposit_event(Event::PlayerSponsored { sponsor, who });
+
+            Ok(())
+        }
+
+        /// Stop paying the fees of `who`
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::unsponsor_player())]
+        pub fn unsponsor_player(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            let sponsor = T::SponsorOrigin::ensure_origin(origin)?;
+            SponsoredPlayers::<T>::take(&sponsor, &who).ok_or(Error::<T>::NotSponsored)?;
+
+            Self::deposit_event(Event::PlayerUnsponsored { sponsor, who });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Variant of `call`, read from the pallet and call indices it starts with
+        pub fn variant_of(call: &<T as frame_system::Config>::RuntimeCall) -> CallVariant {
+            call.using_encoded(|encoded| CallVariant {
+                pallet_index: encoded.first().copied().unwrap_or_default(),
+                call_index: encoded.get(1).copied().unwrap_or_default(),
+            })
+        }
+
+        /// Whether `sponsor` pays a fee of `fee` for `call` signed by `who`
+        pub fn can_sponsor(
+            sponsor: &T::AccountId,
+            who: &T::AccountId,
+            call: &<T as frame_system::Config>::RuntimeCall,
+            fee: T::Balance,
+        ) -> bool {
+            if !SponsoredPlayers::<T>::contains_key(sponsor, who) {
+                return false;
+            }
+            let Some(sponsorship) = Sponsorships::<T>::get(sponsor) else {
+                return false;
+            };
+            let paid = SponsoredFees::<T>::get(sponsor, who);
+            sponsorship.allowed_calls.contains(&Self::variant_of(call))
+                && fee <= sponsorship.budget
+                && paid.saturating_add(fee) <= sponsorship.per_user_limit
+        }
+
+        /// Take the estimated `fee` of a call `sponsor` pays for `who` from the sponsorship,
+        /// before the call runs
+        pub(crate) fn reserve_sponsored(
+            sponsor: &T::AccountId,
+            who: &T::AccountId,
+            fee: T::Balance,
+        ) {
+            Sponsorships::<T>::mutate(sponsor, |sponsorship| {
+                if let Some(sponsorship) = sponsorship {
+                    sponsorship.budget.saturating_reduce(fee);
+                }
+            });
+            SponsoredFees::<T>::mutate(sponsor, who, |paid| paid.saturating_accrue(fee));
+        }
+
+        /// Settle the `fee` `sponsor` paid for `who`, returning what was reserved beyond it to
+        /// the sponsorship
+        pub(crate) fn note_sponsored(
+            sponsor: &T::AccountId,
+            who: &T::AccountId,
+            reserved: T::Balance,
+            fee: T::Balance,
+        ) {
+            let refund = reserved.saturating_sub(fee);
+            let budget_left = Sponsorships::<T>::mutate(sponsor, |sponsorship| {
+                sponsorship.as_mut().map_or(Zero::zero(), |sponsorship| {
+                    sponsorship.budget.saturating_accrue(refund);
+                    sponsorship.budget
+                })
+            });
+            SponsoredFees::<T>::mutate(sponsor, who, |paid| paid.saturating_reduce(refund));
+
+            Self::deposit_event(Event::FeeSponsored {
+                sponsor: sponsor.clone(),
+                who: who.clone(),
+                fee,
+                budget_left,
+            });
+        }
+    }
+}
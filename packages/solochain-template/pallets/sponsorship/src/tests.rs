@@ -0,0 +1,324 @@
+use crate::{
+    mock::*, CallVariant, ChargeSponsored, Error, Event, SponsoredFees, SponsoredPlayers,
+    Sponsorships,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::{DispatchInfo, PostDispatchInfo},
+    weights::Weight,
+    BoundedVec,
+};
+use sp_runtime::{
+    traits::{DispatchTransaction, TransactionExtension},
+    transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError},
+};
+
+type Extension = ChargeSponsored<Test, Charge>;
+
+/// Length of the sponsored transactions
+const LEN: usize = 10;
+
+fn remark() -> RuntimeCall {
+    RuntimeCall::System(frame_system::Call::remark {
+        remark: b"ride".to_vec(),
+    })
+}
+
+fn info() -> DispatchInfo {
+    DispatchInfo {
+        call_weight: Weight::from_parts(1_000, 0),
+        ..Default::default()
+    }
+}
+
+fn fee() -> u64 {
+    TransactionPayment::compute_fee(LEN as u32, &info(), 0)
+}
+
+fn sponsor_remarks(budget: u64, per_user_limit: u64) {
+    assert_ok!(Sponsorship::set_sponsorship(
+        RuntimeOrigin::signed(SPONSOR),
+        budget,
+        per_user_limit,
+        BoundedVec::truncate_from(vec![Sponsorship::variant_of(&remark())])
+    ));
+    for who in 1..=3 {
+        assert_ok!(Sponsorship::sponsor_player(
+            RuntimeOrigin::signed(SPONSOR),
+            who
+        ));
+    }
+}
+
+fn validate(
+    sponsor: Option<u64>,
+    who: u64,
+    call: &RuntimeCall,
+) -> Result<(), TransactionValidityError> {
+    Extension::new(sponsor, Charge)
+        .validate_only(
+            RuntimeOrigin::signed(who),
+            call,
+            &info(),
+            LEN,
+            TransactionSource::External,
+            0,
+        )
+        .map(|_| ())
+}
+
+fn dispatch(who: u64) {
+    let result = Extension::new(Some(SPONSOR), Charge)
+        .dispatch_transaction(RuntimeOrigin::signed(who), remark(), &info(), LEN, 0)
+        .unwrap();
+    assert_ok!(result);
+}
+
+#[test]
+fn set_sponsorship_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Sponsorship::set_sponsorship(
+                RuntimeOrigin::signed(SPONSOR),
+                0,
+                10,
+                BoundedVec::default()
+            ),
+            Error::<Test>::InvalidSponsorship
+        );
+
+        sponsor_remarks(1_000, 100);
+        System::assert_last_event(
+            Event::SponsorshipSet {
+                sponsor: SPONSOR,
+                budget: 1_000,
+                per_user_limit: 100,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Sponsorships::<Test>::get(SPONSOR)
+                .unwrap()
+                .allowed_calls
+                .into_inner(),
+            vec![CallVariant {
+                pallet_index: 0,
+                call_index: 0,
+            }]
+        );
+    });
+}
+
+#[test]
+fn sponsor_player_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Sponsorship::sponsor_player(
+            RuntimeOrigin::signed(SPONSOR),
+            1
+        ));
+        System::assert_last_event(
+            Event::PlayerSponsored {
+                sponsor: SPONSOR,
+                who: 1,
+            }
+            .into(),
+        );
+        assert!(SponsoredPlayers::<Test>::contains_key(SPONSOR, 1));
+        assert_noop!(
+            Sponsorship::sponsor_player(RuntimeOrigin::signed(SPONSOR), 1),
+            Error::<Test>::AlreadySponsored
+        );
+
+        assert_ok!(Sponsorship::unsponsor_player(
+            RuntimeOrigin::signed(SPONSOR),
+            1
+        ));
+        System::assert_last_event(
+            Event::PlayerUnsponsored {
+                sponsor: SPONSOR,
+                who: 1,
+            }
+            .into(),
+        );
+        assert!(!SponsoredPlayers::<Test>::contains_key(SPONSOR, 1));
+        assert_noop!(
+            Sponsorship::unsponsor_player(RuntimeOrigin::signed(SPONSOR), 1),
+            Error::<Test>::NotSponsored
+        );
+    });
+}
+
+#[test]
+fn only_players_of_the_sponsor_are_sponsored() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let fee = fee();
+        sponsor_remarks(3 * fee, 2 * fee);
+
+        // Fresh accounts cannot bill the sponsor
+        assert_eq!(
+            validate(Some(SPONSOR), 4, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        assert_ok!(Sponsorship::unsponsor_player(
+            RuntimeOrigin::signed(SPONSOR),
+            1
+        ));
+        assert_eq!(
+            validate(Some(SPONSOR), 1, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert_eq!(Sponsorships::<Test>::get(SPONSOR).unwrap().budget, 3 * fee);
+        assert_eq!(SponsoredFees::<Test>::get(SPONSOR, 1), 0);
+    });
+}
+
+#[test]
+fn sponsor_pays_fees_of_allowed_calls() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let fee = fee();
+        sponsor_remarks(3 * fee, 2 * fee);
+
+        // Without a sponsor the player pays through the wrapped extension
+        assert_eq!(
+            validate(None, 1, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert_ok!(validate(Some(SPONSOR), 1, &remark()));
+
+        let balance = Balances::free_balance(SPONSOR);
+        dispatch(1);
+        System::assert_last_event(
+            Event::FeeSponsored {
+                sponsor: SPONSOR,
+                who: 1,
+                fee,
+                budget_left: 2 * fee,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(SPONSOR), balance - fee);
+        assert_eq!(Balances::free_balance(1), 0);
+        assert_eq!(SponsoredFees::<Test>::get(SPONSOR, 1), fee);
+    });
+}
+
+#[test]
+fn sponsorship_is_limited_per_player_and_by_budget() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let fee = fee();
+        sponsor_remarks(3 * fee, 2 * fee);
+
+        dispatch(1);
+        dispatch(1);
+        assert_eq!(
+            validate(Some(SPONSOR), 1, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        dispatch(2);
+        assert_eq!(Sponsorships::<Test>::get(SPONSOR).unwrap().budget, 0);
+        assert_eq!(
+            validate(Some(SPONSOR), 3, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+    });
+}
+
+#[test]
+fn budget_is_reserved_before_dispatch_and_unused_fee_returned() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let fee = fee();
+        sponsor_remarks(fee, 2 * fee);
+
+        let (pre, _) = Extension::new(Some(SPONSOR), Charge)
+            .validate_and_prepare(RuntimeOrigin::signed(1), &remark(), &info(), LEN, 0)
+            .unwrap();
+        assert_eq!(Sponsorships::<Test>::get(SPONSOR).unwrap().budget, 0);
+        assert_eq!(SponsoredFees::<Test>::get(SPONSOR, 1), fee);
+
+        // Transactions after it in the block no longer see the reserved fee
+        assert_eq!(
+            validate(Some(SPONSOR), 2, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        let mut post_info = PostDispatchInfo {
+            actual_weight: Some(Weight::zero()),
+            ..Default::default()
+        };
+        let actual_fee = TransactionPayment::compute_actual_fee(LEN as u32, &info(), &post_info, 0);
+        assert!(actual_fee < fee);
+        assert_ok!(Extension::post_dispatch(
+            pre,
+            &info(),
+            &mut post_info,
+            LEN,
+            &Ok(())
+        ));
+        System::assert_last_event(
+            Event::FeeSponsored {
+                sponsor: SPONSOR,
+                who: 1,
+                fee: actual_fee,
+                budget_left: fee - actual_fee,
+            }
+            .into(),
+        );
+        assert_eq!(SponsoredFees::<Test>::get(SPONSOR, 1), actual_fee);
+    });
+}
+
+#[test]
+fn only_allowed_calls_of_known_sponsors_are_sponsored() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        sponsor_remarks(1_000 * fee(), 100 * fee());
+
+        let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+            remark: b"ride".to_vec(),
+        });
+        assert_eq!(
+            validate(Some(SPONSOR), 1, &call),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert_eq!(
+            validate(Some(2), 1, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+    });
+}
+
+#[test]
+fn remove_sponsorship_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        sponsor_remarks(1_000 * fee(), 100 * fee());
+        dispatch(1);
+
+        assert_ok!(Sponsorship::remove_sponsorship(RuntimeOrigin::signed(
+            SPONSOR
+        )));
+        System::assert_last_event(
+            Event::SponsorshipRemoved {
+                sponsor: SPONSOR,
+                unspent: 999 * fee(),
+            }
+            .into(),
+        );
+        assert_noop!(
+            Sponsorship::remove_sponsorship(RuntimeOrigin::signed(SPONSOR)),
+            Error::<Test>::NoSponsorship
+        );
+        assert_eq!(
+            validate(Some(SPONSOR), 1, &remark()),
+            Err(InvalidTransaction::Payment.into())
+        );
+    });
+}
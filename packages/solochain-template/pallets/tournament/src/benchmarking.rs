@@ -0,0 +1,100 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::{sp_runtime::Percent, BoundedVec};
+use frame_system::RawOrigin;
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn players<T: Config>(n: u32) -> BoundedVec<T::AccountId, T::MaxPlayers> {
+    BoundedVec::truncate_from(
+        (0..n)
+            .map(|index| account("player", index, SEED))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Prize shares paying every place up to `MaxPrizePlaces`
+fn prize_shares<T: Config>() -> BoundedVec<Percent, T::MaxPrizePlaces> {
+    let places = T::MaxPrizePlaces::get().min(T::MaxPlayers::get()).max(1);
+    let share = Percent::from_rational(1, places);
+    BoundedVec::truncate_from((0..places).map(|_| share).collect::<Vec<_>>())
+}
+
+/// Insert tournament 0 in registration with `n` players
+fn registering<T: Config>(n: u32) -> TournamentId {
+    Tournaments::<T>::insert(
+        0,
+        Tournament {
+            registration_ends: frame_system::Pallet::<T>::block_number() + 10u32.into(),
+            max_players: T::MaxPlayers::get(),
+            prize_pool: 1_000u32.into(),
+            prize_shares: prize_shares::<T>(),
+            players: players::<T>(n),
+            pending_matches: 0,
+            status: TournamentStatus::Registration,
+        },
+    );
+    NextTournamentId::<T>::put(1);
+    0
+}
+
+benchmarks! {
+    create_tournament {
+        let origin = T::TournamentOrigin::try_successful_origin()
+            .map_err(|_| "no tournament origin")?;
+        let registration_ends = frame_system::Pallet::<T>::block_number() + 10u32.into();
+    }: _<T::RuntimeOrigin>(origin, registration_ends, T::MaxPlayers::get(), 1_000u32.into(), prize_shares::<T>())
+    verify {
+        assert!(Tournaments::<T>::contains_key(0));
+    }
+
+    register {
+        let n in 1 .. T::MaxPlayers::get() - 1;
+        let tournament_id = registering::<T>(n);
+        let caller: T::AccountId = account("joiner", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), tournament_id)
+    verify {
+        assert!(Tournaments::<T>::get(tournament_id).unwrap().players.contains(&caller));
+    }
+
+    start_tournament {
+        let n in 2 .. T::MaxPlayers::get();
+        let tournament_id = registering::<T>(n);
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number() + 11u32.into(),
+        );
+        let caller: T::AccountId = account("caller", 0, SEED);
+    }: _(RawOrigin::Signed(caller), tournament_id)
+    verify {
+        assert!(Brackets::<T>::contains_key(tournament_id));
+    }
+
+    submit_match_result {
+        // Last match of a round of `n` slots; with two slots it is the final
+        let n in 2 .. T::MaxPlayers::get();
+        let tournament_id = registering::<T>(n);
+        let mut tournament = Tournaments::<T>::get(tournament_id).unwrap();
+        tournament.status = TournamentStatus::Running { round: 0 };
+        tournament.pending_matches = 1;
+        let bracket: BracketOf<T> = BoundedVec::truncate_from(
+            tournament.players.iter().cloned().map(Some).collect::<Vec<_>>(),
+        );
+        for match_index in 1..n / 2 {
+            let winner = tournament.players[2 * match_index as usize].clone();
+            MatchWinners::<T>::insert(tournament_id, match_index, winner);
+        }
+        let winner = tournament.players[0].clone();
+        Tournaments::<T>::insert(tournament_id, tournament);
+        Brackets::<T>::insert(tournament_id, bracket);
+        let origin = T::ResultOrigin::try_successful_origin().map_err(|_| "no result origin")?;
+    }: _<T::RuntimeOrigin>(origin, tournament_id, 0, winner)
+    verify {
+        let next_round_slots = (n + 1) / 2;
+        assert_eq!(Tournaments::<T>::get(tournament_id).unwrap().pending_matches, next_round_slots / 2);
+    }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,556 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{AtLeast32BitUnsigned, Zero},
+            Percent,
+        },
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_rewards::{RewardAccrual, RewardReason};
+    use pallet_user_profile::{UserStatsHandler, UserStatsInspect};
+    use sp_std::vec::Vec;
+
+    /// Identifier of a tournament
+    pub type TournamentId = u64;
+
+    pub type BalanceOf<T> = <T as Config>::Balance;
+
+    pub type TournamentOf<T> = Tournament<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        BalanceOf<T>,
+        <T as Config>::MaxPlayers,
+        <T as Config>::MaxPrizePlaces,
+    >;
+
+    /// Players of the current round in bracket order; `None` is a bye
+    pub type BracketOf<T> =
+        BoundedVec<Option<<T as frame_system::Config>::AccountId>, <T as Config>::MaxPlayers>;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type WeightInfo: WeightInfo;
+        /// Origin allowed to create tournaments
+        type TournamentOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to submit match results
+        type ResultOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Player statistics used for seeding, counting tournament entries
+        type UserStats: UserStatsInspect<Self::AccountId> + UserStatsHandler<Self::AccountId>;
+        /// Balance of tournament prizes
+        type Balance: AtLeast32BitUnsigned + Parameter + Member + MaxEncodedLen + Copy;
+        /// Credits prizes as pending tournament rewards
+        type Rewards: RewardAccrual<Self::AccountId, Self::Balance>;
+        /// Largest number of players in a tournament; must be a power of two
+        #[pallet::constant]
+        type MaxPlayers: Get<u32>;
+        /// Largest number of finishers receiving a prize
+        #[pallet::constant]
+        type MaxPrizePlaces: Get<u32>;
+    }
+
+    /// Stage of a tournament
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TournamentStatus<AccountId> {
+        /// Players can register until the registration deadline
+        Registration,
+        /// Bracket is being played; `round` starts at 0
+        Running { round: u32 },
+        /// Final played and prizes paid out
+        Finished { champion: AccountId },
+        /// Not enough players registered
+        Cancelled,
+    }
+
+    /// Single-elimination tournament
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(MaxPlayers, MaxPrizePlaces))]
+    pub struct Tournament<
+        AccountId,
+        BlockNumber,
+        Balance,
+        MaxPlayers: Get<u32>,
+        MaxPrizePlaces: Get<u32>,
+    > {
+        /// Last block players can register at
+        pub registration_ends: BlockNumber,
+        pub max_players: u32,
+        /// Total prize minted to the top finishers
+        pub prize_pool: Balance,
+        /// Share of the prize pool for each place, starting with the champion
+        pub prize_shares: BoundedVec<Percent, MaxPrizePlaces>,
+        /// Registered players; sorted by seed once the tournament starts
+        pub players: BoundedVec<AccountId, MaxPlayers>,
+        /// Matches of the current round still waiting for a result
+        pub pending_matches: u32,
+        pub status: TournamentStatus<AccountId>,
+    }
+
+    /// Map from TournamentId to Tournament
+    #[pallet::storage]
+    pub type Tournaments<T: Config> =
+        StorageMap<_, Blake2_128Concat, TournamentId, TournamentOf<T>>;
+
+    /// Next TournamentId to assign
+    #[pallet::storage]
+    pub type NextTournamentId<T> = StorageValue<_, TournamentId, ValueQuery>;
+
+    /// Bracket of the current round of running tournaments.
+    /// Match `i` is played between slots `2 * i` and `2 * i + 1`.
+    #[pallet::storage]
+    pub type Brackets<T: Config> = StorageMap<_, Blake2_128Concat, TournamentId, BracketOf<T>>;
+
+    /// Winners of the decided matches of the current round
+    #[pallet::storage]
+    pub type MatchWinners<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, TournamentId, Twox64Concat, u32, T::AccountId>;
+
+    /// Eliminated players of running tournaments, from first out to last out
+    #[pallet::storage]
+    pub type Eliminated<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        TournamentId,
+        BoundedVec<T::AccountId, T::MaxPlayers>,
+        ValueQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Tournament opened for registration
+        TournamentCreated {
+            tournament_id: TournamentId,
+            registration_ends: BlockNumberFor<T>,
+            max_players: u32,
+            prize_pool: BalanceOf<T>,
+        },
+        /// Player registered for a tournament
+        PlayerRegistered {
+            tournament_id: TournamentId,
+            who: T::AccountId,
+        },
+        /// Tournament did not gather enough players
+        TournamentCancelled { tournament_id: TournamentId },
+        /// Round bracket generated
+        RoundStarted {
+            tournament_id: TournamentId,
+            round: u32,
+            bracket: BracketOf<T>,
+        },
+        /// Match result submitted
+        MatchDecided {
+            tournament_id: TournamentId,
+            round: u32,
+            match_index: u32,
+            winner: T::AccountId,
+            loser: T::AccountId,
+        },
+        /// Prize credited to the pending rewards of a top finisher; `place` starts at 1
+        PrizePaid {
+            tournament_id: TournamentId,
+            who: T::AccountId,
+            place: u32,
+            amount: BalanceOf<T>,
+        },
+        /// Final played
+        TournamentFinished {
+            tournament_id: TournamentId,
+            champion: T::AccountId,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Tournament does not exist
+        TournamentNotFound,
+        /// Player limit outside the allowed range
+        InvalidPlayerLimit,
+        /// Prize shares exceed 100%
+        InvalidPrizeShares,
+        /// Registration deadline already passed
+        RegistrationClosed,
+        /// Registration deadline not reached yet
+        RegistrationOpen,
+        /// Tournament has no free seat left
+        TournamentFull,
+        /// Caller already registered
+        AlreadyRegistered,
+        /// Tournament is not running
+        TournamentNotRunning,
+        /// Match does not exist in the current round or has a bye
+        InvalidMatch,
+        /// Match already has a winner
+        MatchAlreadyDecided,
+        /// Winner does not play in the match
+        NotInMatch,
+        /// No more ids available
+        Overflow,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                T::MaxPlayers::get().is_power_of_two(),
+                "MaxPlayers must be a power of two"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Open a tournament for registration until `registration_ends`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::create_tournament())]
+        pub fn create_tournament(
+            origin: OriginFor<T>,
+            registration_ends: BlockNumberFor<T>,
+            max_players: u32,
+            prize_pool: BalanceOf<T>,
+            prize_shares: BoundedVec<Percent, T::MaxPrizePlaces>,
+        ) -> DispatchResult {
+            T::TournamentOrigin::ensure_origin(origin)?;
+            ensure!(
+                max_players >= 2 && max_players <= T::MaxPlayers::get(),
+                Error::<T>::InvalidPlayerLimit
+            );
+            let total = prize_shares
+                .iter()
+                .map(|share| share.deconstruct() as u32)
+                .sum::<u32>();
+            ensure!(
+                total <= 100 && prize_shares.len() as u32 <= max_players,
+                Error::<T>::InvalidPrizeShares
+            );
+            ensure!(
+                registration_ends > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::RegistrationClosed
+            );
+
+            let tournament_id = NextTournamentId::<T>::get();
+            let next = tournament_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            NextTournamentId::<T>::put(next);
+
+            Tournaments::<T>::insert(
+                tournament_id,
+                Tournament {
+                    registration_ends,
+                    max_players,
+                    prize_pool,
+                    prize_shares,
+                    players: BoundedVec::new(),
+                    pending_matches: 0,
+                    status: TournamentStatus::Registration,
+                },
+            );
+
+            Self::deposit_event(Event::TournamentCreated {
+                tournament_id,
+                registration_ends,
+                max_players,
+                prize_pool,
+            });
+
+            Ok(())
+        }
+
+        /// Register for a tournament before its registration deadline
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::register(T::MaxPlayers::get()))]
+        pub fn register(origin: OriginFor<T>, tournament_id: TournamentId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut tournament =
+                Tournaments::<T>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            ensure!(
+                tournament.status == TournamentStatus::Registration
+                    && frame_system::Pallet::<T>::block_number() <= tournament.registration_ends,
+                Error::<T>::RegistrationClosed
+            );
+            ensure!(
+                !tournament.players.contains(&who),
+                Error::<T>::AlreadyRegistered
+            );
+            ensure!(
+                (tournament.players.len() as u32) < tournament.max_players,
+                Error::<T>::TournamentFull
+            );
+            tournament
+                .players
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::TournamentFull)?;
+            Tournaments::<T>::insert(tournament_id, tournament);
+
+            Self::deposit_event(Event::PlayerRegistered { tournament_id, who });
+
+            Ok(())
+        }
+
+        /// Close registration: seed players by wins, count the tournament entry of every player
+        /// and generate the first round bracket, or cancel the tournament if fewer than two
+        /// players registered
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            T::WeightInfo::start_tournament(T::MaxPlayers::get()).saturating_add(
+                T::UserStats::record_weight().saturating_mul(T::MaxPlayers::get().into())
+            )
+        )]
+        pub fn start_tournament(
+            origin: OriginFor<T>,
+            tournament_id: TournamentId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut tournament =
+                Tournaments::<T>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            ensure!(
+                tournament.status == TournamentStatus::Registration,
+                Error::<T>::RegistrationClosed
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() > tournament.registration_ends,
+                Error::<T>::RegistrationOpen
+            );
+
+            if tournament.players.len() < 2 {
+                tournament.status = TournamentStatus::Cancelled;
+                Tournaments::<T>::insert(tournament_id, tournament);
+                Self::deposit_event(Event::TournamentCancelled { tournament_id });
+                return Ok(());
+            }
+
+            // Most wins first; the stable sort keeps registration order between equals. Wins are
+            // only recorded by game pallets, so players cannot seed themselves.
+            let mut seeded = tournament
+                .players
+                .iter()
+                .map(|who| (T::UserStats::stats(who).wins, who.clone()))
+                .collect::<Vec<_>>();
+            seeded.sort_by_key(|(wins, _)| core::cmp::Reverse(*wins));
+            tournament.players =
+                BoundedVec::truncate_from(seeded.into_iter().map(|(_, who)| who).collect());
+            let size = (tournament.players.len() as u32).next_power_of_two();
+            let bracket = Self::seeded_order(size)
+                .into_iter()
+                .map(|seed| tournament.players.get(seed as usize).cloned())
+                .collect::<Vec<_>>();
+            let bracket = BoundedVec::truncate_from(bracket);
+
+            Self::begin_round(tournament_id, &mut tournament, 0, bracket)?;
+            for who in tournament.players.iter() {
+                T::UserStats::record_tournament_entry(who);
+            }
+            Tournaments::<T>::insert(tournament_id, tournament);
+
+            Ok(())
+        }
+
+        /// Submit the winner of a match of the current round. The next round starts once all
+        /// matches are decided, and prizes are paid out after the final.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::submit_match_result(T::MaxPlayers::get()))]
+        pub fn submit_match_result(
+            origin: OriginFor<T>,
+            tournament_id: TournamentId,
+            match_index: u32,
+            winner: T::AccountId,
+        ) -> DispatchResult {
+            T::ResultOrigin::ensure_origin(origin)?;
+
+            let mut tournament =
+                Tournaments::<T>::get(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            let TournamentStatus::Running { round } = tournament.status else {
+                return Err(Error::<T>::TournamentNotRunning.into());
+            };
+            let bracket = Brackets::<T>::get(tournament_id).unwrap_or_default();
+            let slot = (match_index as usize).saturating_mul(2);
+            let (Some(Some(first)), Some(Some(second))) =
+                (bracket.get(slot).cloned(), bracket.get(slot + 1).cloned())
+            else {
+                return Err(Error::<T>::InvalidMatch.into());
+            };
+            ensure!(
+                !MatchWinners::<T>::contains_key(tournament_id, match_index),
+                Error::<T>::MatchAlreadyDecided
+            );
+            let loser = if winner == first {
+                second
+            } else if winner == second {
+                first
+            } else {
+                return Err(Error::<T>::NotInMatch.into());
+            };
+
+            MatchWinners::<T>::insert(tournament_id, match_index, &winner);
+            tournament.pending_matches = tournament.pending_matches.saturating_sub(1);
+            Self::deposit_event(Event::MatchDecided {
+                tournament_id,
+                round,
+                match_index,
+                winner,
+                loser,
+            });
+
+            if tournament.pending_matches == 0 {
+                let next = Self::close_round(tournament_id, &tournament, &bracket)?;
+                Self::begin_round(tournament_id, &mut tournament, round + 1, next)?;
+            }
+            Tournaments::<T>::insert(tournament_id, tournament);
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Seeds (starting at 0) in bracket order for a bracket of `size` slots, so that the
+        /// best seeds only meet in the latest rounds and byes go to the top seeds
+        pub fn seeded_order(size: u32) -> Vec<u32> {
+            let mut order = Vec::from([0u32]);
+            while (order.len() as u32) < size {
+                let len = order.len() as u32 * 2;
+                order = order
+                    .iter()
+                    .flat_map(|&seed| [seed, len - 1 - seed])
+                    .collect();
+            }
+            order
+        }
+
+        /// Start `round` with `bracket`, advancing byes right away and finishing the tournament
+        /// once a single player is left
+        fn begin_round(
+            tournament_id: TournamentId,
+            tournament: &mut TournamentOf<T>,
+            mut round: u32,
+            mut bracket: BracketOf<T>,
+        ) -> DispatchResult {
+            loop {
+                if bracket.len() <= 1 {
+                    let Some(Some(champion)) = bracket.first().cloned() else {
+                        return Err(Error::<T>::InvalidMatch.into());
+                    };
+                    return Self::finish(tournament_id, tournament, champion);
+                }
+                let pending = bracket
+                    .chunks(2)
+                    .filter(|pair| pair.iter().all(Option::is_some))
+                    .count() as u32;
+                if pending > 0 {
+                    tournament.pending_matches = pending;
+                    tournament.status = TournamentStatus::Running { round };
+                    Brackets::<T>::insert(tournament_id, &bracket);
+                    Self::deposit_event(Event::RoundStarted {
+                        tournament_id,
+                        round,
+                        bracket,
+                    });
+                    return Ok(());
+                }
+                // Only byes: everyone left advances
+                bracket = BoundedVec::truncate_from(
+                    bracket
+                        .chunks(2)
+                        .map(|pair| pair.iter().flatten().next().cloned())
+                        .collect::<Vec<_>>(),
+                );
+                round = round.saturating_add(1);
+            }
+        }
+
+        /// Collect the winners of the finished round as the next bracket and record its losers
+        fn close_round(
+            tournament_id: TournamentId,
+            tournament: &TournamentOf<T>,
+            bracket: &BracketOf<T>,
+        ) -> Result<BracketOf<T>, DispatchError> {
+            let mut next = Vec::new();
+            let mut losers = Vec::new();
+            for (match_index, pair) in bracket.chunks(2).enumerate() {
+                let winner = match pair {
+                    [Some(first), Some(second)] => {
+                        let winner = MatchWinners::<T>::take(tournament_id, match_index as u32)
+                            .ok_or(Error::<T>::InvalidMatch)?;
+                        losers.push(if winner == *first {
+                            second.clone()
+                        } else {
+                            first.clone()
+                        });
+                        Some(winner)
+                    }
+                    _ => pair.iter().flatten().next().cloned(),
+                };
+                next.push(winner);
+            }
+
+            // Worst seed first, so that better seeds rank higher among players out in the
+            // same round
+            let seed = |who: &T::AccountId| tournament.players.iter().position(|p| p == who);
+            losers.sort_by_key(|who| core::cmp::Reverse(seed(who)));
+            Eliminated::<T>::try_mutate(tournament_id, |eliminated| -> DispatchResult {
+                for loser in losers {
+                    eliminated
+                        .try_push(loser)
+                        .map_err(|_| Error::<T>::TournamentFull)?;
+                }
+                Ok(())
+            })?;
+
+            Ok(BoundedVec::truncate_from(next))
+        }
+
+        /// Pay out prizes by final standing and mark the tournament finished
+        fn finish(
+            tournament_id: TournamentId,
+            tournament: &mut TournamentOf<T>,
+            champion: T::AccountId,
+        ) -> DispatchResult {
+            let eliminated = Eliminated::<T>::take(tournament_id);
+            let standings = core::iter::once(champion.clone()).chain(eliminated.into_iter().rev());
+            for (index, (who, share)) in standings.zip(tournament.prize_shares.iter()).enumerate() {
+                let amount = share.mul_floor(tournament.prize_pool);
+                if amount.is_zero() {
+                    continue;
+                }
+                T::Rewards::accrue(&who, RewardReason::Tournament, amount)?;
+                Self::deposit_event(Event::PrizePaid {
+                    tournament_id,
+                    who,
+                    place: index as u32 + 1,
+                    amount,
+                });
+            }
+
+            Brackets::<T>::remove(tournament_id);
+            tournament.pending_matches = 0;
+            tournament.status = TournamentStatus::Finished {
+                champion: champion.clone(),
+            };
+            Self::deposit_event(Event::TournamentFinished {
+                tournament_id,
+                champion,
+            });
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{mock::*, Brackets, Error, Event, TournamentId, TournamentStatus, Tournaments};
+use frame_support::{assert_noop, assert_ok, traits::ConstU32, BoundedVec};
+use pallet_rewards::RewardReason;
+use pallet_user_profile::{UserStatsHandler, UserStatsMap};
+use sp_runtime::{DispatchError, Percent};
+
+fn shares(percents: Vec<u8>) -> BoundedVec<Percent, ConstU32<4>> {
+    BoundedVec::try_from(
+        percents
+            .into_iter()
+            .map(Percent::from_percent)
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+/// Record `wins` won races for `who`, the way the race pallet does
+fn set_wins(who: u64, wins: u32) {
+    for _ in 0..wins {
+        UserProfile::record_race(&who, true, 1_000);
+    }
+}
+
+/// Create tournament 0 for up to 8 players, registration until block 10, paying 1000 split
+/// 50 / 30 / 20
+fn create() -> TournamentId {
+    assert_ok!(Tournament::create_tournament(
+        RuntimeOrigin::root(),
+        10,
+        8,
+        1_000,
+        shares(vec![50, 30, 20])
+    ));
+    0
+}
+
+/// Tournament prizes `who` can claim from the rewards pallet
+fn prize(who: u64) -> u64 {
+    pallet_rewards::Rewards::<Test>::get(who, RewardReason::Tournament)
+}
+
+fn bracket(id: TournamentId) -> Vec<Option<u64>> {
+    Brackets::<Test>::get(id).unwrap().into_inner()
+}
+
+#[test]
+fn seeded_order_keeps_top_seeds_apart() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Tournament::seeded_order(2), vec![0, 1]);
+        assert_eq!(Tournament::seeded_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+    });
+}
+
+#[test]
+fn create_tournament_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Tournament::create_tournament(RuntimeOrigin::signed(1), 10, 8, 1_000, shares(vec![])),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Tournament::create_tournament(RuntimeOrigin::root(), 10, 16, 1_000, shares(vec![])),
+            Error::<Test>::InvalidPlayerLimit
+        );
+        assert_noop!(
+            Tournament::create_tournament(RuntimeOrigin::root(), 10, 1, 1_000, shares(vec![])),
+            Error::<Test>::InvalidPlayerLimit
+        );
+        assert_noop!(
+            Tournament::create_tournament(
+                RuntimeOrigin::root(),
+                10,
+                8,
+                1_000,
+                shares(vec![60, 50])
+            ),
+            Error::<Test>::InvalidPrizeShares
+        );
+        assert_noop!(
+            Tournament::create_tournament(RuntimeOrigin::root(), 1, 8, 1_000, shares(vec![])),
+            Error::<Test>::RegistrationClosed
+        );
+
+        create();
+        let tournament = Tournaments::<Test>::get(0).unwrap();
+        assert_eq!(tournament.status, TournamentStatus::Registration);
+        System::assert_last_event(
+            Event::TournamentCreated {
+                tournament_id: 0,
+                registration_ends: 10,
+                max_players: 8,
+                prize_pool: 1_000,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn register_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Tournament::create_tournament(
+            RuntimeOrigin::root(),
+            10,
+            2,
+            1_000,
+            shares(vec![100])
+        ));
+
+        assert_noop!(
+            Tournament::register(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::TournamentNotFound
+        );
+        assert_ok!(Tournament::register(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(
+            Event::PlayerRegistered {
+                tournament_id: 0,
+                who: 1,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Tournament::register(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::AlreadyRegistered
+        );
+        assert_ok!(Tournament::register(RuntimeOrigin::signed(2), 0));
+        assert_noop!(
+            Tournament::register(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::TournamentFull
+        );
+        assert_eq!(
+            Tournaments::<Test>::get(0).unwrap().players.into_inner(),
+            vec![1, 2]
+        );
+
+        create();
+        System::set_block_number(11);
+        assert_noop!(
+            Tournament::register(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::RegistrationClosed
+        );
+    });
+}
+
+#[test]
+fn tournament_is_played_to_the_final() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create();
+        for (who, wins) in [(1, 0), (2, 5), (3, 3), (4, 5), (5, 1)] {
+            set_wins(who, wins);
+            assert_ok!(Tournament::register(RuntimeOrigin::signed(who), 0));
+        }
+
+        assert_noop!(
+            Tournament::start_tournament(RuntimeOrigin::signed(9), 0),
+            Error::<Test>::RegistrationOpen
+        );
+        System::set_block_number(11);
+        assert_ok!(Tournament::start_tournament(RuntimeOrigin::signed(9), 0));
+
+        // Seeds 2, 4, 3, 5, 1: the top three get a bye
+        let tournament = Tournaments::<Test>::get(0).unwrap();
+        assert_eq!(tournament.players.into_inner(), vec![2, 4, 3, 5, 1]);
+        assert_eq!(tournament.status, TournamentStatus::Running { round: 0 });
+        assert_eq!(tournament.pending_matches, 1);
+        // Entries are counted once the tournament starts
+        for who in 1..=5 {
+            assert_eq!(
+                UserStatsMap::<Test>::get(who).unwrap().tournament_entries,
+                1
+            );
+        }
+        assert_eq!(
+            bracket(0),
+            vec![
+                Some(2),
+                None,
+                Some(5),
+                Some(1),
+                Some(4),
+                None,
+                Some(3),
+                None
+            ]
+        );
+
+        assert_noop!(
+            Tournament::submit_match_result(RuntimeOrigin::signed(5), 0, 1, 5),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Tournament::submit_match_result(RuntimeOrigin::root(), 0, 0, 2),
+            Error::<Test>::InvalidMatch
+        );
+        assert_noop!(
+            Tournament::submit_match_result(RuntimeOrigin::root(), 0, 1, 2),
+            Error::<Test>::NotInMatch
+        );
+        assert_ok!(Tournament::submit_match_result(
+            RuntimeOrigin::root(),
+            0,
+            1,
+            5
+        ));
+        System::assert_has_event(
+            Event::MatchDecided {
+                tournament_id: 0,
+                round: 0,
+                match_index: 1,
+                winner: 5,
+                loser: 1,
+            }
+            .into(),
+        );
+
+        // Round 1 starts automatically
+        assert_eq!(bracket(0), vec![Some(2), Some(5), Some(4), Some(3)]);
+        assert_ok!(Tournament::submit_match_result(
+            RuntimeOrigin::root(),
+            0,
+            0,
+            5
+        ));
+        assert_noop!(
+            Tournament::submit_match_result(RuntimeOrigin::root(), 0, 0, 5),
+            Error::<Test>::MatchAlreadyDecided
+        );
+        assert_ok!(Tournament::submit_match_result(
+            RuntimeOrigin::root(),
+            0,
+            1,
+            4
+        ));
+
+        assert_eq!(bracket(0), vec![Some(5), Some(4)]);
+        assert_ok!(Tournament::submit_match_result(
+            RuntimeOrigin::root(),
+            0,
+            0,
+            4
+        ));
+
+        // Standings 4, 5, 2, 3, 1
+        assert_eq!(
+            Tournaments::<Test>::get(0).unwrap().status,
+            TournamentStatus::Finished { champion: 4 }
+        );
+        assert_eq!(prize(4), 500);
+        assert_eq!(prize(5), 300);
+        assert_eq!(prize(2), 200);
+        assert_eq!(prize(3), 0);
+        assert!(Brackets::<Test>::get(0).is_none());
+        System::assert_has_event(
+            Event::PrizePaid {
+                tournament_id: 0,
+                who: 2,
+                place: 3,
+                amount: 200,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::TournamentFinished {
+                tournament_id: 0,
+                champion: 4,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Tournament::submit_match_result(RuntimeOrigin::root(), 0, 0, 4),
+            Error::<Test>::TournamentNotRunning
+        );
+    });
+}
+
+#[test]
+fn under_filled_tournament_is_cancelled() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create();
+        assert_ok!(Tournament::register(RuntimeOrigin::signed(1), 0));
+
+        System::set_block_number(11);
+        assert_ok!(Tournament::start_tournament(RuntimeOrigin::signed(1), 0));
+        assert_eq!(
+            Tournaments::<Test>::get(0).unwrap().status,
+            TournamentStatus::Cancelled
+        );
+        System::assert_last_event(Event::TournamentCancelled { tournament_id: 0 }.into());
+        assert!(UserStatsMap::<Test>::get(1).is_none());
+        assert_noop!(
+            Tournament::start_tournament(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::RegistrationClosed
+        );
+    });
+}
+
+#[test]
+fn players_cannot_raise_their_own_seed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create();
+        set_wins(2, 3);
+        for who in [1, 2] {
+            assert_ok!(Tournament::register(RuntimeOrigin::signed(who), 0));
+        }
+        assert_noop!(
+            UserProfile::update_stats(RuntimeOrigin::signed(1), 1, None, Some(100), None, None),
+            DispatchError::BadOrigin
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Tournament::start_tournament(RuntimeOrigin::signed(1), 0));
+        assert_eq!(
+            Tournaments::<Test>::get(0).unwrap().players.into_inner(),
+            vec![2, 1]
+        );
+    });
+}
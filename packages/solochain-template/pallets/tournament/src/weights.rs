@@ -0,0 +1,117 @@
+//! Autogenerated weights for pallet_tournament
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-01-01, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `localhost`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_tournament.
+pub trait WeightInfo {
+	fn create_tournament() -> Weight;
+	fn register(n: u32, ) -> Weight;
+	fn start_tournament(n: u32, ) -> Weight;
+	fn submit_match_result(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_tournament using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Tournament NextTournamentId (r:1 w:1)
+	/// Storage: Tournament Tournaments (r:0 w:1)
+	fn create_tournament() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tournament Tournaments (r:1 w:1)
+	/// The range of component `n` is `[1, 64]`.
+	fn register(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(95_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tournament Tournaments (r:1 w:1)
+	/// Storage: UserProfile UserStatsMap (r:64 w:0)
+	/// Storage: Tournament Brackets (r:0 w:1)
+	/// The range of component `n` is `[2, 64]`.
+	fn start_tournament(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 23_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(2_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tournament Tournaments (r:1 w:1)
+	/// Storage: Tournament Brackets (r:1 w:1)
+	/// Storage: Tournament MatchWinners (r:32 w:32)
+	/// Storage: Tournament Eliminated (r:1 w:1)
+	/// Storage: Rewards Rewards (r:8 w:8)
+	/// Storage: Rewards RewardHistory (r:8 w:8)
+	/// The range of component `n` is `[2, 64]`.
+	fn submit_match_result(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 70_000_000 picoseconds.
+		Weight::from_parts(70_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(3_400_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_tournament() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn register(n: u32, ) -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(Weight::from_parts(95_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn start_tournament(n: u32, ) -> Weight {
+		Weight::from_parts(23_000_000, 0)
+			.saturating_add(Weight::from_parts(2_900_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn submit_match_result(n: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 0)
+			.saturating_add(Weight::from_parts(3_400_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
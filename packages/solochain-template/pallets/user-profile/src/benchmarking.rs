@@ -40,7 +40,7 @@ benchmarks! {
         let caller: T::AccountId = account("caller", 0, SEED);
         // No need to create profile, stats can be updated independently
         UserStatsMap::<T>::insert(&caller, UserStats::default());
-    }: _(RawOrigin::Signed(caller.clone()), Some(10), Some(5), Some(1000), Some(5000))
+    }: _(RawOrigin::Root, caller.clone(), Some(10), Some(5), Some(1000), Some(5000))
     verify {
         let stats = UserStatsMap::<T>::get(&caller).unwrap();
         assert_eq!(stats.total_races, 10);
@@ -4,6 +4,7 @@ pub use pallet::*;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -11,6 +12,8 @@ mod tests;
 pub mod weights;
 pub use weights::*;
 
+use frame_support::{traits::Get, weights::Weight};
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -20,7 +23,11 @@ pub mod pallet {
     #[cfg(not(feature = "std"))]
     use sp_std::vec::Vec;
 
+    /// Version 1 adds `tournament_entries` to `UserStats`
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -33,6 +40,10 @@ pub mod pallet {
         /// Maximum length of bio
         #[pallet::constant]
         type MaxBioLength: Get<u32>;
+        /// Receives player activity for quest conditions
+        type QuestProgress: QuestProgress<Self::AccountId>;
+        /// Notified of the new totals when game pallets record activity
+        type OnStatsUpdated: OnStatsUpdated<Self::AccountId>;
     }
 
     /// User profile information
@@ -55,6 +66,31 @@ pub mod pallet {
         pub wins: u32,
         pub total_distance: u64,
         pub total_rewards: u128,
+        pub tournament_entries: u32,
+    }
+
+    /// Player activity counted towards quest conditions
+    #[derive(
+        Clone,
+        Copy,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum Metric {
+        /// Username set
+        UsernameSet,
+        /// Race finished
+        RacesCompleted,
+        /// Race won
+        RacesWon,
+        /// Distance ridden in races
+        DistanceRidden,
     }
 
     #[pallet::storage]
@@ -100,6 +136,7 @@ pub mod pallet {
             wins: u32,
             total_distance: u64,
             total_rewards: u128,
+            tournament_entries: u32,
         },
     }
 
@@ -119,7 +156,9 @@ pub mod pallet {
         /// Creates profile automatically if it doesn't exist
         /// Account is automatically created by frame_system on first transaction
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::set_username())]
+        #[pallet::weight(
+            T::WeightInfo::set_username().saturating_add(T::QuestProgress::record_weight())
+        )]
         pub fn set_username(origin: OriginFor<T>, username: Vec<u8>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -156,6 +195,7 @@ pub mod pallet {
             // Store profile and username mapping
             Profiles::<T>::insert(&who, profile);
             UsernameToAccount::<T>::insert(&username_bounded, &who);
+            T::QuestProgress::record(&who, Metric::UsernameSet, 1);
 
             Self::deposit_event(Event::UsernameSet {
                 account: who,
@@ -245,18 +285,20 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Update user statistics (typically called by other pallets)
-        /// AccountId is from frame_system, no need to check if account exists
+        /// Correct the statistics of `who` (root only)
+        /// Game pallets record activity through `UserStatsHandler`; quests and achievements
+        /// trust these totals, so players cannot set them themselves
         #[pallet::call_index(2)]
         #[pallet::weight(T::WeightInfo::update_stats())]
         pub fn update_stats(
             origin: OriginFor<T>,
+            who: T::AccountId,
             total_races: Option<u32>,
             wins: Option<u32>,
             total_distance: Option<u64>,
             total_rewards: Option<u128>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            ensure_root(origin)?;
 
             // Get current stats or default
             let mut stats = UserStatsMap::<T>::get(&who).unwrap_or_default();
@@ -284,9 +326,139 @@ pub mod pallet {
                 wins: stats.wins,
                 total_distance: stats.total_distance,
                 total_rewards: stats.total_rewards,
+                tournament_entries: stats.tournament_entries,
             });
 
             Ok(())
         }
     }
 }
+
+/// Hook for other game pallets to record activity in `UserStats`
+pub trait UserStatsHandler<AccountId> {
+    /// Record a finished race and whether it was won
+    fn record_race(who: &AccountId, won: bool, distance: u64);
+    /// Record a claimed reward
+    fn record_reward(who: &AccountId, reward: u128);
+    /// Record a tournament entry
+    fn record_tournament_entry(who: &AccountId);
+    /// Worst-case weight of recording any activity
+    fn record_weight() -> Weight;
+}
+
+/// Read access to player statistics for other game pallets
+pub trait UserStatsInspect<AccountId> {
+    /// Statistics of `who`, empty if they never raced
+    fn stats(who: &AccountId) -> UserStats;
+    /// Whether `who` has created a profile
+    fn has_profile(who: &AccountId) -> bool;
+    /// Overwrite the statistics of `who` to set up benchmarks
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_stats(who: &AccountId, stats: UserStats);
+}
+
+/// Hook for the quest logic to follow player activity
+pub trait QuestProgress<AccountId> {
+    /// Record `amount` more of `metric` for `who`
+    fn record(who: &AccountId, metric: Metric, amount: u64);
+    /// Worst-case weight of `record`
+    fn record_weight() -> Weight;
+}
+
+impl<AccountId> QuestProgress<AccountId> for () {
+    fn record(_who: &AccountId, _metric: Metric, _amount: u64) {}
+
+    fn record_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Hook called with the new statistics of an account after game pallets record activity
+pub trait OnStatsUpdated<AccountId> {
+    /// Statistics of `who` changed to `stats`
+    fn on_stats_updated(who: &AccountId, stats: &UserStats);
+    /// Worst-case weight of `on_stats_updated`
+    fn on_stats_updated_weight() -> Weight;
+}
+
+impl<AccountId> OnStatsUpdated<AccountId> for () {
+    fn on_stats_updated(_who: &AccountId, _stats: &UserStats) {}
+
+    fn on_stats_updated_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl<AccountId> UserStatsHandler<AccountId> for () {
+    fn record_race(_who: &AccountId, _won: bool, _distance: u64) {}
+    fn record_reward(_who: &AccountId, _reward: u128) {}
+    fn record_tournament_entry(_who: &AccountId) {}
+
+    fn record_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Apply `update` to the statistics of `who` and announce the new totals
+    fn mutate_stats(who: &T::AccountId, update: impl FnOnce(&mut UserStats)) {
+        let mut stats = UserStatsMap::<T>::get(who).unwrap_or_default();
+        update(&mut stats);
+        UserStatsMap::<T>::insert(who, stats.clone());
+        T::OnStatsUpdated::on_stats_updated(who, &stats);
+
+        Self::deposit_event(Event::StatsUpdated {
+            account: who.clone(),
+            total_races: stats.total_races,
+            wins: stats.wins,
+            total_distance: stats.total_distance,
+            total_rewards: stats.total_rewards,
+            tournament_entries: stats.tournament_entries,
+        });
+    }
+}
+
+impl<T: Config> UserStatsHandler<T::AccountId> for Pallet<T> {
+    fn record_race(who: &T::AccountId, won: bool, distance: u64) {
+        Self::mutate_stats(who, |stats| {
+            stats.total_races = stats.total_races.saturating_add(1);
+            if won {
+                stats.wins = stats.wins.saturating_add(1);
+            }
+            stats.total_distance = stats.total_distance.saturating_add(distance);
+        });
+    }
+
+    fn record_reward(who: &T::AccountId, reward: u128) {
+        Self::mutate_stats(who, |stats| {
+            stats.total_rewards = stats.total_rewards.saturating_add(reward);
+        });
+    }
+
+    fn record_tournament_entry(who: &T::AccountId) {
+        Self::mutate_stats(who, |stats| {
+            stats.tournament_entries = stats.tournament_entries.saturating_add(1);
+        });
+    }
+
+    fn record_weight() -> Weight {
+        T::DbWeight::get()
+            .reads_writes(1, 1)
+            .saturating_add(T::OnStatsUpdated::on_stats_updated_weight())
+    }
+}
+
+impl<T: Config> UserStatsInspect<T::AccountId> for Pallet<T> {
+    fn stats(who: &T::AccountId) -> UserStats {
+        UserStatsMap::<T>::get(who).unwrap_or_default()
+    }
+
+    fn has_profile(who: &T::AccountId) -> bool {
+        Profiles::<T>::contains_key(who)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_stats(who: &T::AccountId, stats: UserStats) {
+        UserStatsMap::<T>::insert(who, stats);
+    }
+}
@@ -0,0 +1,71 @@
+//! Storage migrations of the user profile pallet.
+
+pub mod v1 {
+    use crate::{Config, Pallet, UserStats, UserStatsMap};
+    use codec::Decode;
+    #[cfg(feature = "try-runtime")]
+    use codec::Encode;
+    #[cfg(feature = "try-runtime")]
+    use frame_support::{ensure, sp_runtime::TryRuntimeError};
+    use frame_support::{
+        migrations::VersionedMigration,
+        traits::{Get, UncheckedOnRuntimeUpgrade},
+        weights::Weight,
+    };
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// `UserStats` before tournament registrations were counted
+    #[derive(Decode)]
+    pub struct OldUserStats {
+        pub total_races: u32,
+        pub wins: u32,
+        pub total_distance: u64,
+        pub total_rewards: u128,
+    }
+
+    /// Adds a zero `tournament_entries` to the stored statistics
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            UserStatsMap::<T>::translate::<OldUserStats, _>(|_, old| {
+                translated += 1;
+                Some(UserStats {
+                    total_races: old.total_races,
+                    wins: old.wins,
+                    total_distance: old.total_distance,
+                    total_rewards: old.total_rewards,
+                    tournament_entries: 0,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((UserStatsMap::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let count = u64::decode(&mut &state[..])
+                .map_err(|_| TryRuntimeError::Other("invalid pre-upgrade state"))?;
+            ensure!(
+                UserStatsMap::<T>::iter_values().count() as u64 == count,
+                "user stats lost in migration"
+            );
+            Ok(())
+        }
+    }
+
+    /// Migrates the statistics from storage version 0 to 1
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
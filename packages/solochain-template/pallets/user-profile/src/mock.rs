@@ -38,6 +38,8 @@ impl pallet_user_profile::Config for Test {
     type WeightInfo = ();
     type MaxUsernameLength = frame_support::traits::ConstU32<32>;
     type MaxBioLength = frame_support::traits::ConstU32<256>;
+    type QuestProgress = ();
+    type OnStatsUpdated = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
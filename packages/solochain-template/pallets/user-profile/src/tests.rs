@@ -1,5 +1,13 @@
-use crate::{mock::*, Error, Event, Profiles, UserStats, UserStatsMap};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations::v1::MigrateV0ToV1, mock::*, Error, Event, Profiles, UserStats, UserStatsHandler,
+    UserStatsMap,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    sp_runtime::DispatchError,
+    storage::unhashed,
+    traits::{OnRuntimeUpgrade, StorageVersion},
+};
 
 #[test]
 fn set_username_works() {
@@ -92,7 +100,8 @@ fn update_stats_works() {
 
         // Update stats (no need to register, account exists from frame_system)
         assert_ok!(UserProfile::update_stats(
-            RuntimeOrigin::signed(account),
+            RuntimeOrigin::root(),
+            account,
             Some(10),
             Some(5),
             Some(1000),
@@ -107,3 +116,98 @@ fn update_stats_works() {
         assert_eq!(stats.total_rewards, 5000);
     });
 }
+
+#[test]
+fn players_cannot_set_their_own_stats() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            UserProfile::update_stats(RuntimeOrigin::signed(1), 1, Some(10), Some(10), None, None),
+            DispatchError::BadOrigin
+        );
+        assert!(UserStatsMap::<Test>::get(1).is_none());
+    });
+}
+
+#[test]
+fn record_race_accumulates_stats() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let account = 1;
+
+        <UserProfile as UserStatsHandler<u64>>::record_race(&account, true, 1200);
+        <UserProfile as UserStatsHandler<u64>>::record_race(&account, false, 800);
+
+        let stats = UserStatsMap::<Test>::get(&account).unwrap();
+        assert_eq!(stats.total_races, 2);
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.total_distance, 2000);
+        assert_eq!(stats.total_rewards, 0);
+
+        System::assert_last_event(
+            Event::StatsUpdated {
+                account,
+                total_races: 2,
+                wins: 1,
+                total_distance: 2000,
+                total_rewards: 0,
+                tournament_entries: 0,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn record_reward_only_counts_rewards() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        <UserProfile as UserStatsHandler<u64>>::record_race(&1, true, 1200);
+        <UserProfile as UserStatsHandler<u64>>::record_reward(&1, 25);
+
+        let stats = UserStatsMap::<Test>::get(1).unwrap();
+        assert_eq!(stats.total_races, 1);
+        assert_eq!(stats.total_rewards, 25);
+    });
+}
+
+#[test]
+fn record_tournament_entry_counts_entries() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        <UserProfile as UserStatsHandler<u64>>::record_tournament_entry(&1);
+        <UserProfile as UserStatsHandler<u64>>::record_tournament_entry(&1);
+
+        let stats = UserStatsMap::<Test>::get(1).unwrap();
+        assert_eq!(stats.tournament_entries, 2);
+        assert_eq!(stats.total_races, 0);
+    });
+}
+
+#[test]
+fn migration_to_v1_adds_tournament_entries() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<UserProfile>();
+        // Statistics as stored before `tournament_entries` existed
+        unhashed::put(
+            &UserStatsMap::<Test>::hashed_key_for(1),
+            &(10u32, 4u32, 5_000u64, 300u128),
+        );
+
+        MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(
+            UserStatsMap::<Test>::get(1),
+            Some(UserStats {
+                total_races: 10,
+                wins: 4,
+                total_distance: 5_000,
+                total_rewards: 300,
+                tournament_entries: 0,
+            })
+        );
+        assert_eq!(StorageVersion::get::<UserProfile>(), 1);
+    });
+}
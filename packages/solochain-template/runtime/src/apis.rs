@@ -25,8 +25,10 @@
 
 // External crates imports
 use alloc::vec::Vec;
+use codec::{Decode, Encode};
 use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
+	traits::{fungible, fungibles},
 	weights::Weight,
 };
 use pallet_grandpa::AuthorityId as GrandpaId;
@@ -42,10 +44,48 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-	RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
+	configs::BicyCurrency, AccountId, Achievements, Assets, Aura, Balance, Balances, Block,
+	BlockNumber, Executive, Grandpa, InherentDataExt, Nonce, Runtime, RuntimeCall,
+	RuntimeGenesisConfig, SessionKeys, Skill, System, TransactionPayment, Vesting, BICY_ASSET_ID,
+	VERSION,
 };
 
+/// Spendable balances of an account in the native token and the BICY reward token.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo, sp_runtime::RuntimeDebug)]
+pub struct TokenBalances<Balance> {
+	pub native: Balance,
+	pub bicy: Balance,
+}
+
+/// BICY of an account held by vesting schedules.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo, sp_runtime::RuntimeDebug)]
+pub struct VestingBalances<Balance> {
+	/// Balance that has not vested yet
+	pub locked: Balance,
+	/// Vested balance still under the vesting lock, freed by calling `vest`
+	pub claimable: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Runtime API for querying game token balances.
+	pub trait TokenBalancesApi<AccountId, Balance> where
+		AccountId: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// Spendable native and BICY balances of `who`
+		fn balances(who: AccountId) -> TokenBalances<Balance>;
+	}
+
+	/// Runtime API for querying vested rewards.
+	pub trait VestingApi<AccountId, Balance> where
+		AccountId: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// Locked and claimable vesting balances of `who`
+		fn vesting_balances(who: AccountId) -> VestingBalances<Balance>;
+	}
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -220,6 +260,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl TokenBalancesApi<Block, AccountId, Balance> for Runtime {
+		fn balances(who: AccountId) -> TokenBalances<Balance> {
+			use frame_support::traits::tokens::{Fortitude, Preservation};
+
+			TokenBalances {
+				native: <Balances as fungible::Inspect<AccountId>>::reducible_balance(
+					&who,
+					Preservation::Preserve,
+					Fortitude::Polite,
+				),
+				bicy: <Assets as fungibles::Inspect<AccountId>>::reducible_balance(
+					BICY_ASSET_ID,
+					&who,
+					Preservation::Expendable,
+					Fortitude::Polite,
+				),
+			}
+		}
+	}
+
+	impl VestingApi<Block, AccountId, Balance> for Runtime {
+		fn vesting_balances(who: AccountId) -> VestingBalances<Balance> {
+			use frame_support::traits::VestingSchedule;
+
+			let locked = <Vesting as VestingSchedule<AccountId>>::vesting_balance(&who)
+				.unwrap_or_default();
+			let lock = BicyCurrency::vesting_lock(&who);
+
+			VestingBalances { locked, claimable: lock.saturating_sub(locked) }
+		}
+	}
+
+	impl pallet_skill::runtime_api::SkillApi<Block> for Runtime {
+		fn effective_stats(
+			character_id: pallet_character::CharacterId,
+			map: Option<u32>,
+		) -> Option<pallet_character::CharacterStats> {
+			Skill::effective_stats(character_id, map)
+		}
+	}
+
+	impl pallet_achievements::runtime_api::AchievementsApi<Block, AccountId, BlockNumber> for Runtime {
+		fn badges(who: AccountId) -> Vec<pallet_achievements::BadgeInfo<BlockNumber>> {
+			Achievements::badges_of(&who)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -31,4 +31,17 @@ frame_benchmarking::define_benchmarks!(
 	[pallet_timestamp, Timestamp]
 	[pallet_sudo, Sudo]
 	[pallet_template, Template]
+	[pallet_bike, Bike]
+	[pallet_character, Character]
+	[pallet_skill, Skill]
+	[pallet_race, Race]
+	[pallet_tournament, Tournament]
+	[pallet_ranked, Ranked]
+	[pallet_rewards, Rewards]
+	[pallet_vesting, Vesting]
+	[pallet_airdrop, Airdrop]
+	[pallet_quest, Quest]
+	[pallet_achievements, Achievements]
+	[pallet_check_in, CheckIn]
+	[pallet_sponsorship, Sponsorship]
 );
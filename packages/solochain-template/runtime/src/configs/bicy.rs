@@ -0,0 +1,233 @@
+// This is free and unencumbered software released into the public domain.
+//
+// Anyone is free to copy, modify, publish, use, compile, sell, or
+// distribute this software, either in source code form or as a compiled
+// binary, for any purpose, commercial or non-commercial, and by any
+// means.
+//
+// In jurisdictions that recognize copyright laws, the author or authors
+// of this software dedicate any and all copyright interest in the
+// software to the public domain. We make this dedication for the benefit
+// of the public at large and to the detriment of our heirs and
+// successors. We intend this dedication to be an overt act of
+// relinquishment in perpetuity of all present and future rights to this
+// software under copyright law.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+// IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR ANY CLAIM, DAMAGES OR
+// OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE,
+// ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR
+// OTHER DEALINGS IN THE SOFTWARE.
+//
+// For more information, please refer to <http://unlicense.org>
+
+//! BICY exposed through the `Currency` traits, so that it can be vested by pallet_vesting.
+
+use frame_support::{
+    ensure,
+    traits::{
+        fungible::{self, Balanced, Inspect, Mutate},
+        fungibles::{InspectFreeze, MutateFreeze},
+        tokens::{Fortitude, Precision, Preservation},
+        ConstU32, Currency, ExistenceRequirement, Imbalance, LockIdentifier, LockableCurrency,
+        SignedImbalance, WithdrawReasons,
+    },
+};
+use pallet_assets::FrozenBalance;
+use sp_runtime::{traits::Zero, DispatchError, DispatchResult, TokenError};
+
+use super::BicyToken;
+use crate::{AccountId, AssetsFreezer, Balance, BlockNumber, RuntimeFreezeReason, BICY_ASSET_ID};
+
+type PositiveImbalance = fungible::Debt<AccountId, BicyToken>;
+type NegativeImbalance = fungible::Credit<AccountId, BicyToken>;
+
+/// Freeze backing the vesting lock
+const VESTING_FREEZE: RuntimeFreezeReason =
+    RuntimeFreezeReason::Rewards(pallet_rewards::FreezeReason::Vesting);
+
+/// BICY as a lockable currency. Vesting is the only lock on BICY, so every lock maps to the
+/// vesting freeze of the assets freezer pallet.
+pub struct BicyCurrency;
+
+impl BicyCurrency {
+    /// BICY of `who` frozen by the vesting lock
+    pub fn vesting_lock(who: &AccountId) -> Balance {
+        <AssetsFreezer as InspectFreeze<AccountId>>::balance_frozen(
+            BICY_ASSET_ID,
+            &VESTING_FREEZE,
+            who,
+        )
+    }
+
+    /// BICY of `who` frozen for any reason
+    fn frozen(who: &AccountId) -> Balance {
+        <AssetsFreezer as FrozenBalance<u32, AccountId, Balance>>::frozen_balance(
+            BICY_ASSET_ID,
+            who,
+        )
+        .unwrap_or_default()
+    }
+
+    fn preservation(existence: ExistenceRequirement) -> Preservation {
+        match existence {
+            ExistenceRequirement::KeepAlive => Preservation::Preserve,
+            ExistenceRequirement::AllowDeath => Preservation::Expendable,
+        }
+    }
+}
+
+impl Currency<AccountId> for BicyCurrency {
+    type Balance = Balance;
+    type PositiveImbalance = PositiveImbalance;
+    type NegativeImbalance = NegativeImbalance;
+
+    fn total_balance(who: &AccountId) -> Balance {
+        <BicyToken as Inspect<AccountId>>::total_balance(who)
+    }
+
+    fn can_slash(who: &AccountId, value: Balance) -> bool {
+        Self::free_balance(who) >= value
+    }
+
+    fn total_issuance() -> Balance {
+        <BicyToken as Inspect<AccountId>>::total_issuance()
+    }
+
+    fn minimum_balance() -> Balance {
+        <BicyToken as Inspect<AccountId>>::minimum_balance()
+    }
+
+    fn burn(amount: Balance) -> PositiveImbalance {
+        <BicyToken as Balanced<AccountId>>::rescind(amount)
+    }
+
+    fn issue(amount: Balance) -> NegativeImbalance {
+        <BicyToken as Balanced<AccountId>>::issue(amount)
+    }
+
+    fn free_balance(who: &AccountId) -> Balance {
+        <BicyToken as Inspect<AccountId>>::balance(who)
+    }
+
+    fn ensure_can_withdraw(
+        who: &AccountId,
+        _amount: Balance,
+        _reasons: WithdrawReasons,
+        new_balance: Balance,
+    ) -> DispatchResult {
+        ensure!(new_balance >= Self::frozen(who), TokenError::Frozen);
+        Ok(())
+    }
+
+    fn transfer(
+        source: &AccountId,
+        dest: &AccountId,
+        value: Balance,
+        existence_requirement: ExistenceRequirement,
+    ) -> DispatchResult {
+        <BicyToken as Mutate<AccountId>>::transfer(
+            source,
+            dest,
+            value,
+            Self::preservation(existence_requirement),
+        )
+        .map(|_| ())
+    }
+
+    fn slash(who: &AccountId, value: Balance) -> (NegativeImbalance, Balance) {
+        let slashed = <BicyToken as Balanced<AccountId>>::withdraw(
+            who,
+            value,
+            Precision::BestEffort,
+            Preservation::Expendable,
+            Fortitude::Force,
+        )
+        .unwrap_or_else(|_| NegativeImbalance::zero());
+        let remaining = value.saturating_sub(slashed.peek());
+        (slashed, remaining)
+    }
+
+    fn deposit_into_existing(
+        who: &AccountId,
+        value: Balance,
+    ) -> Result<PositiveImbalance, DispatchError> {
+        ensure!(
+            !Self::total_balance(who).is_zero(),
+            TokenError::CannotCreate
+        );
+        <BicyToken as Balanced<AccountId>>::deposit(who, value, Precision::Exact)
+    }
+
+    fn deposit_creating(who: &AccountId, value: Balance) -> PositiveImbalance {
+        <BicyToken as Balanced<AccountId>>::deposit(who, value, Precision::Exact)
+            .unwrap_or_else(|_| PositiveImbalance::zero())
+    }
+
+    fn withdraw(
+        who: &AccountId,
+        value: Balance,
+        reasons: WithdrawReasons,
+        liveness: ExistenceRequirement,
+    ) -> Result<NegativeImbalance, DispatchError> {
+        let new_balance = Self::free_balance(who)
+            .checked_sub(value)
+            .ok_or(TokenError::FundsUnavailable)?;
+        Self::ensure_can_withdraw(who, value, reasons, new_balance)?;
+        <BicyToken as Balanced<AccountId>>::withdraw(
+            who,
+            value,
+            Precision::Exact,
+            Self::preservation(liveness),
+            Fortitude::Polite,
+        )
+    }
+
+    fn make_free_balance_be(
+        who: &AccountId,
+        balance: Balance,
+    ) -> SignedImbalance<Balance, PositiveImbalance> {
+        let free = Self::free_balance(who);
+        if balance >= free {
+            SignedImbalance::Positive(Self::deposit_creating(who, balance - free))
+        } else {
+            SignedImbalance::Negative(Self::slash(who, free - balance).0)
+        }
+    }
+}
+
+// Freezes only fail for more freeze reasons than the runtime defines, so the lock cannot fail.
+impl LockableCurrency<AccountId> for BicyCurrency {
+    type Moment = BlockNumber;
+    type MaxLocks = ConstU32<1>;
+
+    fn set_lock(_id: LockIdentifier, who: &AccountId, amount: Balance, _reasons: WithdrawReasons) {
+        let _ = <AssetsFreezer as MutateFreeze<AccountId>>::set_freeze(
+            BICY_ASSET_ID,
+            &VESTING_FREEZE,
+            who,
+            amount,
+        );
+    }
+
+    fn extend_lock(
+        _id: LockIdentifier,
+        who: &AccountId,
+        amount: Balance,
+        _reasons: WithdrawReasons,
+    ) {
+        let _ = <AssetsFreezer as MutateFreeze<AccountId>>::extend_freeze(
+            BICY_ASSET_ID,
+            &VESTING_FREEZE,
+            who,
+            amount,
+        );
+    }
+
+    fn remove_lock(_id: LockIdentifier, who: &AccountId) {
+        let _ =
+            <AssetsFreezer as MutateFreeze<AccountId>>::thaw(BICY_ASSET_ID, &VESTING_FREEZE, who);
+    }
+}
@@ -26,25 +26,37 @@
 // Substrate and Polkadot dependencies
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, VariantCountOf},
+    traits::{
+        fungible::ItemOf, AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
+        Contains, EitherOf, NeverEnsureOrigin, VariantCountOf, WithdrawReasons,
+    },
     weights::{
         constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
         IdentityFee, Weight,
     },
+    PalletId,
 };
 use frame_system::limits::{BlockLength, BlockWeights};
 use pallet_transaction_payment::{ConstFeeMultiplier, FungibleAdapter, Multiplier};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_runtime::{traits::One, Perbill};
+use sp_runtime::{
+    traits::{AccountIdConversion, ConvertInto, One},
+    Perbill, Percent,
+};
 use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-    AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Nonce, PalletInfo, Runtime,
+    AccountId, Achievements, Assets, AssetsFreezer, Aura, Balance, Balances, Bike, Block,
+    BlockNumber, Character, CheckIn, Hash, Nonce, PalletInfo, Quest, Ranked, Rewards, Runtime,
     RuntimeCall, RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
-    System, EXISTENTIAL_DEPOSIT, SLOT_DURATION, VERSION,
+    Skill, System, Timestamp, UserProfile, Vesting, BICY_ASSET_ID, DAYS, EXISTENTIAL_DEPOSIT,
+    HOURS, MICRO_UNIT, MILLI_UNIT, MINUTES, SLOT_DURATION, UNIT, VERSION,
 };
 
+mod bicy;
+pub use bicy::BicyCurrency;
+
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
 
 parameter_types! {
@@ -137,6 +149,50 @@ impl pallet_balances::Config for Runtime {
     type DoneSlashHandler = ();
 }
 
+parameter_types! {
+    pub const BicyAssetId: u32 = BICY_ASSET_ID;
+    /// Keyless owner and issuer of the BICY asset, so that it can only be minted by game pallets
+    pub BicyIssuer: AccountId = PalletId(*b"bicy/iss").into_account_truncating();
+    /// Keyless game treasury, funding airdrops and receiving what is left of them
+    pub GameTreasury: AccountId = PalletId(*b"bicy/trs").into_account_truncating();
+}
+
+/// BICY game reward token, minted and burned by the game pallets.
+pub type BicyToken = ItemOf<Assets, BicyAssetId, AccountId>;
+
+/// Configure the assets pallet.
+/// New assets can only be force created by root; BICY is created at genesis.
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type RemoveItemsLimit = ConstU32<1000>;
+    type AssetId = u32;
+    type AssetIdParameter = codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = ConstU128<UNIT>;
+    type AssetAccountDeposit = ConstU128<MILLI_UNIT>;
+    type MetadataDepositBase = ConstU128<MILLI_UNIT>;
+    type MetadataDepositPerByte = ConstU128<MICRO_UNIT>;
+    type ApprovalDeposit = ConstU128<MILLI_UNIT>;
+    type StringLimit = ConstU32<50>;
+    type Holder = ();
+    type Freezer = AssetsFreezer;
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+/// Configure the assets freezer pallet.
+/// Freezes BICY balances locked by vesting schedules.
+impl pallet_assets_freezer::Config for Runtime {
+    type RuntimeFreezeReason = RuntimeFreezeReason;
+    type RuntimeEvent = RuntimeEvent;
+}
+
 parameter_types! {
     pub FeeMultiplier: Multiplier = Multiplier::one();
 }
@@ -177,4 +233,255 @@ impl pallet_user_profile::Config for Runtime {
     type WeightInfo = pallet_user_profile::weights::SubstrateWeight<Runtime>;
     type MaxUsernameLength = frame_support::traits::ConstU32<32>;
     type MaxBioLength = frame_support::traits::ConstU32<256>;
+    type QuestProgress = Quest;
+    type OnStatsUpdated = Achievements;
+}
+
+/// Configure the bike pallet.
+/// Bike parts are minted by root until a dedicated game admin origin exists.
+/// Repairs burn BICY.
+impl pallet_bike::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_bike::weights::SubstrateWeight<Runtime>;
+    type MintOrigin = frame_system::EnsureRoot<AccountId>;
+    type RewardToken = BicyToken;
+    type WearPerDifficulty = ConstU32<5>;
+    type RepairCostPerPoint = ConstU128<MILLI_UNIT>;
+}
+
+/// Configure the character pallet.
+/// Characters with skill books attached cannot be transferred.
+impl pallet_character::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_character::weights::SubstrateWeight<Runtime>;
+    type MintOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxEnergy = ConstU32<100>;
+    type EnergyRegenPeriod = ConstU32<{ 3 * MINUTES }>;
+    type MaxWater = ConstU32<100>;
+    type WaterRegenPeriod = ConstU32<{ 2 * MINUTES }>;
+    type Attachments = Skill;
+}
+
+/// Configure the skill pallet.
+/// Skill books attach to characters from the character pallet.
+/// Fusion burns BICY.
+impl pallet_skill::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_skill::weights::SubstrateWeight<Runtime>;
+    type MintOrigin = frame_system::EnsureRoot<AccountId>;
+    type Characters = Character;
+    type MaxSkillSlots = ConstU32<4>;
+    type SwapCooldown = ConstU32<{ 10 * MINUTES }>;
+    type RewardToken = BicyToken;
+    type SkillsPerFusion = ConstU32<3>;
+    type MaxSkillLevel = ConstU8<5>;
+    type FusionBoostPercent = ConstU32<150>;
+    type FusionCost = ConstU128<{ 10 * MILLI_UNIT }>;
+}
+
+parameter_types! {
+    pub const SuspiciousSpeedRatio: Percent = Percent::from_percent(90);
+}
+
+/// Configure the race pallet.
+/// Any account may relay results, which must be signed by a game server key registered by root.
+/// Maps and the lobby payout curve are managed by root, which also arbitrates disputed results.
+/// Lobby placements feed the ranked ladder.
+/// Rewards are credited to the rewards pallet and claimed there in BICY.
+impl pallet_race::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_race::weights::SubstrateWeight<Runtime>;
+    type Characters = Character;
+    type Bikes = Bike;
+    type UserStats = UserProfile;
+    type Ranked = Ranked;
+    type QuestProgress = Quest;
+    type ResultOrigin = frame_system::EnsureSigned<AccountId>;
+    type ServerManagerOrigin = frame_system::EnsureRoot<AccountId>;
+    type GameAdminOrigin = frame_system::EnsureRoot<AccountId>;
+    type ArbiterOrigin = frame_system::EnsureRoot<AccountId>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type Balance = Balance;
+    type Rewards = Rewards;
+    type MaxMapNameLength = ConstU32<32>;
+    type MaxAverageSpeed = ConstU32<30>;
+    type SpeedPerBikeStat = ConstU32<500>;
+    type SuspiciousSpeedRatio = SuspiciousSpeedRatio;
+    type SuspicionThreshold = ConstU32<3>;
+    type RankedBanDuration = ConstU32<{ 7 * DAYS }>;
+    type RaceTimeout = ConstU32<HOURS>;
+    type MaxLobbyParticipants = ConstU32<16>;
+    type MinLobbyParticipants = ConstU32<2>;
+    type MaxLeaderboardSize = ConstU32<100>;
+    type ChallengePeriod = ConstU32<{ 10 * MINUTES }>;
+    type DisputeBond = ConstU128<UNIT>;
+}
+
+/// Configure the tournament pallet.
+/// Tournaments are created and their match results submitted by root; prizes are credited to
+/// the rewards pallet.
+impl pallet_tournament::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_tournament::weights::SubstrateWeight<Runtime>;
+    type TournamentOrigin = frame_system::EnsureRoot<AccountId>;
+    type ResultOrigin = frame_system::EnsureRoot<AccountId>;
+    type UserStats = UserProfile;
+    type Balance = Balance;
+    type Rewards = Rewards;
+    type MaxPlayers = ConstU32<64>;
+    type MaxPrizePlaces = ConstU32<8>;
+}
+
+parameter_types! {
+    pub const SoftResetRetention: Percent = Percent::from_percent(50);
+}
+
+/// Configure the ranked pallet.
+/// Seasons last four weeks; tiers are set by root and season rewards credited to the rewards
+/// pallet.
+impl pallet_ranked::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_ranked::weights::SubstrateWeight<Runtime>;
+    type SeasonOrigin = frame_system::EnsureRoot<AccountId>;
+    type Balance = Balance;
+    type Rewards = Rewards;
+    type SeasonLength = ConstU32<{ 28 * DAYS }>;
+    type InitialRating = ConstU32<1500>;
+    type KFactor = ConstU32<32>;
+    type SoftResetRetention = SoftResetRetention;
+    type MaxTiers = ConstU32<8>;
+}
+
+/// Configure the rewards pallet.
+/// Rewards earned in game pallets or distributed by root are minted in BICY when claimed.
+/// Root sets the emission schedule capping race, quest and check-in rewards per day. Claims above
+/// 1000 BICY vest over 30 days.
+impl pallet_rewards::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_rewards::weights::SubstrateWeight<Runtime>;
+    type DistributionOrigin = frame_system::EnsureRoot<AccountId>;
+    type EmissionOrigin = frame_system::EnsureRoot<AccountId>;
+    type RewardToken = BicyToken;
+    type UserStats = UserProfile;
+    type Vesting = Vesting;
+    type VestingThreshold = ConstU128<{ 1_000 * UNIT }>;
+    type VestingDuration = ConstU32<{ 30 * DAYS }>;
+    type MaxHistory = ConstU32<50>;
+    type BlocksPerDay = ConstU32<DAYS>;
+}
+
+parameter_types! {
+    pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+        WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+/// Configure the vesting pallet.
+/// Vests BICY, such as large claimed rewards, through freezes of the assets freezer pallet.
+impl pallet_vesting::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = BicyCurrency;
+    type BlockNumberToBalance = ConvertInto;
+    type MinVestedTransfer = ConstU128<UNIT>;
+    type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+    type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+    type BlockNumberProvider = System;
+    const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+parameter_types! {
+    pub const AirdropPalletId: PalletId = PalletId(*b"bicy/air");
+}
+
+/// Configure the airdrop pallet.
+/// Airdrops are paid in native balance from the game treasury.
+impl pallet_airdrop::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_airdrop::weights::SubstrateWeight<Runtime>;
+    type AirdropOrigin = frame_system::EnsureRoot<AccountId>;
+    type Currency = Balances;
+    type Treasury = GameTreasury;
+    type PalletId = AirdropPalletId;
+    type MaxLeaves = ConstU32<16_384>;
+    type MaxProofLength = ConstU32<16>;
+}
+
+/// Configure the quest pallet.
+/// Quest rewards are credited to the rewards pallet, drawing from the daily emission pool.
+impl pallet_quest::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_quest::weights::SubstrateWeight<Runtime>;
+    type QuestOrigin = frame_system::EnsureRoot<AccountId>;
+    type AttesterOrigin = frame_system::EnsureRoot<AccountId>;
+    type Balance = Balance;
+    type Rewards = Rewards;
+    type UserStats = UserProfile;
+    type MaxQuestNameLength = ConstU32<64>;
+    type MaxRequirements = ConstU32<8>;
+    type MaxQuestsPerMetric = ConstU32<32>;
+    type BlocksPerDay = ConstU32<DAYS>;
+}
+
+/// Configure the achievements pallet.
+/// Badges unlock from the statistics recorded by the user profile pallet.
+impl pallet_achievements::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_achievements::weights::SubstrateWeight<Runtime>;
+    type AchievementOrigin = frame_system::EnsureRoot<AccountId>;
+    type UserStats = UserProfile;
+    type MaxAchievements = ConstU32<64>;
+    type MaxNameLength = ConstU32<64>;
+}
+
+/// Configure the check-in pallet.
+/// Check-in days follow the timestamp pallet; rewards grow by 5 mBICY per day of the streak,
+/// up to 50 mBICY, and draw from the daily emission pool.
+impl pallet_check_in::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_check_in::weights::SubstrateWeight<Runtime>;
+    type Balance = Balance;
+    type Rewards = Rewards;
+    type TimeProvider = Timestamp;
+    type BaseReward = ConstU128<{ 10 * MILLI_UNIT }>;
+    type StreakBonus = ConstU128<{ 5 * MILLI_UNIT }>;
+    type MaxReward = ConstU128<{ 50 * MILLI_UNIT }>;
+}
+
+/// Game calls whose transaction fees are waived for players with a profile.
+/// `set_username` is only free for renames: creating the profile is paid.
+pub struct FeelessGameCalls;
+
+impl Contains<RuntimeCall> for FeelessGameCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::Race(pallet_race::Call::start_race { .. })
+                | RuntimeCall::Race(pallet_race::Call::claim_race_reward { .. })
+                | RuntimeCall::CheckIn(pallet_check_in::Call::check_in { .. })
+                | RuntimeCall::UserProfile(pallet_user_profile::Call::set_username { .. })
+        )
+    }
+}
+
+/// Configure the feeless pallet.
+/// Players get up to 4 feeless game transactions per block.
+impl pallet_feeless::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type FeelessCalls = FeelessGameCalls;
+    type UserStats = UserProfile;
+    type MaxFeelessPerBlock = ConstU32<4>;
+}
+
+/// Configure the sponsorship pallet.
+/// Any account can sponsor the fees of the players it adds; root sponsors them from the game
+/// treasury.
+impl pallet_sponsorship::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_sponsorship::weights::SubstrateWeight<Runtime>;
+    type SponsorOrigin = EitherOf<
+        frame_system::EnsureSigned<AccountId>,
+        frame_system::EnsureRootWithSuccess<AccountId, GameTreasury>,
+    >;
+    type Balance = Balance;
+    type MaxAllowedCalls = ConstU32<16>;
 }
@@ -15,7 +15,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{AccountId, BalancesConfig, RuntimeGenesisConfig, SudoConfig};
+use crate::{
+	configs::{BicyIssuer, GameTreasury},
+	AccountId, AssetsConfig, BalancesConfig, RuntimeGenesisConfig, SudoConfig, BICY_ASSET_ID,
+	BICY_DECIMALS, BICY_MIN_BALANCE,
+};
 use alloc::{vec, vec::Vec};
 use frame_support::build_struct_json_patch;
 use serde_json::Value;
@@ -35,6 +39,7 @@ fn testnet_genesis(
 			balances: endowed_accounts
 				.iter()
 				.cloned()
+				.chain([GameTreasury::get()])
 				.map(|k| (k, 1u128 << 60))
 				.collect::<Vec<_>>(),
 		},
@@ -45,6 +50,15 @@ fn testnet_genesis(
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
 		},
 		sudo: SudoConfig { key: Some(root) },
+		assets: AssetsConfig {
+			assets: vec![(BICY_ASSET_ID, BicyIssuer::get(), true, BICY_MIN_BALANCE)],
+			metadata: vec![(
+				BICY_ASSET_ID,
+				b"Bicycle Token".to_vec(),
+				b"BICY".to_vec(),
+				BICY_DECIMALS
+			)],
+		},
 	})
 }
 
@@ -69,10 +69,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 100,
+    spec_version: 103,
     impl_version: 1,
     apis: apis::RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 3,
     system_version: 1,
 };
 
@@ -106,6 +106,13 @@ pub const MICRO_UNIT: Balance = 1_000_000;
 /// Existential deposit.
 pub const EXISTENTIAL_DEPOSIT: Balance = MILLI_UNIT;
 
+/// Asset id of the BICY game reward token.
+pub const BICY_ASSET_ID: u32 = 0;
+/// Smallest BICY balance an account can hold.
+pub const BICY_MIN_BALANCE: Balance = MILLI_UNIT;
+/// Decimals of the BICY game reward token.
+pub const BICY_DECIMALS: u8 = 12;
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -158,7 +165,13 @@ pub type TxExtension = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    pallet_feeless::FeelessGameplay<
+        Runtime,
+        pallet_sponsorship::ChargeSponsored<
+            Runtime,
+            pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+        >,
+    >,
     frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
     frame_system::WeightReclaim<Runtime>,
 );
@@ -174,7 +187,7 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (pallet_user_profile::migrations::v1::MigrateV0ToV1<Runtime>,);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -236,4 +249,68 @@ mod runtime {
     // User Profile pallet - adds profile metadata on top of frame_system accounts.
     #[runtime::pallet_index(9)]
     pub type UserProfile = pallet_user_profile;
+
+    // Bike pallet - bike part NFTs.
+    #[runtime::pallet_index(10)]
+    pub type Bike = pallet_bike;
+
+    // Character pallet - playable character NFTs.
+    #[runtime::pallet_index(11)]
+    pub type Character = pallet_character;
+
+    // Skill pallet - skill books attached to characters.
+    #[runtime::pallet_index(12)]
+    pub type Skill = pallet_skill;
+
+    // Race pallet - race lifecycle and rewards.
+    #[runtime::pallet_index(13)]
+    pub type Race = pallet_race;
+
+    // Tournament pallet - seeded single-elimination tournaments.
+    #[runtime::pallet_index(14)]
+    pub type Tournament = pallet_tournament;
+
+    // Ranked pallet - seasonal Elo ladder fed by race lobbies.
+    #[runtime::pallet_index(15)]
+    pub type Ranked = pallet_ranked;
+
+    // Assets pallet - fungible assets such as the BICY reward token.
+    #[runtime::pallet_index(16)]
+    pub type Assets = pallet_assets;
+
+    // Rewards pallet - pending play-to-earn rewards and claims.
+    #[runtime::pallet_index(17)]
+    pub type Rewards = pallet_rewards;
+
+    // Vesting pallet - linear release of large claimed BICY rewards.
+    #[runtime::pallet_index(18)]
+    pub type Vesting = pallet_vesting;
+
+    // Assets freezer pallet - freezes of BICY balances, such as vesting locks.
+    #[runtime::pallet_index(19)]
+    pub type AssetsFreezer = pallet_assets_freezer;
+
+    // Airdrop pallet - Merkle-root airdrops funded by the game treasury.
+    #[runtime::pallet_index(20)]
+    pub type Airdrop = pallet_airdrop;
+
+    // Quest pallet - onboarding, daily and weekly quests.
+    #[runtime::pallet_index(21)]
+    pub type Quest = pallet_quest;
+
+    // Achievements pallet - soulbound badges unlocked by player statistics.
+    #[runtime::pallet_index(22)]
+    pub type Achievements = pallet_achievements;
+
+    // Check-in pallet - daily check-ins with streak rewards.
+    #[runtime::pallet_index(23)]
+    pub type CheckIn = pallet_check_in;
+
+    // Feeless pallet - waives the fees of game calls made by players.
+    #[runtime::pallet_index(24)]
+    pub type Feeless = pallet_feeless;
+
+    // Sponsorship pallet - fees of game calls paid by sponsors.
+    #[runtime::pallet_index(25)]
+    pub type Sponsorship = pallet_sponsorship;
 }